@@ -51,7 +51,7 @@ fn large_solution_set(c: &mut Criterion) {
     ]);
 
     c.bench_function("large solution set", |b| {
-        b.iter(|| (black_box(SolutionIter::from(board).count())))
+        b.iter(|| black_box(SolutionIter::from(board).count()))
     });
 }
 
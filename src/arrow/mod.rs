@@ -0,0 +1,302 @@
+//! # Arrow sudoku
+//!
+//! An arrow consists of a circle and a shaft.  The digits along the shaft must add up to the
+//! number in the circle.  A circle may span several cells, in which case its digits are read as a
+//! single number, from the first cell to the last.  Digits may repeat along an arrow, unless the
+//! other rules of the puzzle forbid it.
+//!
+//! Each [`Arrow`] implements [`Constraint`], so arrows may be added to [`Rules`] individually.
+//! Arrows can also be collected into an [`Arrows`] value, which is a single constraint whose
+//! [`violations()`](Arrows::violations) method reports which arrows a board breaks.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let arrow = Arrow::new(&[(0, 0)], &[(1, 1), (2, 2)]).unwrap();
+//! let rules = Rules::classic().with(arrow);
+//!
+//! let solution = SolutionIter::with_rules(&Board::default(), rules).next().unwrap();
+//!
+//! assert_eq!(
+//!     solution.get_cell(0, 0),
+//!     solution.get_cell(1, 1) + solution.get_cell(2, 2)
+//! );
+//! # }
+//! ```
+//!
+//! [`Rules`]: crate::Rules
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+use super::constraint::*;
+
+/// The largest number of cells which may make up the circle of an arrow.
+pub const MAX_CIRCLE_CELLS: usize = 3;
+
+/// An error arising from an invalid arrow definition.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ArrowError {
+    /// The circle contains no cells.
+    EmptyCircle,
+
+    /// The circle contains more than [`MAX_CIRCLE_CELLS`] cells.
+    TooManyCircleCells(usize),
+
+    /// The shaft contains no cells.
+    EmptyShaft,
+
+    /// A cell lies outside the board.
+    CellOutOfRange(usize, usize),
+
+    /// A cell appears more than once on the same arrow.
+    DuplicateCell(usize, usize),
+}
+
+impl std::fmt::Display for ArrowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArrowError::EmptyCircle => write!(f, "arrow circle contains no cells"),
+            ArrowError::TooManyCircleCells(n) => {
+                write!(f, "arrow circle contains too many cells ({})", n)
+            }
+            ArrowError::EmptyShaft => write!(f, "arrow shaft contains no cells"),
+            ArrowError::CellOutOfRange(x, y) => write!(f, "cell ({}, {}) is off the board", x, y),
+            ArrowError::DuplicateCell(x, y) => {
+                write!(f, "cell ({}, {}) appears more than once on an arrow", x, y)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArrowError {}
+
+/// A circle and shaft, where the digits along the shaft add up to the number in the circle.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Arrow {
+    circle: Vec<(usize, usize)>,
+    shaft: Vec<(usize, usize)>,
+}
+
+/// The smallest and largest totals still achievable by a group of cells.
+#[derive(Clone, Copy, Debug)]
+struct Range {
+    min: usize,
+    max: usize,
+}
+
+impl Arrow {
+    /// Create an `Arrow` from the cells of its circle and its shaft.
+    ///
+    /// Cells are given as `(x, y)` pairs, using the same coordinates as [`Board::get_cell()`].  The
+    /// circle cells are listed from the most significant digit to the least significant.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// // A two-cell circle, read as a two-digit number.
+    /// assert!(Arrow::new(&[(0, 0), (1, 0)], &[(2, 1), (3, 2), (4, 3)]).is_ok());
+    ///
+    /// assert_eq!(Arrow::new(&[(0, 0)], &[]), Err(ArrowError::EmptyShaft));
+    /// # }
+    /// ```
+    pub fn new(circle: &[(usize, usize)], shaft: &[(usize, usize)]) -> Result<Self, ArrowError> {
+        if circle.is_empty() {
+            return Err(ArrowError::EmptyCircle);
+        }
+
+        if circle.len() > MAX_CIRCLE_CELLS {
+            return Err(ArrowError::TooManyCircleCells(circle.len()));
+        }
+
+        if shaft.is_empty() {
+            return Err(ArrowError::EmptyShaft);
+        }
+
+        let cells: Vec<(usize, usize)> = circle.iter().chain(shaft.iter()).copied().collect();
+
+        for (i, &(x, y)) in cells.iter().enumerate() {
+            if x >= BOARD_SIZE || y >= BOARD_SIZE {
+                return Err(ArrowError::CellOutOfRange(x, y));
+            }
+
+            if cells[..i].contains(&(x, y)) {
+                return Err(ArrowError::DuplicateCell(x, y));
+            }
+        }
+
+        Ok(Self {
+            circle: circle.to_vec(),
+            shaft: shaft.to_vec(),
+        })
+    }
+
+    /// The cells of the circle, from the most significant digit to the least significant.
+    #[inline]
+    pub fn circle(&self) -> &[(usize, usize)] {
+        &self.circle
+    }
+
+    /// The cells of the shaft.
+    #[inline]
+    pub fn shaft(&self) -> &[(usize, usize)] {
+        &self.shaft
+    }
+
+    /// The place value of the digit at position `i` of the circle.
+    fn weight(&self, i: usize) -> usize {
+        10usize.pow((self.circle.len() - 1 - i) as u32)
+    }
+
+    /// The range of numbers the circle can still hold, ignoring the cell at position `skip`.
+    fn circle_range(&self, b: &Board, skip: Option<usize>) -> Range {
+        let mut range = Range { min: 0, max: 0 };
+
+        for (i, &(x, y)) in self.circle.iter().enumerate() {
+            if Some(i) == skip {
+                continue;
+            }
+
+            let w = self.weight(i);
+
+            match b.get_cell(x, y) as usize {
+                0 => {
+                    range.min += w;
+                    range.max += BOARD_SIZE * w;
+                }
+                v => {
+                    range.min += v * w;
+                    range.max += v * w;
+                }
+            }
+        }
+
+        range
+    }
+
+    /// The range of totals the shaft can still reach, ignoring the cell at position `skip`.
+    fn shaft_range(&self, b: &Board, skip: Option<usize>) -> Range {
+        let mut range = Range { min: 0, max: 0 };
+
+        for (i, &(x, y)) in self.shaft.iter().enumerate() {
+            if Some(i) == skip {
+                continue;
+            }
+
+            match b.get_cell(x, y) as usize {
+                0 => {
+                    range.min += 1;
+                    range.max += BOARD_SIZE;
+                }
+                v => {
+                    range.min += v;
+                    range.max += v;
+                }
+            }
+        }
+
+        range
+    }
+}
+
+/// Returns a mask of the digits `d` for which `d * weight + other` can fall within `target`.
+fn digits_in_range(weight: usize, other: Range, target: Range) -> u16 {
+    (1..=BOARD_SIZE)
+        .filter(|&d| d * weight + other.min <= target.max && d * weight + other.max >= target.min)
+        .fold(0, |acc, d| acc | (1 << d))
+}
+
+impl Constraint for Arrow {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        if let Some(i) = self.shaft.iter().position(|&c| c == (x, y)) {
+            digits_in_range(
+                1,
+                self.shaft_range(board, Some(i)),
+                self.circle_range(board, None),
+            )
+        } else if let Some(i) = self.circle.iter().position(|&c| c == (x, y)) {
+            digits_in_range(
+                self.weight(i),
+                self.circle_range(board, Some(i)),
+                self.shaft_range(board, None),
+            )
+        } else {
+            ALL_CANDIDATES
+        }
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        let circle = self.circle_range(board, None);
+        let shaft = self.shaft_range(board, None);
+
+        circle.min <= shaft.max && shaft.min <= circle.max
+    }
+}
+
+/// A set of arrows.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Arrows {
+    arrows: Vec<Arrow>,
+}
+
+impl Arrows {
+    /// Create an `Arrows` value from a list of arrows.  Arrows may share cells.
+    pub fn new(arrows: Vec<Arrow>) -> Self {
+        Self { arrows }
+    }
+
+    /// The arrows in the set.
+    #[inline]
+    pub fn arrows(&self) -> &[Arrow] {
+        &self.arrows
+    }
+
+    /// Returns `true` if the set contains no arrows.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.arrows.is_empty()
+    }
+
+    /// Returns the arrows whose shafts cannot add up to their circles, given the digits placed so
+    /// far.  On a completed board, these are the arrows whose sums are wrong.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let arrows = Arrows::new(vec![
+    ///     Arrow::new(&[(0, 0)], &[(1, 1), (2, 2)]).unwrap(),
+    ///     Arrow::new(&[(4, 4)], &[(5, 5)]).unwrap(),
+    /// ]);
+    ///
+    /// let mut board = Board::default();
+    /// board.set_cell(0, 0, 5);
+    /// board.set_cell(1, 1, 2);
+    /// board.set_cell(4, 4, 6);
+    /// board.set_cell(5, 5, 7);
+    ///
+    /// assert_eq!(arrows.violations(&board), vec![&arrows.arrows()[1]]);
+    /// # }
+    /// ```
+    pub fn violations(&self, board: &Board) -> Vec<&Arrow> {
+        self.arrows.iter().filter(|a| !a.valid(board)).collect()
+    }
+}
+
+impl Constraint for Arrows {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        self.arrows
+            .iter()
+            .fold(ALL_CANDIDATES, |cs, a| cs & a.candidates(board, x, y))
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        self.arrows.iter().all(|a| a.valid(board))
+    }
+}
@@ -0,0 +1,144 @@
+use super::*;
+
+#[test]
+fn invalid_arrows_are_rejected() {
+    assert_eq!(Arrow::new(&[], &[(0, 0)]), Err(ArrowError::EmptyCircle));
+    assert_eq!(Arrow::new(&[(0, 0)], &[]), Err(ArrowError::EmptyShaft));
+    assert_eq!(
+        Arrow::new(&[(0, 0), (1, 0), (2, 0), (3, 0)], &[(4, 0)]),
+        Err(ArrowError::TooManyCircleCells(4))
+    );
+    assert_eq!(
+        Arrow::new(&[(0, 0)], &[(0, 9)]),
+        Err(ArrowError::CellOutOfRange(0, 9))
+    );
+    assert_eq!(
+        Arrow::new(&[(0, 0)], &[(1, 1), (0, 0)]),
+        Err(ArrowError::DuplicateCell(0, 0))
+    );
+}
+
+#[test]
+fn candidates_are_pruned_by_sum() {
+    let arrow = Arrow::new(&[(0, 0)], &[(1, 1), (2, 2)]).unwrap();
+    let mut board = Board::default();
+
+    // The circle must be at least 2, and each shaft cell at most 8.
+    assert_eq!(arrow.candidates(&board, 0, 0), 0b11_1111_1100);
+    assert_eq!(arrow.candidates(&board, 1, 1), 0b01_1111_1110);
+    assert_eq!(arrow.candidates(&board, 3, 3), ALL_CANDIDATES);
+
+    board.set_cell(0, 0, 5);
+    assert_eq!(arrow.candidates(&board, 1, 1), 0b00_0001_1110); // {1, ..., 4}
+
+    board.set_cell(1, 1, 3);
+    assert_eq!(arrow.candidates(&board, 2, 2), 0b00_0000_0100); // {2}
+
+    board.set_cell(0, 0, 0);
+    assert_eq!(arrow.candidates(&board, 0, 0), 0b11_1111_0000); // {4, ..., 9}
+}
+
+#[test]
+fn multi_cell_circles_are_read_as_numbers() {
+    let arrow = Arrow::new(&[(0, 0), (1, 0)], &[(2, 1), (3, 2), (4, 3)]).unwrap();
+    let mut board = Board::default();
+
+    // The shaft adds up to at most 27, so the tens digit is at most 2.
+    assert_eq!(arrow.candidates(&board, 0, 0), 0b00_0000_0110);
+
+    board.set_cell(0, 0, 2);
+    board.set_cell(1, 0, 5);
+    board.set_cell(2, 1, 9);
+    board.set_cell(3, 2, 9);
+    assert_eq!(arrow.candidates(&board, 4, 3), 0b00_1000_0000); // {7}
+
+    board.set_cell(4, 3, 7);
+    assert!(arrow.valid(&board));
+
+    board.set_cell(4, 3, 6);
+    assert!(!arrow.valid(&board));
+}
+
+#[test]
+fn violations_are_detected() {
+    let arrow = Arrow::new(&[(0, 0)], &[(1, 1), (2, 2)]).unwrap();
+    let mut board = Board::default();
+    assert!(arrow.valid(&board));
+
+    board.set_cell(0, 0, 1);
+    assert!(!arrow.valid(&board));
+
+    board.set_cell(0, 0, 9);
+    board.set_cell(1, 1, 9);
+    assert!(!arrow.valid(&board));
+
+    board.set_cell(1, 1, 4);
+    board.set_cell(2, 2, 5);
+    assert!(arrow.valid(&board));
+}
+
+#[test]
+fn violated_arrows_are_reported() {
+    let first = Arrow::new(&[(0, 0)], &[(1, 1), (2, 2)]).unwrap();
+    let second = Arrow::new(&[(8, 8)], &[(7, 7), (6, 6)]).unwrap();
+    let rules = crate::Rules::classic()
+        .with(first.clone())
+        .with(second.clone());
+
+    // Constraints are compared by their debug output, since they need not implement `PartialEq`.
+    let violations = |board: &Board| -> Vec<String> {
+        rules
+            .violations(board)
+            .iter()
+            .map(|c| format!("{:?}", c))
+            .collect()
+    };
+
+    let mut board = Board::default();
+    assert!(violations(&board).is_empty());
+
+    board.set_cell(8, 8, 1);
+    assert_eq!(violations(&board), vec![format!("{:?}", second)]);
+
+    board.set_cell(0, 0, 1);
+    assert_eq!(
+        violations(&board),
+        vec![format!("{:?}", first), format!("{:?}", second)]
+    );
+}
+
+#[test]
+fn arrow_sets_report_violated_arrows() {
+    let arrows = Arrows::new(vec![
+        Arrow::new(&[(0, 0)], &[(1, 1), (2, 2)]).unwrap(),
+        Arrow::new(&[(8, 8)], &[(7, 7), (6, 6)]).unwrap(),
+        Arrow::new(&[(0, 8), (1, 8)], &[(2, 7), (3, 6)]).unwrap(),
+    ]);
+
+    let mut board = Board::default();
+    assert!(arrows.violations(&board).is_empty());
+    assert!(arrows.valid(&board));
+
+    board.set_cell(8, 8, 1);
+    assert_eq!(arrows.violations(&board), vec![&arrows.arrows()[1]]);
+
+    board.set_cell(0, 8, 3);
+    assert_eq!(
+        arrows.violations(&board),
+        vec![&arrows.arrows()[1], &arrows.arrows()[2]]
+    );
+    assert!(!arrows.valid(&board));
+
+    // A cell on two arrows is limited by both.
+    let crossing = Arrows::new(vec![
+        Arrow::new(&[(0, 0)], &[(1, 1), (2, 2)]).unwrap(),
+        Arrow::new(&[(4, 0)], &[(2, 2)]).unwrap(),
+    ]);
+    board = Board::default();
+    board.set_cell(0, 0, 4);
+    board.set_cell(4, 0, 5);
+    assert_eq!(crossing.candidates(&board, 2, 2), 0);
+
+    board.set_cell(4, 0, 2);
+    assert_eq!(crossing.candidates(&board, 2, 2), 0b00_0000_0100); // {2}
+}
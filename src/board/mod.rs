@@ -4,10 +4,17 @@
 //!
 //! A `Board` represents a nine-by-nine grid of cells.  Each cell contains either a number from 1
 //! to 9, or a zero if the cell is unfilled.
+//!
+//! A board can be written out in any of several [`BoardStyle`]s.  Its `Display` implementation
+//! uses [`BoardStyle::Compact`], or [`BoardStyle::Grid`] for the alternate form, `{:#}`.  Other
+//! styles are chosen with [`Board::display()`].  The compact and one-line styles can be read back
+//! with [`FromStr`].
 
 #[cfg(test)]
 mod tests;
 
+use std::str::FromStr;
+
 /// The height or width of a "square" of cells within the board.  For standard sudoku puzzles, this
 /// is 3.
 pub const SQUARE_SIZE: usize = 3;
@@ -15,6 +22,76 @@ pub const SQUARE_SIZE: usize = 3;
 /// The number of cells in a row, column or square.  For standard sudoku puzzles, this is 9.
 pub const BOARD_SIZE: usize = SQUARE_SIZE * SQUARE_SIZE;
 
+/// The offsets of the orthogonal neighbours of a cell: right, down, left and up.
+pub(crate) const ORTHOGONAL: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Returns the cell `(dx, dy)` away from `(x, y)`, if it lies on the board.
+#[inline]
+pub(crate) fn offset((x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+    let nx = x.checked_add_signed(dx)?;
+    let ny = y.checked_add_signed(dy)?;
+
+    if nx < BOARD_SIZE && ny < BOARD_SIZE {
+        Some((nx, ny))
+    } else {
+        None
+    }
+}
+
+/// Returns the neighbour of `(x, y)` in direction `d`, an index into [`ORTHOGONAL`], if it lies on
+/// the board.
+#[inline]
+pub(crate) fn neighbour(x: usize, y: usize, d: usize) -> Option<(usize, usize)> {
+    offset((x, y), ORTHOGONAL[d])
+}
+
+/// Iterate over the cells reachable from `(x, y)` by the given moves.
+#[inline]
+pub(crate) fn reachable(
+    x: usize,
+    y: usize,
+    moves: &'static [(isize, isize)],
+) -> impl Iterator<Item = (usize, usize)> {
+    moves.iter().filter_map(move |&m| offset((x, y), m))
+}
+
+/// An error arising from reading a [`Board`] from text.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BoardError {
+    /// A character other than a digit, `.`, `-` or whitespace was found.
+    InvalidCharacter(char),
+
+    /// The text does not contain 81 cells.
+    WrongCellCount(usize),
+}
+
+impl std::fmt::Display for BoardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardError::InvalidCharacter(c) => write!(f, "invalid character `{}`", c),
+            BoardError::WrongCellCount(n) => write!(f, "expected 81 cells, found {}", n),
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+/// A way of writing out a [`Board`] as text.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoardStyle {
+    /// Nine lines of nine digits separated by spaces, with `-` for unfilled cells.
+    #[default]
+    Compact,
+
+    /// A grid drawn with Unicode box-drawing characters, with thick lines around each square and
+    /// blanks for unfilled cells.
+    Grid,
+
+    /// A single line of 81 digits, with `.` for unfilled cells.
+    Line,
+}
+
 /// A representation of a puzzle or solution.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct Board {
@@ -80,6 +157,140 @@ impl Board {
     pub fn set_cell(&mut self, x: usize, y: usize, value: u8) {
         self.set_cell_as_mask(x, y, 1 << value);
     }
+
+    /// Returns a value which writes out the board in the given style when formatted with `{}`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let mut board = Board::default();
+    /// board.set_cell(0, 0, 4);
+    /// board.set_cell(8, 8, 2);
+    ///
+    /// let line = board.display(BoardStyle::Line).to_string();
+    /// assert_eq!(line, format!("4{}2", ".".repeat(79)));
+    ///
+    /// let grid = board.display(BoardStyle::Grid).to_string();
+    /// assert_eq!(grid.lines().next(), Some("┏━━━┯━━━┯━━━┳━━━┯━━━┯━━━┳━━━┯━━━┯━━━┓"));
+    /// assert_eq!(grid.lines().nth(1), Some("┃ 4 │   │   ┃   │   │   ┃   │   │   ┃"));
+    /// assert_eq!(grid, format!("{:#}", board));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn display(&self, style: BoardStyle) -> StyledBoard<'_> {
+        StyledBoard { board: self, style }
+    }
+
+    /// Returns the character used for the cell at column `x` and row `y`, or `empty` if the cell
+    /// is unfilled.  Values above 9, which `set_cell` does not reject, are shown as `?`.
+    #[inline]
+    pub(crate) fn cell_char(&self, x: usize, y: usize, empty: char) -> char {
+        match self.get_cell(x, y) {
+            0 => empty,
+            v => char::from_digit(v as u32, 10).unwrap_or('?'),
+        }
+    }
+
+    fn fmt_compact(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = String::new();
+
+        for y in 0..BOARD_SIZE {
+            if y != 0 {
+                s.push('\n');
+            }
+
+            for x in 0..BOARD_SIZE {
+                if x != 0 {
+                    s.push(' ');
+                }
+
+                s.push(self.cell_char(x, y, '-'));
+            }
+        }
+
+        write!(f, "{}", s)
+    }
+
+    fn fmt_grid(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The characters used for the left edge, the lines within a cell, the line between cells in
+        // the same square, the line between squares and the right edge, for each kind of line.
+        const TOP: [char; 5] = ['┏', '━', '┯', '┳', '┓'];
+        const THIN: [char; 5] = ['┠', '─', '┼', '╂', '┨'];
+        const THICK: [char; 5] = ['┣', '━', '┿', '╋', '┫'];
+        const BOTTOM: [char; 5] = ['┗', '━', '┷', '┻', '┛'];
+
+        fn rule(s: &mut String, chars: [char; 5]) {
+            s.push(chars[0]);
+
+            for x in 0..BOARD_SIZE {
+                if x != 0 {
+                    s.push(if x % SQUARE_SIZE == 0 {
+                        chars[3]
+                    } else {
+                        chars[2]
+                    });
+                }
+
+                s.extend([chars[1]; 3]);
+            }
+
+            s.push(chars[4]);
+        }
+
+        let mut s = String::new();
+        rule(&mut s, TOP);
+
+        for y in 0..BOARD_SIZE {
+            if y != 0 {
+                s.push('\n');
+                rule(&mut s, if y % SQUARE_SIZE == 0 { THICK } else { THIN });
+            }
+
+            s.push('\n');
+
+            for x in 0..BOARD_SIZE {
+                s.push(if x % SQUARE_SIZE == 0 { '┃' } else { '│' });
+                s.push(' ');
+                s.push(self.cell_char(x, y, ' '));
+                s.push(' ');
+            }
+
+            s.push('┃');
+        }
+
+        s.push('\n');
+        rule(&mut s, BOTTOM);
+
+        write!(f, "{}", s)
+    }
+
+    fn fmt_line(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s: String = (0..BOARD_SIZE * BOARD_SIZE)
+            .map(|i| self.cell_char(i % BOARD_SIZE, i / BOARD_SIZE, '.'))
+            .collect();
+
+        write!(f, "{}", s)
+    }
+}
+
+/// A [`Board`] together with the [`BoardStyle`] in which to write it out, as returned by
+/// [`Board::display()`].
+#[derive(Clone, Copy, Debug)]
+pub struct StyledBoard<'a> {
+    board: &'a Board,
+    style: BoardStyle,
+}
+
+impl std::fmt::Display for StyledBoard<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.style {
+            BoardStyle::Compact => self.board.fmt_compact(f),
+            BoardStyle::Grid => self.board.fmt_grid(f),
+            BoardStyle::Line => self.board.fmt_line(f),
+        }
+    }
 }
 
 /// Construct a `Board` from a 2D array.
@@ -116,29 +327,56 @@ impl Default for Board {
     }
 }
 
-impl std::fmt::Display for Board {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut s = String::new();
+impl FromStr for Board {
+    type Err = BoardError;
 
-        for y in 0..BOARD_SIZE {
-            if y != 0 {
-                s.push('\n');
-            }
+    /// Read a board from text containing 81 cells, each a digit from 1 to 9 or an unfilled cell
+    /// written as `0`, `.` or `-`.  Whitespace is ignored.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let board: Board = format!("4{}2", ".".repeat(79)).parse().unwrap();
+    /// assert_eq!(board.get_cell(8, 8), 2);
+    ///
+    /// assert_eq!(board.to_string().parse(), Ok(board));
+    /// assert_eq!("123".parse::<Board>(), Err(BoardError::WrongCellCount(3)));
+    /// # }
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut board = Board::default();
+        let mut count = 0;
 
-            for x in 0..BOARD_SIZE {
-                if x != 0 {
-                    s.push(' ');
-                }
+        for c in s.chars().filter(|c| !c.is_whitespace()) {
+            let value = match c {
+                '0' | '.' | '-' => 0,
+                '1'..='9' => c as u8 - b'0',
+                c => return Err(BoardError::InvalidCharacter(c)),
+            };
 
-                let v = self.get_cell(x, y);
-                s.push(if v != 0 {
-                    char::from_digit(v as u32, 10).unwrap()
-                } else {
-                    '-'
-                });
+            if count < BOARD_SIZE * BOARD_SIZE {
+                board.set_cell(count % BOARD_SIZE, count / BOARD_SIZE, value);
             }
+
+            count += 1;
         }
 
-        write!(f, "{}", s)
+        if count != BOARD_SIZE * BOARD_SIZE {
+            return Err(BoardError::WrongCellCount(count));
+        }
+
+        Ok(board)
+    }
+}
+
+impl std::fmt::Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            self.fmt_grid(f)
+        } else {
+            self.fmt_compact(f)
+        }
     }
 }
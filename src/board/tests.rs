@@ -119,3 +119,111 @@ fn string_rep_of_board_is_correct() {
 
     assert_eq!(board.to_string(), str_rep);
 }
+
+#[test]
+fn display_styles() {
+    let board = Board::from(&[
+        [1, 0, 0, 4, 0, 0, 7, 0, 0], // row 1
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 2
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+        [4, 0, 0, 7, 0, 0, 1, 0, 0], // row 4
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 5
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [7, 0, 0, 1, 0, 0, 4, 0, 0], // row 7
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 9], // row 9
+    ]);
+
+    assert_eq!(
+        board.display(BoardStyle::Compact).to_string(),
+        board.to_string()
+    );
+
+    assert_eq!(
+        board.display(BoardStyle::Line).to_string(),
+        "1..4..7..\
+         .........\
+         .........\
+         4..7..1..\
+         .........\
+         .........\
+         7..1..4..\
+         .........\
+         ........9"
+    );
+
+    let str_rep = "┏━━━┯━━━┯━━━┳━━━┯━━━┯━━━┳━━━┯━━━┯━━━┓\n\
+                   ┃ 1 │   │   ┃ 4 │   │   ┃ 7 │   │   ┃\n\
+                   ┠───┼───┼───╂───┼───┼───╂───┼───┼───┨\n\
+                   ┃   │   │   ┃   │   │   ┃   │   │   ┃\n\
+                   ┠───┼───┼───╂───┼───┼───╂───┼───┼───┨\n\
+                   ┃   │   │   ┃   │   │   ┃   │   │   ┃\n\
+                   ┣━━━┿━━━┿━━━╋━━━┿━━━┿━━━╋━━━┿━━━┿━━━┫\n\
+                   ┃ 4 │   │   ┃ 7 │   │   ┃ 1 │   │   ┃\n\
+                   ┠───┼───┼───╂───┼───┼───╂───┼───┼───┨\n\
+                   ┃   │   │   ┃   │   │   ┃   │   │   ┃\n\
+                   ┠───┼───┼───╂───┼───┼───╂───┼───┼───┨\n\
+                   ┃   │   │   ┃   │   │   ┃   │   │   ┃\n\
+                   ┣━━━┿━━━┿━━━╋━━━┿━━━┿━━━╋━━━┿━━━┿━━━┫\n\
+                   ┃ 7 │   │   ┃ 1 │   │   ┃ 4 │   │   ┃\n\
+                   ┠───┼───┼───╂───┼───┼───╂───┼───┼───┨\n\
+                   ┃   │   │   ┃   │   │   ┃   │   │   ┃\n\
+                   ┠───┼───┼───╂───┼───┼───╂───┼───┼───┨\n\
+                   ┃   │   │   ┃   │   │   ┃   │   │ 9 ┃\n\
+                   ┗━━━┷━━━┷━━━┻━━━┷━━━┷━━━┻━━━┷━━━┷━━━┛";
+
+    assert_eq!(board.display(BoardStyle::Grid).to_string(), str_rep);
+    assert_eq!(format!("{:#}", board), str_rep);
+}
+
+#[test]
+fn parse_board() {
+    let board = Board::from(&[
+        [1, 0, 0, 4, 0, 0, 7, 0, 0], // row 1
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 2
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+        [4, 0, 0, 7, 0, 0, 1, 0, 0], // row 4
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 5
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [7, 0, 0, 1, 0, 0, 4, 0, 0], // row 7
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 9], // row 9
+    ]);
+
+    assert_eq!(board.to_string().parse(), Ok(board));
+    assert_eq!(
+        board.display(BoardStyle::Line).to_string().parse(),
+        Ok(board)
+    );
+    assert_eq!(
+        board
+            .display(BoardStyle::Line)
+            .to_string()
+            .replace('.', "0")
+            .parse(),
+        Ok(board)
+    );
+
+    assert_eq!("x".parse::<Board>(), Err(BoardError::InvalidCharacter('x')));
+    assert_eq!(
+        "1".repeat(82).parse::<Board>(),
+        Err(BoardError::WrongCellCount(82))
+    );
+}
+
+#[test]
+fn offsets_stay_on_the_board() {
+    assert_eq!(offset((4, 4), (2, -1)), Some((6, 3)));
+    assert_eq!(offset((0, 4), (-1, 0)), None);
+    assert_eq!(offset((4, 8), (0, 1)), None);
+
+    assert_eq!(neighbour(0, 0, 0), Some((1, 0)));
+    assert_eq!(neighbour(0, 0, 1), Some((0, 1)));
+    assert_eq!(neighbour(0, 0, 2), None);
+    assert_eq!(neighbour(0, 0, 3), None);
+
+    assert_eq!(
+        reachable(8, 0, &ORTHOGONAL).collect::<Vec<_>>(),
+        vec![(8, 1), (7, 0)]
+    );
+}
@@ -0,0 +1,605 @@
+//! # Printable puzzle booklets
+//!
+//! A [`Booklet`] lays out a collection of puzzles as a PDF document, ready for printing.  The
+//! puzzles are placed several to a page, in a grid of slots, each labelled with the puzzle's
+//! number, title and difficulty.  They are followed by an answer key, in which the solutions are
+//! printed at a smaller size, in the same order.
+//!
+//! The PDF is generated entirely by this crate.  It uses only the Helvetica font which every PDF
+//! reader provides, so no fonts are embedded; text outside the Latin-1 character set is printed
+//! as `?`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let puzzle: Board = "
+//!     .2....... ...6....3 .74.8....
+//!     .....3..2 .8..4..1. 6..5.....
+//!     ....1.78. 5....9... .......4."
+//!     .parse()
+//!     .unwrap();
+//!
+//! let mut booklet = Booklet::new("Weekend puzzles").layout(1, 2);
+//! booklet
+//!     .add(BookletEntry::new("Saturday", &puzzle).with_difficulty("Easy"))
+//!     .unwrap();
+//!
+//! let pdf = booklet.to_pdf();
+//! assert!(pdf.starts_with(b"%PDF-1.4"));
+//!
+//! // One page of puzzles, and one for the answers.
+//! assert_eq!(booklet.page_count(), 2);
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+use super::solver::*;
+use super::svg::num;
+
+/// The space left blank around the edge of each page, in points.
+const MARGIN: f64 = 48.0;
+
+/// The height of the heading at the top of each page, in points.
+const HEADER: f64 = 36.0;
+
+/// The height of the page number at the bottom of each page, in points.
+const FOOTER: f64 = 24.0;
+
+/// The height of the label above each puzzle, in points.
+const LABEL: f64 = 18.0;
+
+/// The smallest grid a layout may leave room for, in points.
+const MIN_GRID: f64 = 36.0;
+
+/// The widths of the printable ASCII characters in Helvetica, in thousandths of the font size,
+/// starting with the space.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278,
+    278, // ' '..'/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584,
+    556, // '0'..'?'
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722,
+    778, // '@'..'O'
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469,
+    556, // 'P'..'_'
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556,
+    556, // '`'..'o'
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // 'p'..'~'
+];
+
+/// The size of a page.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PageSize {
+    /// ISO A4, 210 × 297 mm.
+    #[default]
+    A4,
+
+    /// US Letter, 8½ × 11 inches.
+    Letter,
+}
+
+impl PageSize {
+    /// The width and height of the page, in points.
+    pub fn dimensions(self) -> (f64, f64) {
+        match self {
+            PageSize::A4 => (595.28, 841.89),
+            PageSize::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+/// An error arising when a puzzle is added to a booklet.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BookletError {
+    /// The puzzle with the given number, counting from 1, has no solution.
+    NoSolution(usize),
+
+    /// The solution given for the puzzle with the given number is incomplete, or does not agree
+    /// with the puzzle's givens.
+    InvalidSolution(usize),
+}
+
+impl std::fmt::Display for BookletError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BookletError::NoSolution(n) => write!(f, "puzzle {} has no solution", n),
+            BookletError::InvalidSolution(n) => {
+                write!(f, "the solution to puzzle {} does not fit its givens", n)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BookletError {}
+
+/// A puzzle to be printed in a [`Booklet`].
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BookletEntry {
+    /// The title printed above the puzzle, after its number.  May be empty.
+    pub title: String,
+
+    /// The difficulty printed above the puzzle, such as "Easy" or "Diabolical".
+    pub difficulty: Option<String>,
+
+    /// The givens of the puzzle.
+    pub puzzle: Board,
+
+    /// The solution printed in the answer key.  If this is `None`, the puzzle is solved with the
+    /// classic rules when it is added to a booklet.  Variant puzzles should be given their
+    /// solutions.
+    pub solution: Option<Board>,
+}
+
+impl BookletEntry {
+    /// Create a `BookletEntry` for a puzzle, with no difficulty or solution.
+    pub fn new(title: &str, puzzle: &Board) -> Self {
+        Self {
+            title: title.to_string(),
+            difficulty: None,
+            puzzle: *puzzle,
+            solution: None,
+        }
+    }
+
+    /// Set the difficulty label of the puzzle.
+    pub fn with_difficulty(mut self, difficulty: &str) -> Self {
+        self.difficulty = Some(difficulty.to_string());
+        self
+    }
+
+    /// Set the solution of the puzzle.
+    pub fn with_solution(mut self, solution: &Board) -> Self {
+        self.solution = Some(*solution);
+        self
+    }
+
+    /// Returns the label printed above the puzzle: its number followed by its title.
+    fn label(&self, number: usize) -> String {
+        if self.title.is_empty() {
+            number.to_string()
+        } else {
+            format!("{}. {}", number, self.title)
+        }
+    }
+}
+
+/// A collection of puzzles to be printed, with their answers.
+#[derive(Clone, Debug)]
+pub struct Booklet {
+    title: String,
+    page_size: PageSize,
+    layout: (usize, usize),
+    answer_layout: (usize, usize),
+    entries: Vec<BookletEntry>,
+}
+
+impl Booklet {
+    /// Create an empty `Booklet` whose title is printed at the top of each page of puzzles.
+    ///
+    /// By default, pages are A4, with four puzzles and nine answers to a page.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            page_size: PageSize::default(),
+            layout: (2, 2),
+            answer_layout: (3, 3),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Set the size of the pages.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the puzzle or answer layout leaves too little room for a grid on pages of
+    /// this size.
+    pub fn page_size(mut self, size: PageSize) -> Self {
+        assert!(
+            fits(size, self.layout) && fits(size, self.answer_layout),
+            "the layouts do not fit on pages of this size"
+        );
+        self.page_size = size;
+        self
+    }
+
+    /// Place the puzzles on each page in the given number of columns and rows.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if either is zero, or if each puzzle would have too little room for its label and
+    /// a grid half an inch across on the current page size.  An A4 page holds up to twelve
+    /// columns and eleven rows.
+    pub fn layout(mut self, columns: usize, rows: usize) -> Self {
+        assert!(
+            columns > 0 && rows > 0,
+            "a page must hold at least one puzzle"
+        );
+        assert!(
+            fits(self.page_size, (columns, rows)),
+            "too many puzzles to fit on a page"
+        );
+        self.layout = (columns, rows);
+        self
+    }
+
+    /// Place the answers on each page of the answer key in the given number of columns and rows.
+    ///
+    /// ## Panics
+    ///
+    /// Panics under the same conditions as [`layout()`](Self::layout).
+    pub fn answer_layout(mut self, columns: usize, rows: usize) -> Self {
+        assert!(
+            columns > 0 && rows > 0,
+            "a page must hold at least one answer"
+        );
+        assert!(
+            fits(self.page_size, (columns, rows)),
+            "too many answers to fit on a page"
+        );
+        self.answer_layout = (columns, rows);
+        self
+    }
+
+    /// Add a puzzle to the end of the booklet.
+    ///
+    /// If the entry has no solution, the puzzle is solved with the classic rules, and an error
+    /// is returned if it has none.  A solution which was given must be complete, and must agree
+    /// with the givens.
+    pub fn add(&mut self, mut entry: BookletEntry) -> Result<(), BookletError> {
+        let number = self.entries.len() + 1;
+
+        match entry.solution {
+            Some(solution) => {
+                let fits = (0..BOARD_SIZE * BOARD_SIZE).all(|i| {
+                    let (x, y) = (i % BOARD_SIZE, i / BOARD_SIZE);
+                    let (given, solved) = (entry.puzzle.get_cell(x, y), solution.get_cell(x, y));
+
+                    solved != 0 && (given == 0 || given == solved)
+                });
+
+                if !fits {
+                    return Err(BookletError::InvalidSolution(number));
+                }
+            }
+            None => {
+                entry.solution =
+                    Some(solve(&entry.puzzle).ok_or(BookletError::NoSolution(number))?);
+            }
+        }
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// The puzzles in the booklet, in order.
+    #[inline]
+    pub fn entries(&self) -> &[BookletEntry] {
+        &self.entries
+    }
+
+    /// The number of pages needed for the puzzles, followed by the answer key.
+    pub fn page_count(&self) -> usize {
+        let pages = |(columns, rows): (usize, usize)| self.entries.len().div_ceil(columns * rows);
+
+        pages(self.layout) + pages(self.answer_layout)
+    }
+
+    /// Lay out the booklet and return it as a PDF document.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let mut pages = Vec::new();
+        let per_page = self.layout.0 * self.layout.1;
+        let answers_per_page = self.answer_layout.0 * self.answer_layout.1;
+
+        for (i, chunk) in self.entries.chunks(per_page).enumerate() {
+            let mut page = Page::new(self.page_size);
+            page.heading(&self.title);
+
+            for (j, entry) in chunk.iter().enumerate() {
+                let number = i * per_page + j + 1;
+                let slot = page.slot(self.layout, j);
+
+                page.puzzle(
+                    slot,
+                    &entry.label(number),
+                    entry.difficulty.as_deref(),
+                    &entry.puzzle,
+                );
+            }
+
+            pages.push(page);
+        }
+
+        for (i, chunk) in self.entries.chunks(answers_per_page).enumerate() {
+            let mut page = Page::new(self.page_size);
+            page.heading(&format!("{}: Answers", self.title));
+
+            for (j, entry) in chunk.iter().enumerate() {
+                let number = i * answers_per_page + j + 1;
+                let slot = page.slot(self.answer_layout, j);
+
+                // Entries always have a solution once added.
+                let solution = entry.solution.unwrap_or(entry.puzzle);
+                page.puzzle(slot, &entry.label(number), None, &solution);
+            }
+
+            pages.push(page);
+        }
+
+        let count = pages.len();
+
+        for (i, page) in pages.iter_mut().enumerate() {
+            page.footer(&format!("Page {} of {}", i + 1, count));
+        }
+
+        write_pdf(&self.title, self.page_size, &pages)
+    }
+}
+
+/// Returns the width and height of each slot of a page divided into the given number of columns
+/// and rows.
+fn slot_size((width, height): (f64, f64), (columns, rows): (usize, usize)) -> (f64, f64) {
+    (
+        (width - 2.0 * MARGIN) / columns as f64,
+        (height - 2.0 * MARGIN - HEADER - FOOTER) / rows as f64,
+    )
+}
+
+/// Returns the size of the grid drawn in a slot of the given width and height, leaving a gap
+/// between neighbouring slots and room for the label.
+fn grid_size((width, height): (f64, f64)) -> f64 {
+    let gap = width.min(height) / 10.0;
+    (width - gap).min(height - gap - LABEL)
+}
+
+/// Returns whether a page of the given size divided into the given number of columns and rows
+/// leaves room for a grid of at least [`MIN_GRID`] in each slot.
+fn fits(size: PageSize, layout: (usize, usize)) -> bool {
+    grid_size(slot_size(size.dimensions(), layout)) >= MIN_GRID
+}
+
+/// The content of a page being laid out, as PDF drawing operators.
+struct Page {
+    size: (f64, f64),
+    content: String,
+}
+
+impl Page {
+    fn new(size: PageSize) -> Self {
+        Self {
+            size: size.dimensions(),
+            content: String::new(),
+        }
+    }
+
+    /// Returns the left, top, width and height of slot `index` of a page divided into the given
+    /// number of columns and rows, measured in points from the top left of the page.
+    fn slot(&self, layout: (usize, usize), index: usize) -> (f64, f64, f64, f64) {
+        let columns = layout.0;
+        let (slot_width, slot_height) = slot_size(self.size, layout);
+
+        (
+            MARGIN + (index % columns) as f64 * slot_width,
+            MARGIN + HEADER + (index / columns) as f64 * slot_height,
+            slot_width,
+            slot_height,
+        )
+    }
+
+    /// Write `text` with its left end at `x` and its baseline at `y`, measured from the top left
+    /// of the page.
+    fn text(&mut self, x: f64, y: f64, size: f64, text: &str) {
+        self.content.push_str(&format!(
+            "BT /F1 {} Tf {} {} Td {} Tj ET\n",
+            num(size),
+            num(x),
+            num(self.size.1 - y),
+            pdf_string(text)
+        ));
+    }
+
+    /// Write `text` centred on `x`.
+    fn centred_text(&mut self, x: f64, y: f64, size: f64, text: &str) {
+        self.text(x - text_width(text, size) / 2.0, y, size, text);
+    }
+
+    /// Draw a line from `(x1, y1)` to `(x2, y2)`, measured from the top left of the page.
+    fn line(&mut self, width: f64, (x1, y1): (f64, f64), (x2, y2): (f64, f64)) {
+        self.content.push_str(&format!(
+            "{} w {} {} m {} {} l S\n",
+            num(width),
+            num(x1),
+            num(self.size.1 - y1),
+            num(x2),
+            num(self.size.1 - y2)
+        ));
+    }
+
+    fn heading(&mut self, title: &str) {
+        let centre = self.size.0 / 2.0;
+        self.centred_text(centre, MARGIN + HEADER / 2.0, 18.0, title);
+    }
+
+    fn footer(&mut self, text: &str) {
+        let (centre, bottom) = (self.size.0 / 2.0, self.size.1 - MARGIN);
+        self.centred_text(centre, bottom, 10.0, text);
+    }
+
+    /// Draw a labelled board in the given slot, as large as will fit.
+    fn puzzle(
+        &mut self,
+        (left, top, width, height): (f64, f64, f64, f64),
+        label: &str,
+        difficulty: Option<&str>,
+        board: &Board,
+    ) {
+        let grid = grid_size((width, height));
+        let left = left + (width - grid) / 2.0;
+        let top = top + (height - grid - LABEL) / 2.0;
+        let label_size = (LABEL * 0.6).min(grid / 12.0);
+
+        self.text(left, top + LABEL * 0.6, label_size, label);
+
+        if let Some(difficulty) = difficulty {
+            let x = left + grid - text_width(difficulty, label_size);
+            self.text(x, top + LABEL * 0.6, label_size, difficulty);
+        }
+
+        self.grid(left, top + LABEL, grid, board);
+    }
+
+    /// Draw a board with its top left corner at `(left, top)`.
+    fn grid(&mut self, left: f64, top: f64, size: f64, board: &Board) {
+        let cell = size / BOARD_SIZE as f64;
+        let (thin, thick) = (size / 400.0, size / 120.0);
+
+        for i in 1..BOARD_SIZE {
+            let at = i as f64 * cell;
+            let width = if i % SQUARE_SIZE == 0 { thick } else { thin };
+
+            self.line(width, (left + at, top), (left + at, top + size));
+            self.line(width, (left, top + at), (left + size, top + at));
+        }
+
+        self.content.push_str(&format!(
+            "{} w {} {} {} {} re S\n",
+            num(thick),
+            num(left),
+            num(self.size.1 - top - size),
+            num(size),
+            num(size)
+        ));
+
+        // Digits are centred in their cells; the height of a Helvetica digit is about 0.7 of
+        // the font size.
+        let font = cell * 0.65;
+
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let v = board.get_cell(x, y);
+
+                if v != 0 {
+                    let cx = left + (x as f64 + 0.5) * cell;
+                    let baseline = top + (y as f64 + 0.5) * cell + font * 0.35;
+                    self.centred_text(cx, baseline, font, &v.to_string());
+                }
+            }
+        }
+    }
+}
+
+/// Write out a PDF document with the given pages.
+fn write_pdf(title: &str, size: PageSize, pages: &[Page]) -> Vec<u8> {
+    let (width, height) = size.dimensions();
+
+    // The catalog, page tree, font and document information come first, followed by each page
+    // and its content stream.
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 5 + 2 * i).collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        format!(
+            "<< /Title {} /Producer (sudoku-solver) >>",
+            pdf_string(title)
+        ),
+    ];
+
+    for (page, id) in pages.iter().zip(&page_ids) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            num(width),
+            num(height),
+            id + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            page.content.len(),
+            page.content
+        ));
+    }
+
+    let mut out = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, object));
+    }
+
+    let xref = out.len();
+    out.push_str(&format!(
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    ));
+
+    for offset in offsets {
+        out.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+
+    out.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R /Info 4 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    ));
+
+    out.into_bytes()
+}
+
+/// Returns the WinAnsi code for a character, or `?` for a character it cannot encode.
+fn win_ansi(c: char) -> u8 {
+    match c as u32 {
+        0x20..=0x7e | 0xa0..=0xff => c as u8,
+        _ => b'?',
+    }
+}
+
+/// Returns the width of `text` printed in Helvetica at the given size, in points.
+fn text_width(text: &str, size: f64) -> f64 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match win_ansi(c) {
+            b @ 0x20..=0x7e => HELVETICA_WIDTHS[(b - 0x20) as usize] as u32,
+            // An approximation for accented letters and other Latin-1 symbols.
+            _ => 556,
+        })
+        .sum();
+
+    units as f64 * size / 1000.0
+}
+
+/// Write `text` as a PDF string literal.  Characters outside printable ASCII are written as
+/// octal escapes, so that the content of each page is plain ASCII.
+fn pdf_string(text: &str) -> String {
+    let mut s = String::from("(");
+
+    for c in text.chars() {
+        match win_ansi(c) {
+            b @ (b'(' | b')' | b'\\') => {
+                s.push('\\');
+                s.push(b as char);
+            }
+            b @ 0x20..=0x7e => s.push(b as char),
+            b => s.push_str(&format!("\\{:03o}", b)),
+        }
+    }
+
+    s.push(')');
+    s
+}
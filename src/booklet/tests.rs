@@ -0,0 +1,209 @@
+use super::*;
+
+fn puzzle() -> Board {
+    "
+    .2....... ...6....3 .74.8....
+    .....3..2 .8..4..1. 6..5.....
+    ....1.78. 5....9... .......4.
+    "
+    .parse()
+    .unwrap()
+}
+
+/// Check the cross-reference table of a PDF document, and return the text of its objects.
+fn objects(pdf: &[u8]) -> Vec<String> {
+    let text = std::str::from_utf8(pdf).unwrap();
+
+    assert!(text.starts_with("%PDF-1.4\n"));
+    assert!(text.ends_with("%%EOF\n"));
+
+    let startxref = text.rfind("startxref\n").unwrap();
+    let xref: usize = text[startxref + 10..]
+        .lines()
+        .next()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let mut lines = text[xref..].lines();
+
+    assert_eq!(lines.next(), Some("xref"));
+
+    let count: usize = lines.next().unwrap()[2..].parse().unwrap();
+    assert_eq!(lines.next(), Some("0000000000 65535 f "));
+
+    (1..count)
+        .map(|id| {
+            let offset: usize = lines.next().unwrap()[..10].parse().unwrap();
+            let object = &text[offset..];
+            let header = format!("{} 0 obj\n", id);
+
+            assert!(object.starts_with(&header), "object {} is misplaced", id);
+            object[header.len()..object.find("\nendobj\n").unwrap()].to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn lays_out_pages() {
+    let mut booklet = Booklet::new("Puzzles");
+
+    for i in 0..5 {
+        booklet
+            .add(BookletEntry::new(&format!("Puzzle {}", i), &puzzle()))
+            .unwrap();
+    }
+
+    // Two pages of four puzzles, and one of nine answers.
+    assert_eq!(booklet.page_count(), 3);
+
+    let objects = objects(&booklet.to_pdf());
+    let contents: Vec<&String> = objects.iter().filter(|o| o.contains("stream\n")).collect();
+
+    assert_eq!(objects.len(), 4 + 2 * 3);
+    assert!(objects[1].contains("/Count 3"));
+    assert!(objects[4].contains("/MediaBox [0 0 595.28 841.89]"));
+    assert_eq!(contents.len(), 3);
+
+    for stream in &contents {
+        let length: usize = stream["<< /Length ".len()..stream.find(" >>").unwrap()]
+            .parse()
+            .unwrap();
+        let start = stream.find("stream\n").unwrap() + 7;
+        assert_eq!(stream.len() - start - "endstream".len(), length);
+    }
+
+    assert!(contents[0].contains("(1. Puzzle 0) Tj"));
+    assert!(contents[0].contains("(4. Puzzle 3) Tj"));
+    assert!(!contents[0].contains("(5. Puzzle 4) Tj"));
+    assert!(contents[1].contains("(5. Puzzle 4) Tj"));
+    assert!(contents[1].contains("(Page 2 of 3) Tj"));
+    assert!(contents[2].contains("(Puzzles: Answers) Tj"));
+    assert!(contents[2].contains("(5. Puzzle 4) Tj"));
+
+    // The first page shows only the givens, and the answers every digit.
+    let digits = |s: &str| {
+        (1..=9)
+            .map(|d| s.matches(&format!("({}) Tj", d)).count())
+            .sum::<usize>()
+    };
+    assert_eq!(digits(contents[1]), 19);
+    assert_eq!(digits(contents[2]), 5 * 81);
+}
+
+#[test]
+fn sets_page_size_and_layout() {
+    let mut booklet = Booklet::new("Letter")
+        .page_size(PageSize::Letter)
+        .layout(1, 1)
+        .answer_layout(2, 1);
+
+    for _ in 0..3 {
+        booklet.add(BookletEntry::new("", &puzzle())).unwrap();
+    }
+
+    assert_eq!(booklet.page_count(), 5);
+
+    let objects = objects(&booklet.to_pdf());
+
+    assert!(objects[1].contains("/Count 5"));
+    assert!(objects[4].contains("/MediaBox [0 0 612 792]"));
+    assert!(objects[5].contains("(1) Tj"));
+    assert!(objects[3].contains("/Title (Letter)"));
+}
+
+#[test]
+fn layouts_must_leave_room_for_a_grid() {
+    let mut booklet = Booklet::new("Small").layout(12, 11).answer_layout(12, 11);
+    booklet.add(BookletEntry::new("", &puzzle())).unwrap();
+
+    // No size or position on the page is negative.
+    let objects = objects(&booklet.to_pdf());
+    assert!(!objects.iter().any(|o| o.contains(" -")));
+
+    assert!(fits(PageSize::A4, (1, 11)));
+    assert!(!fits(PageSize::A4, (1, 12)));
+    assert!(!fits(PageSize::A4, (13, 1)));
+    assert!(!fits(PageSize::Letter, (1, 11)));
+}
+
+#[test]
+#[should_panic]
+fn layouts_with_too_many_rows_are_rejected() {
+    let _ = Booklet::new("Small").layout(1, 39);
+}
+
+#[test]
+#[should_panic]
+fn page_sizes_must_fit_the_layouts() {
+    let _ = Booklet::new("Small")
+        .answer_layout(1, 11)
+        .page_size(PageSize::Letter);
+}
+
+#[test]
+fn labels_difficulty() {
+    let mut booklet = Booklet::new("Puzzles").layout(1, 1);
+    booklet
+        .add(BookletEntry::new("Monday", &puzzle()).with_difficulty("Hard"))
+        .unwrap();
+
+    let objects = objects(&booklet.to_pdf());
+
+    assert!(objects[5].contains("(1. Monday) Tj"));
+    assert!(objects[5].contains("(Hard) Tj"));
+
+    // The difficulty is only shown with the puzzle, not its answer.
+    assert!(!objects[7].contains("(Hard) Tj"));
+}
+
+#[test]
+fn checks_solutions() {
+    let mut booklet = Booklet::new("Puzzles");
+    let solution = solve(&puzzle()).unwrap();
+
+    booklet
+        .add(BookletEntry::new("", &puzzle()).with_solution(&solution))
+        .unwrap();
+    assert_eq!(booklet.entries()[0].solution, Some(solution));
+
+    booklet.add(BookletEntry::new("", &puzzle())).unwrap();
+    assert_eq!(booklet.entries()[1].solution, Some(solution));
+
+    let mut wrong = solution;
+    wrong.set_cell(1, 0, 5);
+    assert_eq!(
+        booklet.add(BookletEntry::new("", &puzzle()).with_solution(&wrong)),
+        Err(BookletError::InvalidSolution(3))
+    );
+
+    let mut partial = solution;
+    partial.set_cell(0, 0, 0);
+    assert_eq!(
+        booklet.add(BookletEntry::new("", &puzzle()).with_solution(&partial)),
+        Err(BookletError::InvalidSolution(3))
+    );
+
+    let mut impossible = puzzle();
+    impossible.set_cell(0, 0, 2);
+    assert_eq!(
+        booklet.add(BookletEntry::new("", &impossible)),
+        Err(BookletError::NoSolution(3))
+    );
+
+    assert_eq!(booklet.entries().len(), 2);
+}
+
+#[test]
+fn writes_strings() {
+    assert_eq!(pdf_string("Plain"), "(Plain)");
+    assert_eq!(pdf_string(r"(a\b)"), r"(\(a\\b\))");
+    assert_eq!(pdf_string("Café"), r"(Caf\351)");
+    assert_eq!(pdf_string("数独"), "(??)");
+}
+
+#[test]
+fn measures_text() {
+    assert_eq!(text_width("1", 10.0), 5.56);
+    assert_eq!(text_width("Wi", 1000.0), 944.0 + 222.0);
+    assert_eq!(text_width("é", 1000.0), 556.0);
+}
@@ -0,0 +1,512 @@
+//! # Candidate grids
+//!
+//! A [`Board`] holds at most one digit per cell.  A [`CandidateGrid`] instead holds the set of
+//! digits each cell may still take, as pencilmarks do on paper.  A grid is usually built from a
+//! board, with each empty cell given the digits its row, column and square allow (or those
+//! allowed by some other [`Rules`]), and then narrowed down by eliminating candidates.
+//!
+//! Candidate sets are represented as masks, in which bit `n` is set if digit `n` is a candidate,
+//! as elsewhere in the crate.
+//!
+//! A `CandidateGrid` is also a [`Constraint`], which only permits each cell's candidates.  This
+//! is how Sukaku puzzles, which are given as a full grid of pencilmarks rather than as clues, are
+//! solved.  Such puzzles are usually written in a 729-character format, with nine characters per
+//! cell: the `n`th character is the digit `n` if it is a candidate, or `.` (or `0`) if it is not.
+//! `CandidateGrid` implements [`FromStr`] and [`Display`](std::fmt::Display) for this format.
+//!
+//! For reading by people, the alternate form of `Display` (`{:#}`) draws the grid as pencilmarks
+//! in the style of HoDoKu and SudokuWiki, with each cell's candidates in a three-by-three block.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let mut board = Board::default();
+//! board.set_cell(0, 0, 5);
+//!
+//! let mut grid = CandidateGrid::from(&board);
+//! assert_eq!(grid.candidates(0, 0), 1 << 5);
+//! assert!(!grid.contains(8, 0, 5));
+//!
+//! grid.eliminate(8, 0, 7);
+//! assert_eq!(grid.digits(8, 0).collect::<Vec<_>>(), vec![1, 2, 3, 4, 6, 8, 9]);
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::str::FromStr;
+
+use super::board::*;
+use super::constraint::*;
+
+/// A row, column or square of the board.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Region {
+    /// The row with the given index, from 0 at the top.
+    Row(usize),
+
+    /// The column with the given index, from 0 at the left.
+    Column(usize),
+
+    /// The square with the given index, from 0 at the top left, counting across then down.
+    Square(usize),
+}
+
+impl Region {
+    /// Iterate over every row, column and square of the board.
+    pub fn all() -> impl Iterator<Item = Region> {
+        (0..BOARD_SIZE)
+            .map(Region::Row)
+            .chain((0..BOARD_SIZE).map(Region::Column))
+            .chain((0..BOARD_SIZE).map(Region::Square))
+    }
+
+    /// The row, column and square containing the cell at column `x` and row `y`.
+    pub fn containing(x: usize, y: usize) -> [Region; 3] {
+        [
+            Region::Row(y),
+            Region::Column(x),
+            Region::Square(SQUARE_SIZE * (y / SQUARE_SIZE) + x / SQUARE_SIZE),
+        ]
+    }
+
+    /// Iterate over the cells of the region, as `(x, y)` pairs.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let cells: Vec<(usize, usize)> = Region::Square(4).cells().take(4).collect();
+    /// assert_eq!(cells, vec![(3, 3), (4, 3), (5, 3), (3, 4)]);
+    /// # }
+    /// ```
+    pub fn cells(self) -> impl Iterator<Item = (usize, usize)> + Clone {
+        (0..BOARD_SIZE).map(move |i| match self {
+            Region::Row(y) => (i, y),
+            Region::Column(x) => (x, i),
+            Region::Square(s) => (
+                SQUARE_SIZE * (s % SQUARE_SIZE) + i % SQUARE_SIZE,
+                SQUARE_SIZE * (s / SQUARE_SIZE) + i / SQUARE_SIZE,
+            ),
+        })
+    }
+}
+
+/// An error arising from an invalid Sukaku grid.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CandidateGridError {
+    /// A character other than a digit, `.` or whitespace was found.
+    InvalidCharacter(char),
+
+    /// A digit was found in a position belonging to another digit.
+    MisplacedDigit(u8),
+
+    /// The grid does not contain 729 candidate positions.
+    WrongLength(usize),
+}
+
+impl std::fmt::Display for CandidateGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CandidateGridError::InvalidCharacter(c) => write!(f, "invalid character `{}`", c),
+            CandidateGridError::MisplacedDigit(d) => {
+                write!(f, "digit {} is not in its own position", d)
+            }
+            CandidateGridError::WrongLength(n) => write!(f, "expected 729 characters, found {}", n),
+        }
+    }
+}
+
+impl std::error::Error for CandidateGridError {}
+
+/// The candidate digits of every cell of a board.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CandidateGrid {
+    cells: [[u16; BOARD_SIZE]; BOARD_SIZE],
+}
+
+impl Default for CandidateGrid {
+    /// Create a grid in which every digit is a candidate for every cell.
+    fn default() -> Self {
+        Self {
+            cells: [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE],
+        }
+    }
+}
+
+impl CandidateGrid {
+    /// Create a grid from a board, using the given rules to find the candidates of each empty
+    /// cell.  Each filled cell has its digit as its only candidate.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let mut board = Board::default();
+    /// board.set_cell(0, 0, 5);
+    ///
+    /// let grid = CandidateGrid::with_rules(&board, &Rules::classic().with(Diagonals));
+    /// assert!(!grid.contains(8, 8, 5));
+    /// assert!(CandidateGrid::from(&board).contains(8, 8, 5));
+    /// # }
+    /// ```
+    pub fn with_rules(board: &Board, rules: &Rules) -> Self {
+        let mut grid = Self::default();
+
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                grid.cells[y][x] = match board.get_cell_as_mask(x, y) {
+                    1 => rules.candidates(board, x, y),
+                    mask => mask,
+                };
+            }
+        }
+
+        grid
+    }
+
+    /// Returns the candidates of the cell at column `x` and row `y`, as a mask.
+    #[inline]
+    pub fn candidates(&self, x: usize, y: usize) -> u16 {
+        self.cells[y][x]
+    }
+
+    /// Replace the candidates of the cell at column `x` and row `y`.  Bits outside the digits 1 to
+    /// 9 are ignored.
+    #[inline]
+    pub fn set_candidates(&mut self, x: usize, y: usize, mask: u16) {
+        self.cells[y][x] = mask & ALL_CANDIDATES;
+    }
+
+    /// Iterate over the candidates of the cell at column `x` and row `y`, in increasing order.
+    pub fn digits(&self, x: usize, y: usize) -> impl Iterator<Item = u8> {
+        let mask = self.cells[y][x];
+
+        (1..=BOARD_SIZE as u8).filter(move |&d| mask & (1 << d) != 0)
+    }
+
+    /// Test whether `digit` is a candidate of the cell at column `x` and row `y`.
+    #[inline]
+    pub fn contains(&self, x: usize, y: usize, digit: u8) -> bool {
+        digit >= 1 && digit as usize <= BOARD_SIZE && self.cells[y][x] & (1 << digit) != 0
+    }
+
+    /// Remove `digit` from the candidates of the cell at column `x` and row `y`.  Returns `true`
+    /// if it was a candidate.
+    pub fn eliminate(&mut self, x: usize, y: usize, digit: u8) -> bool {
+        let present = self.contains(x, y, digit);
+
+        if present {
+            self.cells[y][x] &= !(1 << digit);
+        }
+
+        present
+    }
+
+    /// Add `digit` back to the candidates of the cell at column `x` and row `y`.  Returns `true`
+    /// if it was not already a candidate.
+    pub fn restore(&mut self, x: usize, y: usize, digit: u8) -> bool {
+        if digit < 1 || digit as usize > BOARD_SIZE || self.contains(x, y, digit) {
+            return false;
+        }
+
+        self.cells[y][x] |= 1 << digit;
+        true
+    }
+
+    /// Returns the candidates of each cell of a region, in the order given by
+    /// [`Region::cells()`].
+    pub fn region(&self, region: Region) -> [u16; BOARD_SIZE] {
+        let mut masks = [0; BOARD_SIZE];
+
+        for (mask, (x, y)) in masks.iter_mut().zip(region.cells()) {
+            *mask = self.cells[y][x];
+        }
+
+        masks
+    }
+
+    /// Returns the cells of a region which have `digit` as a candidate.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let mut board = Board::default();
+    /// board.set_cell(0, 1, 3);
+    /// board.set_cell(4, 2, 3);
+    ///
+    /// let grid = CandidateGrid::from(&board);
+    /// assert_eq!(grid.places(Region::Row(0), 3), vec![(6, 0), (7, 0), (8, 0)]);
+    /// # }
+    /// ```
+    pub fn places(&self, region: Region, digit: u8) -> Vec<(usize, usize)> {
+        region
+            .cells()
+            .filter(|&(x, y)| self.contains(x, y, digit))
+            .collect()
+    }
+
+    /// Returns `true` if every cell has exactly one candidate.
+    pub fn is_solved(&self) -> bool {
+        self.cells
+            .iter()
+            .flatten()
+            .all(|mask| mask.count_ones() == 1)
+    }
+
+    /// Convert the grid to a [`Board`], if every cell has exactly one candidate.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let solution = solve(&Board::default()).unwrap();
+    /// let grid = CandidateGrid::from(&solution);
+    ///
+    /// assert_eq!(grid.to_board(), Some(solution));
+    /// assert_eq!(CandidateGrid::default().to_board(), None);
+    /// # }
+    /// ```
+    pub fn to_board(&self) -> Option<Board> {
+        if !self.is_solved() {
+            return None;
+        }
+
+        let mut board = Board::default();
+
+        for (y, row) in self.cells.iter().enumerate() {
+            for (x, &mask) in row.iter().enumerate() {
+                board.set_cell_as_mask(x, y, mask);
+            }
+        }
+
+        Some(board)
+    }
+}
+
+/// Construct a `CandidateGrid` from a [`Board`], using the classic sudoku rules.
+impl From<&Board> for CandidateGrid {
+    fn from(board: &Board) -> Self {
+        Self::with_rules(board, &Rules::classic())
+    }
+}
+
+/// `Constraint` implementation for `CandidateGrid`, permitting only the candidates of each cell.
+///
+/// ## Example
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let mut grid = CandidateGrid::default();
+/// grid.set_candidates(0, 0, 1 << 7);
+/// grid.set_candidates(1, 0, 1 << 3 | 1 << 4);
+///
+/// let solution = SolutionIter::from_candidates(&grid).next().unwrap();
+///
+/// assert_eq!(solution.get_cell(0, 0), 7);
+/// assert!(grid.contains(1, 0, solution.get_cell(1, 0)));
+/// # }
+/// ```
+impl Constraint for CandidateGrid {
+    #[inline]
+    fn candidates(&self, _board: &Board, x: usize, y: usize) -> u16 {
+        self.cells[y][x]
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        for (y, row) in self.cells.iter().enumerate() {
+            for (x, &mask) in row.iter().enumerate() {
+                let value = board.get_cell_as_mask(x, y);
+
+                if mask == 0 || (value != 1 && mask & value == 0) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// The candidates of a Sukaku puzzle rarely leave any cell with a single choice, so the
+    /// solver is asked to propagate them, which lets the houses of the board narrow them down
+    /// further.
+    fn propagates(&self) -> bool {
+        true
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        let mut changed = false;
+
+        for (y, row) in self.cells.iter().enumerate() {
+            for (x, &mask) in row.iter().enumerate() {
+                let m = &mut masks[y][x];
+
+                if board.get_cell(x, y) == 0 && *m & mask != *m {
+                    *m &= mask;
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+}
+
+impl FromStr for CandidateGrid {
+    type Err = CandidateGridError;
+
+    /// Read a grid in the 729-character Sukaku format.  Whitespace is ignored.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let s = format!("..3.5....{}", "123456789".repeat(80));
+    /// let grid: CandidateGrid = s.parse().unwrap();
+    ///
+    /// assert_eq!(grid.digits(0, 0).collect::<Vec<_>>(), vec![3, 5]);
+    /// assert_eq!(grid.to_string(), s);
+    /// # }
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = [[0; BOARD_SIZE]; BOARD_SIZE];
+        let mut count = 0;
+
+        for c in s.chars().filter(|c| !c.is_whitespace()) {
+            let position = (count % BOARD_SIZE) as u8 + 1;
+
+            match c {
+                '.' | '0' => {}
+                '1'..='9' => {
+                    let digit = c as u8 - b'0';
+
+                    if digit != position {
+                        return Err(CandidateGridError::MisplacedDigit(digit));
+                    }
+
+                    if count < BOARD_SIZE * BOARD_SIZE * BOARD_SIZE {
+                        let cell = count / BOARD_SIZE;
+                        cells[cell / BOARD_SIZE][cell % BOARD_SIZE] |= 1 << digit;
+                    }
+                }
+                c => return Err(CandidateGridError::InvalidCharacter(c)),
+            }
+
+            count += 1;
+        }
+
+        if count != BOARD_SIZE * BOARD_SIZE * BOARD_SIZE {
+            return Err(CandidateGridError::WrongLength(count));
+        }
+
+        Ok(Self { cells })
+    }
+}
+
+/// Write the grid in the 729-character Sukaku format, on a single line.
+///
+/// The alternate form, `{:#}`, draws the grid as pencilmarks instead, with each cell shown as a
+/// three-by-three block of its candidates and lines around each square.
+///
+/// ## Example
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let mut board = Board::default();
+/// board.set_cell(4, 0, 5);
+///
+/// let grid = CandidateGrid::from(&board);
+/// let pencilmarks = format!("{:#}", grid);
+/// let lines: Vec<&str> = pencilmarks.lines().collect();
+///
+/// assert_eq!(lines[0], "+-------------+-------------+-------------+");
+/// assert_eq!(lines[1], "| 123 123 123 | 123 ... 123 | 123 123 123 |");
+/// assert_eq!(lines[2], "| 4.6 4.6 4.6 | 4.6 .5. 4.6 | 4.6 4.6 4.6 |");
+/// assert_eq!(lines[3], "| 789 789 789 | 789 ... 789 | 789 789 789 |");
+/// assert_eq!(lines[4], "|             |             |             |");
+/// # }
+/// ```
+impl std::fmt::Display for CandidateGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return self.fmt_pencilmarks(f);
+        }
+
+        let mut s = String::with_capacity(BOARD_SIZE * BOARD_SIZE * BOARD_SIZE);
+
+        for &mask in self.cells.iter().flatten() {
+            for d in 1..=BOARD_SIZE as u32 {
+                s.push(candidate_char(mask, d));
+            }
+        }
+
+        write!(f, "{}", s)
+    }
+}
+
+impl CandidateGrid {
+    /// Draw the grid as pencilmarks, for the alternate form of `Display`.
+    fn fmt_pencilmarks(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cell_width = SQUARE_SIZE;
+        let square_width = SQUARE_SIZE * (cell_width + 1) + 1;
+        let border = format!(
+            "+{}\n",
+            format!("{}+", "-".repeat(square_width)).repeat(SQUARE_SIZE)
+        );
+        let gap = format!(
+            "|{}\n",
+            format!("{}|", " ".repeat(square_width)).repeat(SQUARE_SIZE)
+        );
+
+        let mut s = String::new();
+
+        for (y, row) in self.cells.iter().enumerate() {
+            s.push_str(if y % SQUARE_SIZE == 0 { &border } else { &gap });
+
+            for line in 0..SQUARE_SIZE {
+                for (x, &mask) in row.iter().enumerate() {
+                    s.push_str(match x {
+                        0 => "| ",
+                        x if x % SQUARE_SIZE == 0 => " | ",
+                        _ => " ",
+                    });
+
+                    for column in 0..SQUARE_SIZE {
+                        s.push(candidate_char(
+                            mask,
+                            (SQUARE_SIZE * line + column + 1) as u32,
+                        ));
+                    }
+                }
+
+                s.push_str(" |\n");
+            }
+        }
+
+        s.push_str(border.trim_end());
+
+        write!(f, "{}", s)
+    }
+}
+
+/// Returns digit `d` if it is in `mask`, or `.` if it is not.
+#[inline]
+fn candidate_char(mask: u16, d: u32) -> char {
+    if mask & (1 << d) != 0 {
+        char::from_digit(d, 10).unwrap()
+    } else {
+        '.'
+    }
+}
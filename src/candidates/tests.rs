@@ -0,0 +1,210 @@
+use super::*;
+
+#[test]
+fn region_cells() {
+    assert_eq!(
+        Region::Row(2).cells().collect::<Vec<_>>(),
+        (0..9).map(|x| (x, 2)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Region::Column(5).cells().collect::<Vec<_>>(),
+        (0..9).map(|y| (5, y)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Region::Square(8).cells().collect::<Vec<_>>(),
+        vec![
+            (6, 6),
+            (7, 6),
+            (8, 6),
+            (6, 7),
+            (7, 7),
+            (8, 7),
+            (6, 8),
+            (7, 8),
+            (8, 8)
+        ]
+    );
+    assert_eq!(Region::all().count(), 27);
+    assert_eq!(
+        Region::containing(4, 7),
+        [Region::Row(7), Region::Column(4), Region::Square(7)]
+    );
+}
+
+#[test]
+fn candidates_from_board() {
+    let mut board = Board::default();
+    board.set_cell(0, 0, 1);
+    board.set_cell(8, 1, 2);
+    board.set_cell(1, 8, 3);
+
+    let grid = CandidateGrid::from(&board);
+
+    assert_eq!(grid.candidates(0, 0), 0b00_0000_0010);
+    assert_eq!(grid.candidates(1, 1), 0b11_1111_0000);
+    assert_eq!(grid.candidates(4, 4), ALL_CANDIDATES);
+    assert!(!grid.is_solved());
+    assert_eq!(grid.to_board(), None);
+}
+
+#[test]
+fn eliminate_and_restore() {
+    let mut grid = CandidateGrid::default();
+
+    assert!(grid.eliminate(3, 4, 7));
+    assert!(!grid.eliminate(3, 4, 7));
+    assert!(!grid.contains(3, 4, 7));
+    assert!(!grid.eliminate(3, 4, 0));
+    assert!(!grid.eliminate(3, 4, 10));
+
+    assert!(grid.restore(3, 4, 7));
+    assert!(!grid.restore(3, 4, 7));
+    assert!(!grid.restore(3, 4, 0));
+    assert_eq!(grid.candidates(3, 4), ALL_CANDIDATES);
+
+    grid.set_candidates(0, 0, 0xffff);
+    assert_eq!(grid.candidates(0, 0), ALL_CANDIDATES);
+}
+
+#[test]
+fn region_queries() {
+    let mut grid = CandidateGrid::default();
+    grid.set_candidates(0, 0, 1 << 4);
+    grid.eliminate(2, 0, 9);
+
+    let row = grid.region(Region::Row(0));
+    assert_eq!(row[0], 1 << 4);
+    assert_eq!(row[2], ALL_CANDIDATES & !(1 << 9));
+    assert_eq!(grid.region(Region::Square(0))[0], 1 << 4);
+
+    assert_eq!(grid.places(Region::Row(0), 4).len(), 9);
+    assert_eq!(grid.places(Region::Column(2), 9).len(), 8);
+    assert_eq!(grid.places(Region::Square(0), 9)[..2], [(1, 0), (0, 1)]);
+}
+
+#[test]
+fn convert_to_board() {
+    let solution = crate::solve(&Board::default()).unwrap();
+    let mut grid = CandidateGrid::from(&solution);
+
+    assert!(grid.is_solved());
+    assert_eq!(grid.to_board(), Some(solution));
+
+    let v = solution.get_cell(4, 4);
+    grid.restore(4, 4, v % 9 + 1);
+    assert_eq!(grid.to_board(), None);
+
+    grid.eliminate(4, 4, v);
+    grid.eliminate(4, 4, v % 9 + 1);
+    assert_eq!(grid.to_board(), None);
+}
+
+#[test]
+fn parse_sukaku_format() {
+    let s = format!("1........{}", ".23456789".repeat(80));
+    let grid: CandidateGrid = s.parse().unwrap();
+
+    assert_eq!(grid.candidates(0, 0), 1 << 1);
+    assert_eq!(grid.candidates(8, 8), ALL_CANDIDATES & !(1 << 1));
+    assert_eq!(grid.to_string(), s);
+
+    let spaced: String = s
+        .as_bytes()
+        .chunks(BOARD_SIZE)
+        .map(|cell| std::str::from_utf8(cell).unwrap())
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert_eq!(spaced.replace('.', "0").parse(), Ok(grid));
+}
+
+#[test]
+fn reject_bad_sukaku_format() {
+    assert_eq!(
+        "123".parse::<CandidateGrid>(),
+        Err(CandidateGridError::WrongLength(3))
+    );
+    assert_eq!(
+        "123456789".repeat(82).parse::<CandidateGrid>(),
+        Err(CandidateGridError::WrongLength(738))
+    );
+    assert_eq!(
+        "2".parse::<CandidateGrid>(),
+        Err(CandidateGridError::MisplacedDigit(2))
+    );
+    assert_eq!(
+        "12x".parse::<CandidateGrid>(),
+        Err(CandidateGridError::InvalidCharacter('x'))
+    );
+}
+
+#[test]
+fn constraint_permits_only_candidates() {
+    let mut grid = CandidateGrid::default();
+    grid.set_candidates(0, 0, 1 << 2 | 1 << 5);
+
+    let mut board = Board::default();
+    assert_eq!(
+        grid.candidates(0, 0),
+        Constraint::candidates(&grid, &board, 0, 0)
+    );
+    assert!(Constraint::valid(&grid, &board));
+
+    board.set_cell(0, 0, 5);
+    assert!(Constraint::valid(&grid, &board));
+
+    board.set_cell(0, 0, 4);
+    assert!(!Constraint::valid(&grid, &board));
+
+    board.set_cell(0, 0, 0);
+    grid.set_candidates(1, 1, 0);
+    assert!(!Constraint::valid(&grid, &board));
+}
+
+#[test]
+fn constraint_propagates_candidates() {
+    let mut grid = CandidateGrid::default();
+    grid.set_candidates(0, 0, 1 << 2 | 1 << 5);
+    grid.set_candidates(1, 0, 1 << 3);
+
+    let mut board = Board::default();
+    board.set_cell(1, 0, 7);
+
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+    masks[1][0] = 1 << 2 | 1 << 3;
+    masks[0][1] = 1 << 7;
+
+    assert!(Constraint::propagate(&grid, &board, &mut masks));
+    assert_eq!(masks[0][0], 1 << 2 | 1 << 5);
+    assert_eq!(masks[0][1], 1 << 7);
+    assert_eq!(masks[1][0], 1 << 2 | 1 << 3);
+    assert!(!Constraint::propagate(&grid, &board, &mut masks));
+
+    // Without the houses of the classic rules, the grid alone still narrows down the search.
+    let rules = Rules::empty().with(grid);
+    assert_eq!(
+        crate::SolutionIter::with_rules(&Board::default(), rules)
+            .next()
+            .map(|solution| solution.get_cell(0, 0)),
+        Some(2)
+    );
+}
+
+#[test]
+fn pencilmark_layout() {
+    let solution = crate::solve(&Board::default()).unwrap();
+    let mut grid = CandidateGrid::from(&solution);
+    grid.set_candidates(8, 8, ALL_CANDIDATES);
+
+    let s = format!("{:#}", grid);
+    let lines: Vec<&str> = s.lines().collect();
+
+    assert_eq!(lines.len(), 37);
+    assert!(lines.iter().all(|line| line.len() == 43));
+    assert_eq!(lines[0], lines[12]);
+    assert_eq!(lines[0], lines[36]);
+    assert_eq!(lines[4], "|             |             |             |");
+    assert!(lines[33].ends_with(" 123 |"));
+    assert!(lines[34].ends_with(" 456 |"));
+    assert!(lines[35].ends_with(" 789 |"));
+    assert!(!s.ends_with('\n'));
+}
@@ -0,0 +1,118 @@
+//! # Anti-knight and anti-king sudoku
+//!
+//! These variants forbid equal digits in any two cells which are a chess knight's move
+//! ([`AntiKnight`]) or a chess king's move ([`AntiKing`]) apart.  In combination with the classic
+//! rules, the anti-king constraint only adds restrictions between diagonally adjacent cells.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let mut board = Board::default();
+//! board.set_cell(2, 2, 7);
+//! board.set_cell(3, 4, 7);
+//!
+//! assert!(valid(&board));
+//! assert_eq!(AntiKnight.conflicts(&board), vec![((2, 2), (3, 4))]);
+//! assert!(!valid_with_rules(&board, &Rules::classic().with(AntiKnight)));
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+use super::constraint::*;
+
+const KNIGHT_MOVES: [(isize, isize); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+const KING_MOVES: [(isize, isize); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// Returns every pair of cells, one move apart, which hold the same digit.  Each pair is listed
+/// once, in reading order of its first cell.
+fn conflicts(b: &Board, moves: &'static [(isize, isize)]) -> Vec<((usize, usize), (usize, usize))> {
+    let mut pairs = Vec::new();
+
+    for y in 0..BOARD_SIZE {
+        for x in 0..BOARD_SIZE {
+            let v = b.get_cell(x, y);
+
+            if v == 0 {
+                continue;
+            }
+
+            for (nx, ny) in reachable(x, y, moves) {
+                if (ny, nx) > (y, x) && b.get_cell(nx, ny) == v {
+                    pairs.push(((x, y), (nx, ny)));
+                }
+            }
+        }
+    }
+
+    pairs
+}
+
+/// No two cells a knight's move apart may contain the same digit.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AntiKnight;
+
+impl AntiKnight {
+    /// Returns every pair of cells a knight's move apart which contain the same digit.
+    pub fn conflicts(&self, board: &Board) -> Vec<((usize, usize), (usize, usize))> {
+        conflicts(board, &KNIGHT_MOVES)
+    }
+}
+
+impl Constraint for AntiKnight {
+    #[inline]
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        ALL_CANDIDATES & !used_digits(board, reachable(x, y, &KNIGHT_MOVES))
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        self.conflicts(board).is_empty()
+    }
+}
+
+/// No two cells a king's move apart may contain the same digit.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AntiKing;
+
+impl AntiKing {
+    /// Returns every pair of cells a king's move apart which contain the same digit.
+    pub fn conflicts(&self, board: &Board) -> Vec<((usize, usize), (usize, usize))> {
+        conflicts(board, &KING_MOVES)
+    }
+}
+
+impl Constraint for AntiKing {
+    #[inline]
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        ALL_CANDIDATES & !used_digits(board, reachable(x, y, &KING_MOVES))
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        self.conflicts(board).is_empty()
+    }
+}
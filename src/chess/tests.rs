@@ -0,0 +1,49 @@
+use super::*;
+
+#[test]
+fn knight_candidates_are_correct() {
+    let mut board = Board::default();
+    board.set_cell(1, 2, 1);
+    board.set_cell(2, 1, 2);
+    board.set_cell(2, 2, 3);
+    board.set_cell(8, 8, 4);
+
+    assert_eq!(AntiKnight.candidates(&board, 0, 0), 0b11_1111_1000); // {3, ..., 9}
+    assert_eq!(AntiKnight.candidates(&board, 7, 6), 0b11_1110_1110); // not 4
+    assert_eq!(AntiKnight.candidates(&board, 4, 4), ALL_CANDIDATES);
+}
+
+#[test]
+fn king_candidates_are_correct() {
+    let mut board = Board::default();
+    board.set_cell(1, 1, 1);
+    board.set_cell(0, 1, 2);
+    board.set_cell(2, 2, 3);
+
+    assert_eq!(AntiKing.candidates(&board, 0, 0), 0b11_1111_1000); // {3, ..., 9}
+    assert_eq!(AntiKing.candidates(&board, 2, 0), 0b11_1111_1100); // {2, ..., 9}
+    assert_eq!(AntiKing.candidates(&board, 3, 3), 0b11_1111_0110); // not 3
+}
+
+#[test]
+fn conflicts_are_reported() {
+    let mut board = Board::default();
+    assert!(AntiKnight.valid(&board));
+    assert!(AntiKing.valid(&board));
+
+    board.set_cell(4, 4, 5);
+    board.set_cell(5, 5, 5);
+    board.set_cell(6, 5, 5);
+    board.set_cell(3, 2, 5);
+
+    assert_eq!(
+        AntiKnight.conflicts(&board),
+        vec![((3, 2), (4, 4)), ((4, 4), (6, 5))]
+    );
+    assert_eq!(
+        AntiKing.conflicts(&board),
+        vec![((4, 4), (5, 5)), ((5, 5), (6, 5))]
+    );
+    assert!(!AntiKnight.valid(&board));
+    assert!(!AntiKing.valid(&board));
+}
@@ -0,0 +1,332 @@
+//! # Greater-than sudoku
+//!
+//! In greater-than (or comparison) sudoku, inequality signs between orthogonally adjacent cells
+//! say which of the two digits is larger.  Such puzzles often have no givens at all.
+//!
+//! The [`Inequalities`] constraint holds the signs.  Each sign is given as a pair of cells, the
+//! greater first.  A set of signs can also be read from and written to text, as a list of signs
+//! such as `r1c1>r1c2` or `r2c1<r3c1`, separated by commas or whitespace.  Rows and columns are
+//! numbered from 1.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let signs: Inequalities = "r1c1>r1c2, r1c2>r1c3, r2c1<r1c1".parse().unwrap();
+//! let solution = SolutionIter::with_rules(&Board::default(), Rules::classic().with(signs))
+//!     .next()
+//!     .unwrap();
+//!
+//! assert!(solution.get_cell(0, 0) > solution.get_cell(1, 0));
+//! assert!(solution.get_cell(1, 0) > solution.get_cell(2, 0));
+//! assert!(solution.get_cell(0, 1) < solution.get_cell(0, 0));
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use super::board::*;
+use super::constraint::*;
+
+/// An inequality sign, given as the cell holding the greater digit followed by the cell holding
+/// the lesser digit.
+pub type Sign = ((usize, usize), (usize, usize));
+
+/// An error arising from an invalid set of inequality signs.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum InequalityError {
+    /// A cell lies outside the board.
+    CellOutOfRange(usize, usize),
+
+    /// The two cells of a sign are not orthogonally adjacent.
+    NotAdjacent((usize, usize), (usize, usize)),
+
+    /// More than one sign sits between the same pair of cells.
+    DuplicateSign((usize, usize), (usize, usize)),
+
+    /// A cell lies on a loop of signs, or on a chain too long to fill with distinct digits.
+    Impossible(usize, usize),
+
+    /// The sign at the given position, counting from 1, could not be parsed.
+    InvalidSign(usize),
+}
+
+impl std::fmt::Display for InequalityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InequalityError::CellOutOfRange(x, y) => {
+                write!(f, "cell ({}, {}) is off the board", x, y)
+            }
+            InequalityError::NotAdjacent(a, b) => {
+                write!(f, "cells {:?} and {:?} are not adjacent", a, b)
+            }
+            InequalityError::DuplicateSign(a, b) => {
+                write!(f, "more than one sign between cells {:?} and {:?}", a, b)
+            }
+            InequalityError::Impossible(x, y) => {
+                write!(
+                    f,
+                    "the signs around cell ({}, {}) cannot be satisfied",
+                    x, y
+                )
+            }
+            InequalityError::InvalidSign(n) => write!(f, "sign {} could not be parsed", n),
+        }
+    }
+}
+
+impl std::error::Error for InequalityError {}
+
+/// Returns a mask of the digits from `lo` to `hi` inclusive.
+#[inline]
+fn digits_between(lo: u8, hi: u8) -> u16 {
+    if lo > hi {
+        0
+    } else {
+        ((1 << (hi + 1)) - (1 << lo)) & ALL_CANDIDATES
+    }
+}
+
+/// Inequality signs between adjacent cells.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Inequalities {
+    signs: Vec<Sign>,
+
+    /// For each cell and direction, how the cell compares with its neighbour.
+    edges: [[[Option<Ordering>; 4]; BOARD_SIZE]; BOARD_SIZE],
+
+    /// The smallest and largest digits each cell can hold, given the chains of signs through it.
+    bounds: [[(u8, u8); BOARD_SIZE]; BOARD_SIZE],
+}
+
+impl Inequalities {
+    /// Create a set of inequality signs.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// assert!(Inequalities::new(&[((0, 0), (1, 0))]).is_ok());
+    ///
+    /// assert_eq!(
+    ///     Inequalities::new(&[((0, 0), (1, 0)), ((1, 0), (0, 0))]),
+    ///     Err(InequalityError::DuplicateSign((1, 0), (0, 0)))
+    /// );
+    /// # }
+    /// ```
+    pub fn new(signs: &[Sign]) -> Result<Self, InequalityError> {
+        let mut edges = [[[None; 4]; BOARD_SIZE]; BOARD_SIZE];
+
+        for &(a, b) in signs {
+            for (x, y) in [a, b] {
+                if x >= BOARD_SIZE || y >= BOARD_SIZE {
+                    return Err(InequalityError::CellOutOfRange(x, y));
+                }
+            }
+
+            let d = (0..4)
+                .find(|&d| neighbour(a.0, a.1, d) == Some(b))
+                .ok_or(InequalityError::NotAdjacent(a, b))?;
+
+            if edges[a.1][a.0][d].is_some() {
+                return Err(InequalityError::DuplicateSign(a, b));
+            }
+
+            edges[a.1][a.0][d] = Some(Ordering::Greater);
+            edges[b.1][b.0][(d + 2) % 4] = Some(Ordering::Less);
+        }
+
+        let below = Self::chain_lengths(&edges, Ordering::Greater)?;
+        let above = Self::chain_lengths(&edges, Ordering::Less)?;
+        let mut bounds = [[(1, BOARD_SIZE as u8); BOARD_SIZE]; BOARD_SIZE];
+
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let lo = 1 + below[y][x];
+                let hi = BOARD_SIZE - above[y][x];
+
+                if lo > hi {
+                    return Err(InequalityError::Impossible(x, y));
+                }
+
+                bounds[y][x] = (lo as u8, hi as u8);
+            }
+        }
+
+        Ok(Self {
+            signs: signs.to_vec(),
+            edges,
+            bounds,
+        })
+    }
+
+    /// For each cell, find the number of cells on the longest chain of signs leading away from it
+    /// in the `order` direction.
+    fn chain_lengths(
+        edges: &[[[Option<Ordering>; 4]; BOARD_SIZE]; BOARD_SIZE],
+        order: Ordering,
+    ) -> Result<[[usize; BOARD_SIZE]; BOARD_SIZE], InequalityError> {
+        // `None` marks a cell whose chains are still being explored, so meeting one again means
+        // the signs form a loop.
+        fn visit(
+            edges: &[[[Option<Ordering>; 4]; BOARD_SIZE]; BOARD_SIZE],
+            order: Ordering,
+            lengths: &mut [[Option<Option<usize>>; BOARD_SIZE]; BOARD_SIZE],
+            x: usize,
+            y: usize,
+        ) -> Result<usize, InequalityError> {
+            match lengths[y][x] {
+                Some(Some(n)) => return Ok(n),
+                Some(None) => return Err(InequalityError::Impossible(x, y)),
+                None => {}
+            }
+
+            lengths[y][x] = Some(None);
+            let mut longest = 0;
+
+            for d in 0..4 {
+                if edges[y][x][d] == Some(order) {
+                    let (nx, ny) = neighbour(x, y, d).unwrap();
+                    longest = longest.max(1 + visit(edges, order, lengths, nx, ny)?);
+                }
+            }
+
+            lengths[y][x] = Some(Some(longest));
+            Ok(longest)
+        }
+
+        let mut lengths = [[None; BOARD_SIZE]; BOARD_SIZE];
+        let mut result = [[0; BOARD_SIZE]; BOARD_SIZE];
+
+        for (y, row) in result.iter_mut().enumerate() {
+            for (x, length) in row.iter_mut().enumerate() {
+                *length = visit(edges, order, &mut lengths, x, y)?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// The signs in the set.
+    #[inline]
+    pub fn signs(&self) -> &[Sign] {
+        &self.signs
+    }
+}
+
+impl Constraint for Inequalities {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        let (mut lo, mut hi) = self.bounds[y][x];
+
+        for (d, edge) in self.edges[y][x].iter().enumerate() {
+            let v = match (edge, neighbour(x, y, d)) {
+                (Some(_), Some((nx, ny))) => board.get_cell(nx, ny),
+                _ => continue,
+            };
+
+            match (edge, v) {
+                (_, 0) => {}
+                (Some(Ordering::Greater), v) => lo = lo.max(v + 1),
+                (_, v) => hi = hi.min(v - 1),
+            }
+        }
+
+        digits_between(lo, hi)
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        self.signs.iter().all(|&((ax, ay), (bx, by))| {
+            let a = board.get_cell(ax, ay);
+            let b = board.get_cell(bx, by);
+
+            a == 0 || b == 0 || a > b
+        })
+    }
+
+    fn propagates(&self) -> bool {
+        true
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        let mut changed = false;
+
+        for &((ax, ay), (bx, by)) in &self.signs {
+            let a = masks[ay][ax];
+            let b = masks[by][bx];
+
+            if a == 0 || b == 0 {
+                continue;
+            }
+
+            // The greater cell must beat the smallest candidate of the lesser, and vice versa.
+            let over = digits_between(b.trailing_zeros() as u8 + 1, BOARD_SIZE as u8);
+            let under = digits_between(1, 14 - a.leading_zeros() as u8);
+
+            if board.get_cell(ax, ay) == 0 && a & !over != 0 {
+                masks[ay][ax] &= over;
+                changed = true;
+            }
+
+            if board.get_cell(bx, by) == 0 && b & !under != 0 {
+                masks[by][bx] &= under;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+/// Parse a cell reference such as `r3c5` into board coordinates.
+fn parse_cell(s: &str) -> Option<(usize, usize)> {
+    let s = s.trim().strip_prefix(['r', 'R'])?;
+    let (row, column) = s.split_once(['c', 'C'])?;
+    let row: usize = row.parse().ok()?;
+    let column: usize = column.parse().ok()?;
+
+    Some((column.checked_sub(1)?, row.checked_sub(1)?))
+}
+
+impl FromStr for Inequalities {
+    type Err = InequalityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut signs = Vec::new();
+
+        for (i, token) in s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|t| !t.is_empty())
+            .enumerate()
+        {
+            let sign = if let Some((a, b)) = token.split_once('>') {
+                parse_cell(a).zip(parse_cell(b))
+            } else if let Some((a, b)) = token.split_once('<') {
+                parse_cell(b).zip(parse_cell(a))
+            } else {
+                None
+            };
+
+            signs.push(sign.ok_or(InequalityError::InvalidSign(i + 1))?);
+        }
+
+        Self::new(&signs)
+    }
+}
+
+impl std::fmt::Display for Inequalities {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let signs: Vec<String> = self
+            .signs
+            .iter()
+            .map(|&((ax, ay), (bx, by))| format!("r{}c{}>r{}c{}", ay + 1, ax + 1, by + 1, bx + 1))
+            .collect();
+
+        write!(f, "{}", signs.join(", "))
+    }
+}
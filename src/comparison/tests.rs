@@ -0,0 +1,94 @@
+use super::*;
+
+#[test]
+fn filled_neighbours_bound_candidates() {
+    let signs = Inequalities::new(&[((1, 0), (0, 0)), ((1, 0), (2, 0))]).unwrap();
+    let mut board = Board::default();
+
+    // The greater end of a sign can't hold 1, and the lesser end can't hold 9.
+    assert_eq!(signs.candidates(&board, 1, 0), 0b11_1111_1100); // {2, ..., 9}
+    assert_eq!(signs.candidates(&board, 0, 0), 0b01_1111_1110); // {1, ..., 8}
+    assert_eq!(signs.candidates(&board, 4, 4), ALL_CANDIDATES);
+
+    board.set_cell(0, 0, 4);
+    board.set_cell(2, 0, 6);
+    assert_eq!(signs.candidates(&board, 1, 0), 0b11_1000_0000); // {7, 8, 9}
+
+    board.set_cell(1, 0, 5);
+    assert!(!signs.valid(&board));
+
+    board.set_cell(1, 0, 7);
+    assert!(signs.valid(&board));
+}
+
+#[test]
+fn chains_bound_candidates() {
+    // r1c1 > r1c2 > r1c3 > r2c3
+    let signs = Inequalities::new(&[((0, 0), (1, 0)), ((1, 0), (2, 0)), ((2, 0), (2, 1))]).unwrap();
+    let board = Board::default();
+
+    assert_eq!(signs.candidates(&board, 0, 0), 0b11_1111_0000); // {4, ..., 9}
+    assert_eq!(signs.candidates(&board, 1, 0), 0b01_1111_1000); // {3, ..., 8}
+    assert_eq!(signs.candidates(&board, 2, 1), 0b00_0111_1110); // {1, ..., 6}
+}
+
+#[test]
+fn signs_are_checked() {
+    assert_eq!(
+        Inequalities::new(&[((0, 0), (2, 0))]),
+        Err(InequalityError::NotAdjacent((0, 0), (2, 0)))
+    );
+    assert_eq!(
+        Inequalities::new(&[((0, 0), (0, 9))]),
+        Err(InequalityError::CellOutOfRange(0, 9))
+    );
+
+    // A loop around a 2x2 block can never be satisfied.
+    let loop_signs = [
+        ((0, 0), (1, 0)),
+        ((1, 0), (1, 1)),
+        ((1, 1), (0, 1)),
+        ((0, 1), (0, 0)),
+    ];
+    assert!(matches!(
+        Inequalities::new(&loop_signs),
+        Err(InequalityError::Impossible(_, _))
+    ));
+}
+
+#[test]
+fn propagation_follows_chains_of_empty_cells() {
+    let signs = Inequalities::new(&[((0, 0), (1, 0))]).unwrap();
+    let board = Board::default();
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+
+    masks[0][1] = 0b00_1110_0000; // {5, 6, 7}
+    assert!(signs.propagate(&board, &mut masks));
+    assert_eq!(masks[0][0], 0b11_1100_0000); // {6, ..., 9}
+
+    masks[0][0] = 0b00_0100_0000; // {6}
+    assert!(signs.propagate(&board, &mut masks));
+    assert_eq!(masks[0][1], 0b00_0010_0000); // {5}
+    assert!(!signs.propagate(&board, &mut masks));
+}
+
+#[test]
+fn signs_round_trip_through_text() {
+    let signs: Inequalities = "r1c1>r1c2 r3c3<r3c2,R9C9>R8C9".parse().unwrap();
+
+    assert_eq!(
+        signs.signs(),
+        &[((0, 0), (1, 0)), ((1, 2), (2, 2)), ((8, 8), (8, 7))]
+    );
+    assert_eq!(signs.to_string(), "r1c1>r1c2, r3c2>r3c3, r9c9>r8c9");
+    assert_eq!(signs.to_string().parse::<Inequalities>(), Ok(signs));
+
+    assert_eq!(
+        "r1c1>r1c2, r1c3=r1c4".parse::<Inequalities>(),
+        Err(InequalityError::InvalidSign(2))
+    );
+    assert_eq!(
+        "r0c1>r1c1".parse::<Inequalities>(),
+        Err(InequalityError::InvalidSign(1))
+    );
+}
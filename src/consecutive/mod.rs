@@ -0,0 +1,118 @@
+//! # Non-consecutive sudoku
+//!
+//! In a non-consecutive sudoku, orthogonally adjacent cells may not contain consecutive digits.
+//!
+//! These puzzles often have very few givens, so the [`NonConsecutive`] constraint propagates
+//! candidates between empty cells as well as checking filled ones: a digit is ruled out if every
+//! remaining candidate of an adjacent cell is consecutive to it.  For example, once a cell is down
+//! to a single candidate, its neighbours lose the digits either side of it.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let rules = Rules::classic().with(NonConsecutive);
+//! let solution = SolutionIter::with_rules(&Board::default(), rules).next().unwrap();
+//!
+//! for y in 0..BOARD_SIZE {
+//!     for x in 1..BOARD_SIZE {
+//!         assert_ne!(solution.get_cell(x - 1, y).abs_diff(solution.get_cell(x, y)), 1);
+//!     }
+//! }
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+use super::constraint::*;
+
+/// Iterate over the orthogonal neighbours of `(x, y)` which lie on the board.
+#[inline]
+fn neighbours(x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+    reachable(x, y, &ORTHOGONAL)
+}
+
+/// Returns a mask of the digits consecutive to `mask`'s digits.
+#[inline]
+fn consecutive_to(mask: u16) -> u16 {
+    ((mask << 1) | (mask >> 1)) & ALL_CANDIDATES
+}
+
+/// Returns a mask of the digits ruled out for `(x, y)` by its filled neighbours.
+#[inline]
+fn excluded(b: &Board, x: usize, y: usize) -> u16 {
+    consecutive_to(used_digits(b, neighbours(x, y)))
+}
+
+/// Orthogonally adjacent cells may not contain consecutive digits.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NonConsecutive;
+
+impl Constraint for NonConsecutive {
+    #[inline]
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        ALL_CANDIDATES & !excluded(board, x, y)
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let v = board.get_cell(x, y);
+
+                if v == 0 {
+                    continue;
+                }
+
+                // Only look right and down, so that each pair is checked once.
+                for d in 0..2 {
+                    let w = match neighbour(x, y, d) {
+                        Some((nx, ny)) => board.get_cell(nx, ny),
+                        None => continue,
+                    };
+
+                    if w != 0 && v.abs_diff(w) == 1 {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    fn propagates(&self) -> bool {
+        true
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        let mut changed = false;
+
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                if board.get_cell(x, y) != 0 {
+                    continue;
+                }
+
+                for (nx, ny) in neighbours(x, y) {
+                    let theirs = masks[ny][nx];
+
+                    // Keep digit `d` only if some candidate of the neighbour isn't consecutive.
+                    let keep = (1..=BOARD_SIZE)
+                        .filter(|&d| theirs & !consecutive_to(1 << d) != 0)
+                        .fold(0, |acc, d| acc | (1 << d));
+
+                    if masks[y][x] & !keep != 0 {
+                        masks[y][x] &= keep;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        changed
+    }
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+#[test]
+fn neighbours_of_filled_cells_are_pruned() {
+    let mut board = Board::default();
+    board.set_cell(4, 3, 5);
+
+    assert_eq!(NonConsecutive.candidates(&board, 4, 4), 0b11_1010_1110); // not 4 or 6
+    assert_eq!(NonConsecutive.candidates(&board, 5, 4), ALL_CANDIDATES);
+
+    board.set_cell(3, 4, 9);
+    assert_eq!(NonConsecutive.candidates(&board, 4, 4), 0b10_1010_1110); // not 4, 6 or 8
+}
+
+#[test]
+fn candidates_are_propagated_between_empty_cells() {
+    let board = Board::default();
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+    assert!(!NonConsecutive.propagate(&board, &mut masks));
+
+    // A cell with a single candidate rules out its consecutive digits next door.
+    masks[4][4] = 1 << 5;
+    assert!(NonConsecutive.propagate(&board, &mut masks));
+    assert_eq!(masks[4][4], 1 << 5);
+    assert_eq!(masks[3][4], 0b11_1010_1110); // not 4 or 6
+    assert_eq!(masks[4][5], 0b11_1010_1110);
+    assert_eq!(masks[5][5], ALL_CANDIDATES);
+
+    // Digits are only removed if every candidate of a neighbour is consecutive to them.
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+    masks[0][0] = 1 << 2 | 1 << 4;
+    assert!(NonConsecutive.propagate(&board, &mut masks));
+    assert_eq!(masks[0][1], ALL_CANDIDATES & !(1 << 3));
+    assert_eq!(masks[1][0], ALL_CANDIDATES & !(1 << 3));
+}
+
+#[test]
+fn propagation_solves_puzzles_with_few_givens() {
+    let board = Board::from(&[
+        [0, 0, 0, 0, 0, 0, 0, 6, 0], // row 1
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 2
+        [0, 0, 2, 0, 0, 0, 0, 3, 5], // row 3
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 4
+        [0, 0, 0, 6, 0, 0, 0, 0, 0], // row 5
+        [0, 0, 3, 0, 5, 0, 2, 0, 0], // row 6
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 7
+        [0, 0, 0, 1, 0, 5, 0, 0, 0], // row 8
+        [9, 0, 0, 0, 0, 0, 0, 5, 0], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [1, 3, 5, 2, 7, 9, 4, 6, 8], // row 1
+        [4, 6, 8, 5, 1, 3, 7, 9, 2], // row 2
+        [7, 9, 2, 8, 4, 6, 1, 3, 5], // row 3
+        [2, 4, 6, 3, 8, 1, 5, 7, 9], // row 4
+        [5, 7, 9, 6, 2, 4, 8, 1, 3], // row 5
+        [8, 1, 3, 9, 5, 7, 2, 4, 6], // row 6
+        [3, 5, 1, 7, 9, 2, 6, 8, 4], // row 7
+        [6, 8, 4, 1, 3, 5, 9, 2, 7], // row 8
+        [9, 2, 7, 4, 6, 8, 3, 5, 1], // row 9
+    ]);
+
+    let rules = crate::Rules::classic().with(NonConsecutive);
+    assert!(rules.propagates());
+
+    let mut solutions = crate::SolutionIter::with_rules(&board, rules);
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn violations_are_detected() {
+    let mut board = Board::default();
+    board.set_cell(4, 4, 5);
+    board.set_cell(5, 5, 6);
+    assert!(NonConsecutive.valid(&board));
+
+    board.set_cell(4, 5, 4);
+    assert!(!NonConsecutive.valid(&board));
+
+    board.set_cell(4, 5, 3);
+    assert!(NonConsecutive.valid(&board));
+
+    board.set_cell(5, 4, 6);
+    assert!(!NonConsecutive.valid(&board));
+}
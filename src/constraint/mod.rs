@@ -0,0 +1,670 @@
+//! # Constraints
+//!
+//! The rules of a puzzle are expressed as a set of constraints, each of which implements the
+//! [`Constraint`] trait.  The classic sudoku rules are provided by the [`Rows`], [`Columns`] and
+//! [`Squares`] constraints, and variants are built by adding further constraints to a [`Rules`]
+//! value.
+//!
+//! ## Example
+//!
+//! A constraint which forbids the digit 5 from appearing in the top-left cell:
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! #[derive(Debug)]
+//! struct NoFiveInCorner;
+//!
+//! impl Constraint for NoFiveInCorner {
+//!     fn candidates(&self, _board: &Board, x: usize, y: usize) -> u16 {
+//!         if (x, y) == (0, 0) {
+//!             ALL_CANDIDATES & !(1 << 5)
+//!         } else {
+//!             ALL_CANDIDATES
+//!         }
+//!     }
+//!
+//!     fn valid(&self, board: &Board) -> bool {
+//!         board.get_cell(0, 0) != 5
+//!     }
+//! }
+//!
+//! let rules = Rules::classic().with(NoFiveInCorner);
+//! let solution = SolutionIter::with_rules(&Board::default(), rules).next().unwrap();
+//!
+//! assert_ne!(solution.get_cell(0, 0), 5);
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::sync::Arc;
+
+use super::board::*;
+
+/// A candidate mask in which every digit from 1 to 9 is permitted.
+pub const ALL_CANDIDATES: u16 = 0b11_1111_1110;
+
+/// A candidate mask for every cell of the board, indexed as `masks[y][x]`.
+pub type CandidateMasks = [[u16; BOARD_SIZE]; BOARD_SIZE];
+
+/// A rule which a puzzle solution must obey.
+///
+/// The solver consults each constraint in two ways:
+///
+/// * [`candidates()`] narrows down the digits which may be placed in an empty cell.  It is called
+///   repeatedly during the search, so should be cheap to evaluate.
+/// * [`valid()`] tests whether a board state obeys the constraint.  It is used to check the
+///   initial puzzle and every completed grid before it is reported as a solution.
+///
+/// Constraints which relate the candidates of neighbouring cells may also implement
+/// [`propagate()`], which the solver calls repeatedly until no more candidates can be removed.
+///
+/// [`candidates()`]: Constraint::candidates
+/// [`valid()`]: Constraint::valid
+/// [`propagate()`]: Constraint::propagate
+pub trait Constraint: std::fmt::Debug + Send + Sync {
+    /// Returns a mask of the digits which this constraint permits in the empty cell at column `x`
+    /// and row `y`, given the current state of the board.
+    ///
+    /// Bit `n` of the mask is set if digit `n` may be placed.  Bit 0 is ignored.  A constraint
+    /// which does not apply to the cell should return [`ALL_CANDIDATES`].
+    ///
+    /// It is always safe to permit too many digits, at the expense of a slower search, but a digit
+    /// which could appear in a solution must never be excluded.
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16;
+
+    /// Test whether a board state obeys this constraint.
+    ///
+    /// Zeroes represent unfilled cells, so a partially filled board is valid as long as nothing
+    /// placed so far breaks the constraint.
+    fn valid(&self, board: &Board) -> bool;
+
+    /// Returns `true` if this constraint implements [`propagate()`].
+    ///
+    /// The solver only builds the full grid of candidates needed for propagation when at least
+    /// one constraint asks for it.
+    ///
+    /// [`propagate()`]: Constraint::propagate
+    fn propagates(&self) -> bool {
+        false
+    }
+
+    /// Narrow down the candidates of empty cells, using the candidates of the other cells.
+    ///
+    /// `masks` holds the candidates of each empty cell, as permitted by every constraint, and the
+    /// digit of each filled cell as a single bit.  Only the masks of empty cells may be changed,
+    /// and only by removing digits which cannot appear in any solution.  Returns `true` if any
+    /// mask was changed.
+    ///
+    /// The default implementation does nothing.
+    fn propagate(&self, _board: &Board, _masks: &mut CandidateMasks) -> bool {
+        false
+    }
+}
+
+/// Returns the set of digits placed in the given cells, as a mask.
+#[inline]
+pub(crate) fn used_digits(b: &Board, cells: impl IntoIterator<Item = (usize, usize)>) -> u16 {
+    cells
+        .into_iter()
+        .fold(0, |acc, (x, y)| acc | b.get_cell_as_mask(x, y))
+        & !1
+}
+
+/// Test whether the given cells contain no repeated digits.  Unfilled cells are ignored.
+#[inline]
+pub(crate) fn distinct_digits(b: &Board, cells: impl IntoIterator<Item = (usize, usize)>) -> bool {
+    let mut acc = 0;
+
+    for (x, y) in cells {
+        let mask = b.get_cell_as_mask(x, y);
+
+        if mask != 1 {
+            if acc & mask != 0 {
+                return false;
+            }
+
+            acc |= mask;
+        }
+    }
+
+    true
+}
+
+/// Remove candidates within a group of cells whose digits must be distinct.
+///
+/// A digit which is the only candidate of a cell is removed from the other cells.
+pub(crate) fn propagate_distinct(
+    b: &Board,
+    masks: &mut CandidateMasks,
+    cells: impl Iterator<Item = (usize, usize)> + Clone,
+) -> bool {
+    let mut changed = false;
+    let mut singles = 0;
+
+    for (x, y) in cells.clone() {
+        if masks[y][x].count_ones() == 1 {
+            singles |= masks[y][x];
+        }
+    }
+
+    for (x, y) in cells.clone() {
+        if b.get_cell(x, y) == 0 && masks[y][x].count_ones() > 1 && masks[y][x] & singles != 0 {
+            masks[y][x] &= !singles;
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Remove candidates within a house of nine cells, which must contain every digit exactly once.
+///
+/// A digit which is the only candidate of an empty cell is removed from the other cells, and a
+/// digit which can only go in one cell of the house is placed there.
+pub(crate) fn propagate_house(
+    b: &Board,
+    masks: &mut CandidateMasks,
+    cells: impl Iterator<Item = (usize, usize)> + Clone,
+) -> bool {
+    let mut changed = propagate_distinct(b, masks, cells.clone());
+
+    // Hidden singles.
+    let mut seen_once = 0;
+    let mut seen_twice = 0;
+
+    for (x, y) in cells.clone() {
+        seen_twice |= seen_once & masks[y][x];
+        seen_once |= masks[y][x];
+    }
+
+    if seen_once & ALL_CANDIDATES != ALL_CANDIDATES {
+        // Some digit has nowhere to go, so empty a cell to signal the contradiction.
+        if let Some((x, y)) = cells
+            .clone()
+            .find(|&(x, y)| masks[y][x] != 0 && b.get_cell(x, y) == 0)
+        {
+            masks[y][x] = 0;
+            return true;
+        }
+    }
+
+    let hidden = seen_once & !seen_twice;
+
+    for (x, y) in cells {
+        let mask = masks[y][x] & hidden;
+
+        if b.get_cell(x, y) == 0 && mask != 0 && mask != masks[y][x] {
+            masks[y][x] = mask;
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// No digit may be repeated within a row.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rows;
+
+impl Constraint for Rows {
+    #[inline]
+    fn candidates(&self, board: &Board, _x: usize, y: usize) -> u16 {
+        ALL_CANDIDATES & !used_digits(board, (0..BOARD_SIZE).map(|i| (i, y)))
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        (0..BOARD_SIZE).all(|y| distinct_digits(board, (0..BOARD_SIZE).map(|i| (i, y))))
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        (0..BOARD_SIZE).fold(false, |changed, y| {
+            propagate_house(board, masks, (0..BOARD_SIZE).map(move |i| (i, y))) | changed
+        })
+    }
+}
+
+/// No digit may be repeated within a column.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Columns;
+
+impl Constraint for Columns {
+    #[inline]
+    fn candidates(&self, board: &Board, x: usize, _y: usize) -> u16 {
+        ALL_CANDIDATES & !used_digits(board, (0..BOARD_SIZE).map(|i| (x, i)))
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        (0..BOARD_SIZE).all(|x| distinct_digits(board, (0..BOARD_SIZE).map(|i| (x, i))))
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        (0..BOARD_SIZE).fold(false, |changed, x| {
+            propagate_house(board, masks, (0..BOARD_SIZE).map(move |i| (x, i))) | changed
+        })
+    }
+}
+
+/// No digit may be repeated within a square.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Squares;
+
+impl Squares {
+    fn cells(x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + Clone {
+        let xs = SQUARE_SIZE * (x / SQUARE_SIZE);
+        let ys = SQUARE_SIZE * (y / SQUARE_SIZE);
+
+        (0..BOARD_SIZE).map(move |i| (xs + (i % SQUARE_SIZE), ys + (i / SQUARE_SIZE)))
+    }
+}
+
+impl Constraint for Squares {
+    #[inline]
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        ALL_CANDIDATES & !used_digits(board, Squares::cells(x, y))
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        (0..BOARD_SIZE).all(|square| {
+            let x = SQUARE_SIZE * (square % SQUARE_SIZE);
+            let y = SQUARE_SIZE * (square / SQUARE_SIZE);
+
+            distinct_digits(board, Squares::cells(x, y))
+        })
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        (0..BOARD_SIZE).fold(false, |changed, square| {
+            let x = SQUARE_SIZE * (square % SQUARE_SIZE);
+            let y = SQUARE_SIZE * (square / SQUARE_SIZE);
+
+            propagate_house(board, masks, Squares::cells(x, y)) | changed
+        })
+    }
+}
+
+/// No digit may be repeated along either of the two main diagonals, as in X-sudoku.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagonals;
+
+impl Diagonals {
+    fn leading() -> impl Iterator<Item = (usize, usize)> + Clone {
+        (0..BOARD_SIZE).map(|i| (i, i))
+    }
+
+    fn trailing() -> impl Iterator<Item = (usize, usize)> + Clone {
+        (0..BOARD_SIZE).map(|i| (BOARD_SIZE - 1 - i, i))
+    }
+}
+
+impl Constraint for Diagonals {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        let mut cs = ALL_CANDIDATES;
+
+        if x == y {
+            cs &= !used_digits(board, Diagonals::leading());
+        }
+
+        if x + y == BOARD_SIZE - 1 {
+            cs &= !used_digits(board, Diagonals::trailing());
+        }
+
+        cs
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        distinct_digits(board, Diagonals::leading())
+            && distinct_digits(board, Diagonals::trailing())
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        propagate_house(board, masks, Diagonals::leading())
+            | propagate_house(board, masks, Diagonals::trailing())
+    }
+}
+
+/// No digit may be repeated within any of the four extra 3×3 windows of a Windoku (or hyper
+/// sudoku).  The windows are offset by one cell from the edges of the board, with one cell
+/// between them.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Windows;
+
+impl Windows {
+    /// The column or row at which the window covering coordinate `i` starts, if there is one.
+    #[inline]
+    fn start(i: usize) -> Option<usize> {
+        match i {
+            1..=3 => Some(1),
+            5..=7 => Some(5),
+            _ => None,
+        }
+    }
+
+    fn cells(xs: usize, ys: usize) -> impl Iterator<Item = (usize, usize)> + Clone {
+        (0..BOARD_SIZE).map(move |i| (xs + (i % SQUARE_SIZE), ys + (i / SQUARE_SIZE)))
+    }
+
+    fn all() -> impl Iterator<Item = (usize, usize)> {
+        [(1, 1), (5, 1), (1, 5), (5, 5)].into_iter()
+    }
+}
+
+impl Constraint for Windows {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        match (Windows::start(x), Windows::start(y)) {
+            (Some(xs), Some(ys)) => ALL_CANDIDATES & !used_digits(board, Windows::cells(xs, ys)),
+            _ => ALL_CANDIDATES,
+        }
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        Windows::all().all(|(xs, ys)| distinct_digits(board, Windows::cells(xs, ys)))
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        Windows::all().fold(false, |changed, (xs, ys)| {
+            propagate_house(board, masks, Windows::cells(xs, ys)) | changed
+        })
+    }
+}
+
+/// No digit may be repeated among the cells which occupy the same position within each square,
+/// as in disjoint groups sudoku.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisjointGroups;
+
+impl DisjointGroups {
+    fn cells(x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + Clone {
+        let xo = x % SQUARE_SIZE;
+        let yo = y % SQUARE_SIZE;
+
+        (0..BOARD_SIZE).map(move |i| {
+            (
+                xo + SQUARE_SIZE * (i % SQUARE_SIZE),
+                yo + SQUARE_SIZE * (i / SQUARE_SIZE),
+            )
+        })
+    }
+}
+
+impl Constraint for DisjointGroups {
+    #[inline]
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        ALL_CANDIDATES & !used_digits(board, DisjointGroups::cells(x, y))
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        (0..BOARD_SIZE).all(|group| {
+            distinct_digits(
+                board,
+                DisjointGroups::cells(group % SQUARE_SIZE, group / SQUARE_SIZE),
+            )
+        })
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        (0..BOARD_SIZE).fold(false, |changed, group| {
+            let cells = DisjointGroups::cells(group % SQUARE_SIZE, group / SQUARE_SIZE);
+
+            propagate_house(board, masks, cells) | changed
+        })
+    }
+}
+
+/// An error arising from an invalid house definition.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum HouseError {
+    /// A house contains no cells.
+    Empty,
+
+    /// A house contains more cells than there are distinct digits.
+    TooManyCells(usize),
+
+    /// A cell lies outside the board.
+    CellOutOfRange(usize, usize),
+
+    /// A cell appears more than once in the same house.
+    DuplicateCell(usize, usize),
+}
+
+impl std::fmt::Display for HouseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HouseError::Empty => write!(f, "house contains no cells"),
+            HouseError::TooManyCells(n) => write!(f, "house contains too many cells ({})", n),
+            HouseError::CellOutOfRange(x, y) => write!(f, "cell ({}, {}) is off the board", x, y),
+            HouseError::DuplicateCell(x, y) => {
+                write!(f, "cell ({}, {}) appears more than once in a house", x, y)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HouseError {}
+
+/// An extra house: a set of up to nine cells, chosen by the puzzle setter, which may not repeat a
+/// digit.
+///
+/// Houses can describe many variants without a dedicated constraint.  A house of nine cells must
+/// contain every digit, and takes part in the same candidate propagation as the rows, columns and
+/// squares.
+///
+/// ## Example
+///
+/// A centre-dot sudoku, in which the middle cells of the squares form a house:
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let centres: Vec<(usize, usize)> = (0..BOARD_SIZE)
+///     .map(|i| (3 * (i % 3) + 1, 3 * (i / 3) + 1))
+///     .collect();
+///
+/// let rules = Rules::classic().with(House::new(&centres).unwrap());
+/// let solution = SolutionIter::with_rules(&Board::default(), rules.clone()).next().unwrap();
+///
+/// assert!(valid_with_rules(&solution, &rules));
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct House {
+    cells: Vec<(usize, usize)>,
+}
+
+impl House {
+    /// Create a `House` from a list of cells, given as `(x, y)` pairs.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// assert!(House::new(&[(0, 0), (4, 4), (8, 8)]).is_ok());
+    /// assert_eq!(
+    ///     House::new(&[(0, 0), (0, 0)]),
+    ///     Err(HouseError::DuplicateCell(0, 0))
+    /// );
+    /// # }
+    /// ```
+    pub fn new(cells: &[(usize, usize)]) -> Result<Self, HouseError> {
+        if cells.is_empty() {
+            return Err(HouseError::Empty);
+        }
+
+        if cells.len() > BOARD_SIZE {
+            return Err(HouseError::TooManyCells(cells.len()));
+        }
+
+        for (i, &(x, y)) in cells.iter().enumerate() {
+            if x >= BOARD_SIZE || y >= BOARD_SIZE {
+                return Err(HouseError::CellOutOfRange(x, y));
+            }
+
+            if cells[..i].contains(&(x, y)) {
+                return Err(HouseError::DuplicateCell(x, y));
+            }
+        }
+
+        Ok(Self {
+            cells: cells.to_vec(),
+        })
+    }
+
+    /// The cells in the house, as `(x, y)` pairs.
+    #[inline]
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+}
+
+impl Constraint for House {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        if self.cells.contains(&(x, y)) {
+            ALL_CANDIDATES & !used_digits(board, self.cells.iter().copied())
+        } else {
+            ALL_CANDIDATES
+        }
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        distinct_digits(board, self.cells.iter().copied())
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        if self.cells.len() == BOARD_SIZE {
+            propagate_house(board, masks, self.cells.iter().copied())
+        } else {
+            propagate_distinct(board, masks, self.cells.iter().copied())
+        }
+    }
+}
+
+/// The set of constraints which together define the rules of a puzzle.
+///
+/// The default value contains the classic sudoku constraints.
+///
+/// ## Example
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let mut board = Board::default();
+/// board.set_cell(0, 0, 9);
+/// board.set_cell(8, 8, 9);
+///
+/// let x_sudoku = Rules::classic().with(Diagonals);
+///
+/// assert!(Rules::classic().valid(&board));
+/// assert!(!x_sudoku.valid(&board));
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rules {
+    constraints: Vec<Arc<dyn Constraint>>,
+    propagates: bool,
+}
+
+impl Rules {
+    /// Create a `Rules` value with no constraints at all.
+    pub fn empty() -> Self {
+        Self {
+            constraints: Vec::new(),
+            propagates: false,
+        }
+    }
+
+    /// Create a `Rules` value containing the classic sudoku constraints: no digit may be repeated
+    /// in any row, column or square.
+    pub fn classic() -> Self {
+        Self::empty().with(Rows).with(Columns).with(Squares)
+    }
+
+    /// Add a constraint, returning the updated `Rules`.
+    pub fn with(mut self, constraint: impl Constraint + 'static) -> Self {
+        self.add(constraint);
+        self
+    }
+
+    /// Add a constraint.
+    pub fn add(&mut self, constraint: impl Constraint + 'static) {
+        self.propagates |= constraint.propagates();
+        self.constraints.push(Arc::new(constraint));
+    }
+
+    /// Iterate over the constraints.
+    pub fn constraints(&self) -> impl Iterator<Item = &dyn Constraint> {
+        self.constraints.iter().map(|c| c.as_ref())
+    }
+
+    /// Returns a mask of the digits which every constraint permits in the empty cell at column `x`
+    /// and row `y`.
+    #[inline]
+    pub fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        self.constraints
+            .iter()
+            .fold(ALL_CANDIDATES, |cs, c| cs & c.candidates(board, x, y))
+    }
+
+    /// Returns `true` if any constraint implements [`Constraint::propagate()`].
+    #[inline]
+    pub fn propagates(&self) -> bool {
+        self.propagates
+    }
+
+    /// Apply [`Constraint::propagate()`] for every constraint, until no more candidates can be
+    /// removed.
+    pub fn propagate(&self, board: &Board, masks: &mut CandidateMasks) {
+        while self
+            .constraints
+            .iter()
+            .fold(false, |changed, c| c.propagate(board, masks) | changed)
+        {}
+    }
+
+    /// Test whether a board state obeys every constraint, and contains only values from 0 to 9.
+    pub fn valid(&self, board: &Board) -> bool {
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                if board.get_cell(x, y) > 9 {
+                    return false;
+                }
+            }
+        }
+
+        self.constraints.iter().all(|c| c.valid(board))
+    }
+
+    /// Returns the constraints which a board state breaks, in the order they were added.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let mut board = Board::default();
+    /// board.set_cell(0, 0, 9);
+    /// board.set_cell(8, 8, 9);
+    ///
+    /// let rules = Rules::classic().with(Diagonals);
+    /// let violations = rules.violations(&board);
+    /// assert_eq!(format!("{:?}", violations), "[Diagonals]");
+    /// # }
+    /// ```
+    pub fn violations(&self, board: &Board) -> Vec<&dyn Constraint> {
+        self.constraints().filter(|c| !c.valid(board)).collect()
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
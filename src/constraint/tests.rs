@@ -0,0 +1,256 @@
+use super::*;
+
+#[test]
+fn classic_candidates_are_correct() {
+    let board = Board::from(&[
+        [0, 3, 5, 2, 0, 9, 7, 8, 0], // row 1
+        [6, 0, 2, 5, 0, 1, 4, 0, 3], // row 2
+        [1, 9, 0, 8, 0, 4, 0, 6, 2], // row 3
+        [8, 2, 6, 0, 0, 0, 3, 4, 7], // row 4
+        [3, 7, 4, 6, 0, 2, 9, 1, 5], // row 5
+        [9, 5, 1, 0, 0, 0, 6, 2, 8], // row 6
+        [5, 1, 0, 3, 0, 6, 0, 7, 4], // row 7
+        [2, 0, 8, 9, 0, 7, 1, 0, 6], // row 8
+        [0, 6, 3, 4, 1, 8, 2, 5, 0], // row 9
+    ]);
+
+    assert_eq!(Rows.candidates(&board, 0, 0), 0b00_0101_0010); // {1, 4, 6}
+    assert_eq!(Columns.candidates(&board, 0, 0), 0b00_1001_0000); // {4, 7}
+    assert_eq!(Squares.candidates(&board, 0, 0), 0b01_1001_0000); // {4, 7, 8}
+    assert_eq!(Rules::classic().candidates(&board, 0, 0), 0b00_0001_0000); // {4}
+}
+
+#[test]
+fn diagonal_candidates_are_correct() {
+    let mut board = Board::default();
+    board.set_cell(0, 0, 1);
+    board.set_cell(8, 8, 2);
+    board.set_cell(8, 0, 3);
+    board.set_cell(1, 7, 4);
+
+    assert_eq!(Diagonals.candidates(&board, 4, 4), 0b11_1110_0000); // {5, 6, 7, 8, 9}
+    assert_eq!(Diagonals.candidates(&board, 2, 2), 0b11_1111_1000); // {3, ..., 9}
+    assert_eq!(Diagonals.candidates(&board, 2, 6), 0b11_1110_0110); // {1, 2, 5, ..., 9}
+    assert_eq!(Diagonals.candidates(&board, 1, 0), ALL_CANDIDATES);
+}
+
+#[test]
+fn window_candidates_are_correct() {
+    let mut board = Board::default();
+    board.set_cell(1, 1, 1);
+    board.set_cell(3, 3, 2);
+    board.set_cell(5, 5, 3);
+    board.set_cell(4, 0, 4);
+
+    assert_eq!(Windows.candidates(&board, 2, 2), 0b11_1111_1000); // {3, ..., 9}
+    assert_eq!(Windows.candidates(&board, 7, 7), 0b11_1111_0110); // {1, 2, 4, ..., 9}
+    assert_eq!(Windows.candidates(&board, 4, 4), ALL_CANDIDATES);
+    assert_eq!(Windows.candidates(&board, 0, 2), ALL_CANDIDATES);
+
+    assert!(Windows.valid(&board));
+    board.set_cell(6, 7, 3);
+    assert!(!Windows.valid(&board));
+}
+
+#[test]
+fn disjoint_group_candidates_are_correct() {
+    let mut board = Board::default();
+    board.set_cell(0, 0, 1);
+    board.set_cell(3, 6, 2);
+    board.set_cell(1, 0, 3);
+
+    assert_eq!(DisjointGroups.candidates(&board, 6, 3), 0b11_1111_1000); // {3, ..., 9}
+    assert_eq!(DisjointGroups.candidates(&board, 4, 3), 0b11_1111_0110); // {1, 2, 4, ..., 9}
+
+    assert!(DisjointGroups.valid(&board));
+    board.set_cell(6, 6, 1);
+    assert!(!DisjointGroups.valid(&board));
+}
+
+#[test]
+fn invalid_houses_are_rejected() {
+    assert_eq!(House::new(&[]), Err(HouseError::Empty));
+    assert_eq!(
+        House::new(&[(0, 0), (0, 9)]),
+        Err(HouseError::CellOutOfRange(0, 9))
+    );
+
+    let cells: Vec<(usize, usize)> = (0..BOARD_SIZE + 1).map(|i| (i, 0)).collect();
+    assert_eq!(House::new(&cells), Err(HouseError::TooManyCells(10)));
+}
+
+#[test]
+fn house_candidates_are_correct() {
+    let house = House::new(&[(0, 0), (4, 4), (8, 8), (8, 0)]).unwrap();
+    let mut board = Board::default();
+    board.set_cell(0, 0, 1);
+    board.set_cell(8, 8, 2);
+    board.set_cell(4, 5, 3);
+
+    assert_eq!(house.candidates(&board, 4, 4), 0b11_1111_1000); // {3, ..., 9}
+    assert_eq!(house.candidates(&board, 4, 5), ALL_CANDIDATES);
+
+    assert!(house.valid(&board));
+    board.set_cell(8, 0, 2);
+    assert!(!house.valid(&board));
+}
+
+#[test]
+fn houses_generalise_diagonals() {
+    let leading: Vec<(usize, usize)> = (0..BOARD_SIZE).map(|i| (i, i)).collect();
+    let trailing: Vec<(usize, usize)> = (0..BOARD_SIZE).map(|i| (8 - i, i)).collect();
+    let houses = Rules::empty()
+        .with(House::new(&leading).unwrap())
+        .with(House::new(&trailing).unwrap());
+
+    let mut board = Board::default();
+    board.set_cell(0, 0, 1);
+    board.set_cell(8, 8, 2);
+    board.set_cell(8, 0, 3);
+    board.set_cell(1, 7, 4);
+
+    for (x, y) in [(4, 4), (2, 2), (2, 6), (1, 0)] {
+        assert_eq!(
+            houses.candidates(&board, x, y),
+            Diagonals.candidates(&board, x, y)
+        );
+    }
+}
+
+#[test]
+fn small_houses_only_propagate_naked_singles() {
+    let house = House::new(&[(0, 0), (1, 0)]).unwrap();
+    let board = Board::default();
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+
+    // No digit has to appear in a house of two cells.
+    assert!(!house.propagate(&board, &mut masks));
+
+    masks[0][0] = 1 << 4;
+    assert!(house.propagate(&board, &mut masks));
+    assert_eq!(masks[0][1], ALL_CANDIDATES & !(1 << 4));
+}
+
+#[test]
+fn empty_rules_accept_anything() {
+    let rules = Rules::empty();
+    let mut board = Board::default();
+    board.set_cell(0, 0, 1);
+    board.set_cell(1, 0, 1);
+
+    assert_eq!(rules.constraints().count(), 0);
+    assert_eq!(rules.candidates(&board, 2, 0), ALL_CANDIDATES);
+    assert!(rules.valid(&board));
+    assert!(!Rules::classic().valid(&board));
+}
+
+#[test]
+fn rules_can_be_extended() {
+    let mut rules = Rules::classic();
+    assert_eq!(rules.constraints().count(), 3);
+
+    rules.add(Diagonals);
+    assert_eq!(rules.constraints().count(), 4);
+
+    let rules = rules.with(Diagonals);
+    assert_eq!(rules.constraints().count(), 5);
+}
+
+/// A constraint which only rejects a completed grid, without narrowing down candidates.
+#[derive(Debug)]
+struct TopLeftIsNotFour;
+
+impl Constraint for TopLeftIsNotFour {
+    fn candidates(&self, _board: &Board, _x: usize, _y: usize) -> u16 {
+        ALL_CANDIDATES
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        board.get_cell(0, 0) != 4
+    }
+}
+
+#[test]
+fn completed_grids_are_checked_by_solver() {
+    let board = Board::from(&[
+        [0, 3, 5, 2, 6, 9, 7, 8, 0], // row 1
+        [6, 8, 2, 5, 7, 0, 4, 9, 3], // row 2
+        [0, 9, 7, 8, 3, 4, 5, 6, 2], // row 3
+        [8, 2, 6, 0, 9, 5, 3, 4, 7], // row 4
+        [3, 7, 4, 6, 8, 2, 9, 0, 5], // row 5
+        [9, 5, 0, 7, 4, 3, 6, 2, 8], // row 6
+        [5, 0, 9, 3, 2, 6, 8, 7, 4], // row 7
+        [2, 4, 8, 9, 5, 7, 0, 3, 6], // row 8
+        [7, 6, 3, 4, 0, 8, 2, 5, 9], // row 9
+    ]);
+
+    assert_eq!(crate::SolutionIter::new(&board).count(), 1);
+
+    let rules = Rules::classic().with(TopLeftIsNotFour);
+    assert_eq!(crate::SolutionIter::with_rules(&board, rules).count(), 0);
+}
+
+#[test]
+fn house_propagation_finds_singles() {
+    let board = Board::default();
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+
+    // A naked single is removed from the rest of the row.
+    masks[0][0] = 1 << 1;
+    assert!(Rows.propagate(&board, &mut masks));
+    assert_eq!(masks[0][0], 1 << 1);
+    assert_eq!(masks[0][8], ALL_CANDIDATES & !(1 << 1));
+
+    // 9 can now only go in the last cell of the column.
+    for row in masks.iter_mut().take(BOARD_SIZE - 1) {
+        row[4] &= !(1 << 9);
+    }
+
+    assert!(Columns.propagate(&board, &mut masks));
+    assert_eq!(masks[8][4], 1 << 9);
+
+    // Nothing more to do for the columns.
+    assert!(!Columns.propagate(&board, &mut masks));
+}
+
+#[test]
+fn diagonals_propagate_singles() {
+    let board = Board::default();
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+
+    // A naked single on the leading diagonal is removed from the rest of it.
+    masks[0][0] = 1 << 3;
+    assert!(Diagonals.propagate(&board, &mut masks));
+    assert_eq!(masks[8][8], ALL_CANDIDATES & !(1 << 3));
+    assert_eq!(masks[0][8], ALL_CANDIDATES);
+
+    // 6 can now only go in the bottom left cell of the trailing diagonal.
+    for i in 0..BOARD_SIZE - 1 {
+        masks[i][BOARD_SIZE - 1 - i] &= !(1 << 6);
+    }
+
+    assert!(Diagonals.propagate(&board, &mut masks));
+    assert_eq!(masks[8][0], 1 << 6);
+    assert!(!Diagonals.propagate(&board, &mut masks));
+}
+
+#[test]
+fn house_propagation_detects_missing_digits() {
+    let board = Board::default();
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+
+    for row in masks.iter_mut().take(SQUARE_SIZE) {
+        for mask in row.iter_mut().take(SQUARE_SIZE) {
+            *mask &= !(1 << 7);
+        }
+    }
+
+    assert!(Squares.propagate(&board, &mut masks));
+    assert!((0..SQUARE_SIZE).any(|y| masks[y].contains(&0)));
+}
+
+#[test]
+fn classic_rules_do_not_propagate_by_default() {
+    assert!(!Rules::classic().propagates());
+    assert!(!Rules::classic().with(Diagonals).propagates());
+}
@@ -0,0 +1,393 @@
+//! # Puzzle file formats
+//!
+//! Readers and writers for the plain-text formats used by common sudoku programs:
+//!
+//! * SDK: a single puzzle as nine lines of nine cells.  Lines starting with `#` hold comments or
+//!   metadata, and are ignored.
+//! * SDM: any number of puzzles, one per line, each written as 81 cells.
+//! * Simple Sudoku (`.ss`): a single puzzle as nine lines of nine cells, with the squares
+//!   separated by `|` within each line and by lines of `-` between each band of three rows.
+//! * SadMan Software Sudoku: a `[Puzzle]` section holding the givens, optionally followed by a
+//!   `[State]` section holding every digit placed so far.  Other sections are ignored.
+//!
+//! In each format, a digit from 1 to 9 is a filled cell and `.` or `0` is an unfilled one.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let board = read_ss(
+//!     "
+//!     ..3|.1.|...
+//!     ...|...|...
+//!     ...|...|...
+//!     -----------
+//!     ...|...|...
+//!     ...|.5.|...
+//!     ...|...|...
+//!     -----------
+//!     ...|...|...
+//!     ...|...|...
+//!     ...|...|..9
+//!     ",
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(board.get_cell(2, 0), 3);
+//! assert_eq!(read_sdk(&write_sdk(&board)), Ok(board));
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+
+/// An error arising from reading a puzzle file.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FormatError {
+    /// A character could not be understood.
+    InvalidCharacter {
+        /// The line number, counting from 1.
+        line: usize,
+        /// The offending character.
+        character: char,
+    },
+
+    /// A line does not contain the right number of cells.
+    WrongCellCount {
+        /// The line number, counting from 1.
+        line: usize,
+        /// The number of cells found.
+        count: usize,
+    },
+
+    /// A grid does not contain nine rows.
+    WrongLineCount(usize),
+
+    /// A required section of a SadMan file is missing.
+    MissingSection(&'static str),
+
+    /// A digit in the state of a SadMan file differs from the given in the same cell.
+    Conflict(usize, usize),
+}
+
+impl std::fmt::Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError::InvalidCharacter { line, character } => {
+                write!(f, "line {}: invalid character `{}`", line, character)
+            }
+            FormatError::WrongCellCount { line, count } => {
+                write!(f, "line {}: unexpected number of cells ({})", line, count)
+            }
+            FormatError::WrongLineCount(n) => write!(f, "expected 9 rows, found {}", n),
+            FormatError::MissingSection(name) => write!(f, "missing {} section", name),
+            FormatError::Conflict(x, y) => {
+                write!(f, "cell ({}, {}) differs from the given digit", x, y)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// A puzzle read from a SadMan Software Sudoku file.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SadManPuzzle {
+    /// The givens.
+    pub givens: Board,
+
+    /// The digits placed by the solver so far, not including the givens.
+    pub placed: Board,
+}
+
+impl SadManPuzzle {
+    /// Returns the current state of the puzzle: the givens together with the placed digits.
+    pub fn current(&self) -> Board {
+        let mut board = self.givens;
+
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let v = self.placed.get_cell(x, y);
+
+                if v != 0 && board.get_cell(x, y) == 0 {
+                    board.set_cell(x, y, v);
+                }
+            }
+        }
+
+        board
+    }
+}
+
+/// Returns the non-empty lines of `s`, trimmed and paired with their line numbers.
+fn numbered_lines(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty())
+}
+
+/// Read the cells in a line of text, ignoring whitespace and any character for which `skip`
+/// returns `true`.
+fn read_cells(
+    line: usize,
+    text: &str,
+    skip: impl Fn(char) -> bool,
+) -> Result<Vec<u8>, FormatError> {
+    text.chars()
+        .filter(|&c| !c.is_whitespace() && !skip(c))
+        .map(|c| match c {
+            '.' | '0' => Ok(0),
+            '1'..='9' => Ok(c as u8 - b'0'),
+            character => Err(FormatError::InvalidCharacter { line, character }),
+        })
+        .collect()
+}
+
+/// Read a board from nine lines of nine cells each.
+fn read_grid(
+    lines: &[(usize, &str)],
+    skip: impl Fn(char) -> bool + Copy,
+) -> Result<Board, FormatError> {
+    if lines.len() != BOARD_SIZE {
+        return Err(FormatError::WrongLineCount(lines.len()));
+    }
+
+    let mut board = Board::default();
+
+    for (y, &(line, text)) in lines.iter().enumerate() {
+        let cells = read_cells(line, text, skip)?;
+
+        if cells.len() != BOARD_SIZE {
+            return Err(FormatError::WrongCellCount {
+                line,
+                count: cells.len(),
+            });
+        }
+
+        for (x, &v) in cells.iter().enumerate() {
+            board.set_cell(x, y, v);
+        }
+    }
+
+    Ok(board)
+}
+
+/// Write a board as nine lines of nine cells, with `.` for unfilled cells.
+fn write_grid(board: &Board) -> String {
+    let mut s = String::new();
+
+    for y in 0..BOARD_SIZE {
+        for x in 0..BOARD_SIZE {
+            s.push(board.cell_char(x, y, '.'));
+        }
+
+        s.push('\n');
+    }
+
+    s
+}
+
+/// Read a puzzle in the SDK format.
+///
+/// ## Example
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let board = read_sdk(
+///     "
+///     #A An example puzzle
+///     .2.......
+///     ...6....3
+///     .74.8....
+///     .....3..2
+///     .8..4..1.
+///     6..5.....
+///     ....1.78.
+///     5....9...
+///     .......4.
+///     ",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(board.get_cell(1, 0), 2);
+/// # }
+/// ```
+pub fn read_sdk(s: &str) -> Result<Board, FormatError> {
+    let lines: Vec<(usize, &str)> = numbered_lines(s)
+        .filter(|(_, l)| !l.starts_with('#'))
+        .collect();
+
+    read_grid(&lines, |_| false)
+}
+
+/// Write a puzzle in the SDK format, with `.` for unfilled cells.
+pub fn write_sdk(board: &Board) -> String {
+    write_grid(board)
+}
+
+/// Read the puzzles in the SDM format, one per line.
+///
+/// ## Example
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let puzzles = read_sdm(&format!("{}\n{}\n", "0".repeat(81), "1".repeat(81))).unwrap();
+///
+/// assert_eq!(puzzles.len(), 2);
+/// assert_eq!(puzzles[1].get_cell(8, 8), 1);
+/// # }
+/// ```
+pub fn read_sdm(s: &str) -> Result<Vec<Board>, FormatError> {
+    numbered_lines(s)
+        .filter(|(_, l)| !l.starts_with('#'))
+        .map(|(line, text)| {
+            let cells = read_cells(line, text, |_| false)?;
+
+            if cells.len() != BOARD_SIZE * BOARD_SIZE {
+                return Err(FormatError::WrongCellCount {
+                    line,
+                    count: cells.len(),
+                });
+            }
+
+            let mut board = Board::default();
+
+            for (i, &v) in cells.iter().enumerate() {
+                board.set_cell(i % BOARD_SIZE, i / BOARD_SIZE, v);
+            }
+
+            Ok(board)
+        })
+        .collect()
+}
+
+/// Write puzzles in the SDM format, one per line, with `0` for unfilled cells.
+pub fn write_sdm(boards: &[Board]) -> String {
+    let mut s = String::new();
+
+    for board in boards {
+        for i in 0..BOARD_SIZE * BOARD_SIZE {
+            s.push(board.cell_char(i % BOARD_SIZE, i / BOARD_SIZE, '0'));
+        }
+
+        s.push('\n');
+    }
+
+    s
+}
+
+/// Read a puzzle in the Simple Sudoku format.  The `|` separators are optional, and lines made
+/// up only of `-`, `+` and `|` are ignored.
+pub fn read_ss(s: &str) -> Result<Board, FormatError> {
+    let lines: Vec<(usize, &str)> = numbered_lines(s)
+        .filter(|(_, l)| !l.chars().all(|c| matches!(c, '-' | '+' | '|')))
+        .collect();
+
+    read_grid(&lines, |c| c == '|')
+}
+
+/// Write a puzzle in the Simple Sudoku format.
+pub fn write_ss(board: &Board) -> String {
+    let mut s = String::new();
+
+    for y in 0..BOARD_SIZE {
+        if y != 0 && y % SQUARE_SIZE == 0 {
+            s.push_str("-----------\n");
+        }
+
+        for x in 0..BOARD_SIZE {
+            if x != 0 && x % SQUARE_SIZE == 0 {
+                s.push('|');
+            }
+
+            s.push(board.cell_char(x, y, '.'));
+        }
+
+        s.push('\n');
+    }
+
+    s
+}
+
+/// Read a SadMan Software Sudoku file.
+///
+/// ## Example
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let mut givens = Board::default();
+/// givens.set_cell(0, 0, 5);
+///
+/// let mut placed = Board::default();
+/// placed.set_cell(1, 0, 3);
+///
+/// let puzzle = SadManPuzzle { givens, placed };
+/// let text = write_sadman(&puzzle);
+///
+/// assert!(text.starts_with("[Puzzle]\n5........\n"));
+/// assert_eq!(read_sadman(&text), Ok(puzzle));
+/// assert_eq!(puzzle.current().get_cell(1, 0), 3);
+/// # }
+/// ```
+pub fn read_sadman(s: &str) -> Result<SadManPuzzle, FormatError> {
+    let mut sections: Vec<(&str, Vec<(usize, &str)>)> = vec![];
+
+    for (line, text) in numbered_lines(s) {
+        if text.starts_with('[') && text.ends_with(']') {
+            sections.push((text, vec![]));
+        } else if let Some((_, lines)) = sections.last_mut() {
+            lines.push((line, text));
+        }
+    }
+
+    let section = |name| {
+        sections
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, lines)| read_grid(lines, |_| false))
+            .transpose()
+    };
+
+    let puzzle = section("[Puzzle]")?;
+    let state = section("[State]")?;
+
+    let givens = puzzle.ok_or(FormatError::MissingSection("[Puzzle]"))?;
+    let mut placed = Board::default();
+
+    if let Some(state) = state {
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let (given, v) = (givens.get_cell(x, y), state.get_cell(x, y));
+
+                if given == 0 {
+                    placed.set_cell(x, y, v);
+                } else if v != 0 && v != given {
+                    return Err(FormatError::Conflict(x, y));
+                }
+            }
+        }
+    }
+
+    Ok(SadManPuzzle { givens, placed })
+}
+
+/// Write a SadMan Software Sudoku file.  The `[State]` section is only written if some digits
+/// have been placed.
+pub fn write_sadman(puzzle: &SadManPuzzle) -> String {
+    let mut s = format!("[Puzzle]\n{}", write_grid(&puzzle.givens));
+
+    if puzzle.placed != Board::default() {
+        s.push_str("[State]\n");
+        s.push_str(&write_grid(&puzzle.current()));
+    }
+
+    s
+}
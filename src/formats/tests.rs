@@ -0,0 +1,150 @@
+use super::*;
+
+fn example_board() -> Board {
+    Board::from(&[
+        [0, 2, 0, 0, 0, 0, 0, 0, 0], // row 1
+        [0, 0, 0, 6, 0, 0, 0, 0, 3], // row 2
+        [0, 7, 4, 0, 8, 0, 0, 0, 0], // row 3
+        [0, 0, 0, 0, 0, 3, 0, 0, 2], // row 4
+        [0, 8, 0, 0, 4, 0, 0, 1, 0], // row 5
+        [6, 0, 0, 5, 0, 0, 0, 0, 0], // row 6
+        [0, 0, 0, 0, 1, 0, 7, 8, 0], // row 7
+        [5, 0, 0, 0, 0, 9, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 4, 0], // row 9
+    ])
+}
+
+#[test]
+fn sdk_round_trip() {
+    let board = example_board();
+    let text = write_sdk(&board);
+
+    assert_eq!(
+        text,
+        ".2.......\n\
+         ...6....3\n\
+         .74.8....\n\
+         .....3..2\n\
+         .8..4..1.\n\
+         6..5.....\n\
+         ....1.78.\n\
+         5....9...\n\
+         .......4.\n"
+    );
+    assert_eq!(read_sdk(&text), Ok(board));
+    assert_eq!(read_sdk(&text.replace('.', "0")), Ok(board));
+}
+
+#[test]
+fn out_of_range_values_are_written_as_question_marks() {
+    let mut board = example_board();
+    board.set_cell(0, 0, 12);
+
+    assert!(write_sdk(&board).starts_with("?2.......\n"));
+    assert!(write_ss(&board).starts_with("?2.|"));
+    assert!(write_sdm(&[board]).starts_with("?2000000"));
+    assert_eq!(board.to_string().chars().next(), Some('?'));
+}
+
+#[test]
+fn sdk_errors() {
+    assert_eq!(
+        read_sdk("#comment\n.........\n"),
+        Err(FormatError::WrongLineCount(1))
+    );
+
+    let text = write_sdk(&example_board()).replacen(".2.", ".2", 1);
+    assert_eq!(
+        read_sdk(&text),
+        Err(FormatError::WrongCellCount { line: 1, count: 8 })
+    );
+
+    let text = format!("\n{}", write_sdk(&example_board()).replacen('3', "x", 1));
+    assert_eq!(
+        read_sdk(&text),
+        Err(FormatError::InvalidCharacter {
+            line: 3,
+            character: 'x'
+        })
+    );
+}
+
+#[test]
+fn sdm_round_trip() {
+    let boards = vec![example_board(), Board::default()];
+    let text = write_sdm(&boards);
+
+    assert_eq!(text.lines().count(), 2);
+    assert!(text.starts_with("020000000000600003"));
+    assert_eq!(read_sdm(&text), Ok(boards));
+    assert_eq!(read_sdm(""), Ok(vec![]));
+    assert_eq!(
+        read_sdm("123\n"),
+        Err(FormatError::WrongCellCount { line: 1, count: 3 })
+    );
+}
+
+#[test]
+fn ss_round_trip() {
+    let board = example_board();
+    let text = write_ss(&board);
+
+    assert_eq!(
+        text,
+        ".2.|...|...\n\
+         ...|6..|..3\n\
+         .74|.8.|...\n\
+         -----------\n\
+         ...|..3|..2\n\
+         .8.|.4.|.1.\n\
+         6..|5..|...\n\
+         -----------\n\
+         ...|.1.|78.\n\
+         5..|..9|...\n\
+         ...|...|.4.\n"
+    );
+    assert_eq!(read_ss(&text), Ok(board));
+    assert_eq!(read_ss(&write_sdk(&board)), Ok(board));
+}
+
+#[test]
+fn sadman_sections() {
+    let givens = example_board();
+    let mut state = givens;
+    state.set_cell(0, 0, 1);
+    state.set_cell(8, 8, 9);
+
+    let text = format!(
+        "[Puzzle]\n{}[Info]\nAuthor\n[State]\n{}",
+        write_sdk(&givens),
+        write_sdk(&state)
+    );
+    let puzzle = read_sadman(&text).unwrap();
+
+    assert_eq!(puzzle.givens, givens);
+    assert_eq!(puzzle.placed.get_cell(0, 0), 1);
+    assert_eq!(puzzle.placed.get_cell(1, 0), 0);
+    assert_eq!(puzzle.current(), state);
+    assert_eq!(read_sadman(&write_sadman(&puzzle)), Ok(puzzle));
+
+    let text = format!("[Puzzle]\n{}", write_sdk(&givens));
+    assert_eq!(write_sadman(&read_sadman(&text).unwrap()), text);
+}
+
+#[test]
+fn sadman_errors() {
+    assert_eq!(
+        read_sadman(&write_sdk(&example_board())),
+        Err(FormatError::MissingSection("[Puzzle]"))
+    );
+
+    let mut state = example_board();
+    state.set_cell(1, 0, 3);
+
+    let text = format!(
+        "[Puzzle]\n{}[State]\n{}",
+        write_sdk(&example_board()),
+        write_sdk(&state)
+    );
+    assert_eq!(read_sadman(&text), Err(FormatError::Conflict(1, 0)));
+}
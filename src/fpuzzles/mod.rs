@@ -0,0 +1,880 @@
+//! # f-puzzles import
+//!
+//! Variant puzzles are commonly shared as f-puzzles JSON, which SudokuPad also reads.  Links to
+//! such puzzles usually hold the JSON compressed with lz-string and encoded as base64, as in
+//! `https://www.f-puzzles.com/?load=N4IgzglgXgpiBc...`.
+//!
+//! An [`FPuzzle`] can be read from the JSON itself, from the compressed data or from a whole
+//! link, and holds the givens together with the [`Rules`] of the puzzle.  The following
+//! constraints are understood:
+//!
+//! * Irregular regions, from the `region` of each cell, and `extraregion`.
+//! * `diagonal+`, `diagonal-`, `antiknight`, `antiking`, `disjointgroups` and `nonconsecutive`.
+//! * `killercage`, `thermometer`, `arrow`, `sandwichsum`, `littlekillersum`, `odd` and `even`.
+//! * `difference` (white kropki dots), `ratio` (black kropki dots) and `xv`, with the negative
+//!   constraint if `negative` lists both `difference` and `ratio`, or `xv`.  The negative
+//!   constraint applies even if no markers of that kind are given, while a negative constraint
+//!   on only one kind of kropki dot is not supported.
+//! * `whispers` with a difference of 5, `renban` and `palindrome` lines.
+//!
+//! Cosmetic elements (`cage`, `line`, `rectangle`, `circle` and `text`) are ignored, as are the
+//! settings of the f-puzzles solver (`disabledlogic` and `truecandidatesoptions`).  Any other
+//! constraint is reported as [`FPuzzlesError::Unsupported`], so that a puzzle is never solved
+//! under the wrong rules.  Only givens are read from the grid; digits and pencilmarks entered
+//! while solving are ignored.
+//!
+//! This module is only available with the `fpuzzles` feature.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let json = r#"{
+//!     "size": 9,
+//!     "title": "Example",
+//!     "grid": [
+//!         [{"value": 5, "given": true}, {}, {}, {}, {}, {}, {}, {}, {}],
+//!         [{}, {}, {}, {}, {}, {}, {}, {}, {}],
+//!         [{}, {}, {}, {}, {}, {}, {}, {}, {}],
+//!         [{}, {}, {}, {}, {}, {}, {}, {}, {}],
+//!         [{}, {}, {}, {}, {}, {}, {}, {}, {}],
+//!         [{}, {}, {}, {}, {}, {}, {}, {}, {}],
+//!         [{}, {}, {}, {}, {}, {}, {}, {}, {}],
+//!         [{}, {}, {}, {}, {}, {}, {}, {}, {}],
+//!         [{}, {}, {}, {}, {}, {}, {}, {}, {}]
+//!     ],
+//!     "diagonal+": true,
+//!     "killercage": [{"cells": ["R1C2", "R1C3"], "value": "3"}]
+//! }"#;
+//!
+//! let puzzle = FPuzzle::from_json(json).unwrap();
+//! assert_eq!(puzzle.title.as_deref(), Some("Example"));
+//! assert_eq!(puzzle.board.get_cell(0, 0), 5);
+//!
+//! let solution = SolutionIter::with_rules(&puzzle.board, puzzle.rules.clone())
+//!     .next()
+//!     .unwrap();
+//! assert_eq!(solution.get_cell(1, 0) + solution.get_cell(2, 0), 3);
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use super::arrow::*;
+use super::board::*;
+use super::chess::*;
+use super::consecutive::*;
+use super::constraint::*;
+use super::killer::*;
+use super::lines::*;
+use super::little_killer::*;
+use super::markers::*;
+use super::parity::*;
+use super::sandwich::*;
+use super::thermo::*;
+
+/// An error arising from reading an f-puzzles puzzle.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum FPuzzlesError {
+    /// The compressed data could not be decompressed.
+    Decompression,
+
+    /// The JSON is malformed, or does not have the expected structure.
+    Json(String),
+
+    /// The grid is not nine by nine.
+    UnsupportedSize(usize),
+
+    /// The puzzle uses a constraint which this crate does not support.
+    Unsupported(String),
+
+    /// A cell reference could not be understood.
+    InvalidCell(String),
+
+    /// A constraint could not be built from its definition.
+    InvalidConstraint {
+        /// The f-puzzles name of the constraint.
+        kind: String,
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+impl std::fmt::Display for FPuzzlesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FPuzzlesError::Decompression => write!(f, "could not decompress puzzle data"),
+            FPuzzlesError::Json(message) => write!(f, "invalid puzzle JSON: {}", message),
+            FPuzzlesError::UnsupportedSize(n) => write!(f, "unsupported grid size {}", n),
+            FPuzzlesError::Unsupported(kind) => write!(f, "unsupported constraint `{}`", kind),
+            FPuzzlesError::InvalidCell(cell) => write!(f, "invalid cell reference `{}`", cell),
+            FPuzzlesError::InvalidConstraint { kind, message } => {
+                write!(f, "invalid `{}` constraint: {}", kind, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FPuzzlesError {}
+
+/// A puzzle read from f-puzzles JSON.
+#[derive(Clone, Debug)]
+pub struct FPuzzle {
+    /// The givens.
+    pub board: Board,
+
+    /// The rules of the puzzle, including its regions.
+    pub rules: Rules,
+
+    /// The title of the puzzle, if it has one.
+    pub title: Option<String>,
+
+    /// The author of the puzzle, if known.
+    pub author: Option<String>,
+}
+
+/// The `(x, y)` coordinates of a cell.
+type Cell = (usize, usize);
+
+/// The region of each cell, or `None` for a cell outside every region.
+type Regions = [[Option<u64>; BOARD_SIZE]; BOARD_SIZE];
+
+/// Keys which hold information about the puzzle, rather than constraints, settings for the
+/// f-puzzles solver, or which are purely cosmetic.
+const IGNORED: &[&str] = &[
+    "size",
+    "grid",
+    "title",
+    "author",
+    "ruleset",
+    "solution",
+    "disabledlogic",
+    "truecandidatesoptions",
+    "cage",
+    "line",
+    "rectangle",
+    "circle",
+    "text",
+];
+
+impl FPuzzle {
+    /// Read a puzzle from f-puzzles JSON.
+    pub fn from_json(json: &str) -> Result<Self, FPuzzlesError> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|e| FPuzzlesError::Json(e.to_string()))?;
+        let object = value
+            .as_object()
+            .ok_or_else(|| FPuzzlesError::Json("expected an object".to_string()))?;
+
+        Reader { object }.read()
+    }
+
+    /// Read a puzzle from lz-string compressed data, as found in f-puzzles and SudokuPad links.
+    /// A whole link may be given, in which case the data is taken from after its `load=`
+    /// parameter, or after `fpuzzles` in the path of a SudokuPad link.
+    ///
+    /// Both the standard and the URL-safe base64 alphabets are accepted, as is `%`-encoding of
+    /// the data.
+    pub fn from_compressed(data: &str) -> Result<Self, FPuzzlesError> {
+        let data = data.trim();
+        let data = match (data.find("load="), data.find("fpuzzles")) {
+            (Some(i), _) => &data[i + 5..],
+            (None, Some(i)) if data.contains('/') => &data[i + 8..],
+            _ => data,
+        };
+        let data = data.split('&').next().unwrap_or(data);
+
+        let json =
+            decompress_from_base64(&percent_decode(data)).ok_or(FPuzzlesError::Decompression)?;
+
+        Self::from_json(&json)
+    }
+}
+
+/// Read a puzzle from either f-puzzles JSON or compressed data, as with
+/// [`FPuzzle::from_json()`] or [`FPuzzle::from_compressed()`].
+impl FromStr for FPuzzle {
+    type Err = FPuzzlesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim_start().starts_with('{') {
+            Self::from_json(s)
+        } else {
+            Self::from_compressed(s)
+        }
+    }
+}
+
+/// Builds an [`FPuzzle`] from the top-level object of the JSON.
+struct Reader<'a> {
+    object: &'a Map<String, Value>,
+}
+
+impl Reader<'_> {
+    fn read(&self) -> Result<FPuzzle, FPuzzlesError> {
+        let size = self.object.get("size").and_then(Value::as_u64).unwrap_or(9) as usize;
+
+        if size != BOARD_SIZE {
+            return Err(FPuzzlesError::UnsupportedSize(size));
+        }
+
+        let (board, regions) = self.grid()?;
+        let mut rules = self.regions(regions)?;
+        let mut handled: HashSet<&str> = IGNORED.iter().copied().collect();
+
+        for (key, value) in self.object {
+            if !handled.contains(key.as_str()) && is_set(value) {
+                handled.insert(key);
+                self.add_constraint(&mut rules, key)?;
+            }
+        }
+
+        Ok(FPuzzle {
+            board,
+            rules,
+            title: self.string("title"),
+            author: self.string("author"),
+        })
+    }
+
+    fn string(&self, key: &str) -> Option<String> {
+        self.object
+            .get(key)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Read the givens and the region of each cell from the grid.
+    fn grid(&self) -> Result<(Board, Regions), FPuzzlesError> {
+        let mut board = Board::default();
+        let mut regions = [[None; BOARD_SIZE]; BOARD_SIZE];
+
+        for (y, row) in regions.iter_mut().enumerate() {
+            for (x, region) in row.iter_mut().enumerate() {
+                *region = Some((SQUARE_SIZE * (y / SQUARE_SIZE) + x / SQUARE_SIZE) as u64);
+            }
+        }
+
+        let rows = match self.object.get("grid") {
+            Some(grid) => json_array(grid, "grid")?,
+            None => return Ok((board, regions)),
+        };
+
+        if rows.len() != BOARD_SIZE {
+            return Err(FPuzzlesError::UnsupportedSize(rows.len()));
+        }
+
+        for (y, row) in rows.iter().enumerate() {
+            let cells = json_array(row, "grid")?;
+
+            if cells.len() != BOARD_SIZE {
+                return Err(FPuzzlesError::UnsupportedSize(cells.len()));
+            }
+
+            for (x, cell) in cells.iter().enumerate() {
+                let given = cell.get("given").and_then(Value::as_bool).unwrap_or(false);
+
+                if given {
+                    match cell.get("value").and_then(Value::as_u64) {
+                        Some(v @ 1..=9) => board.set_cell(x, y, v as u8),
+                        _ => {
+                            return Err(FPuzzlesError::Json(format!(
+                                "invalid given in r{}c{}",
+                                y + 1,
+                                x + 1
+                            )))
+                        }
+                    }
+                }
+
+                match cell.get("region") {
+                    None => {}
+                    Some(Value::Null) => regions[y][x] = None,
+                    Some(region) => {
+                        regions[y][x] = Some(region.as_u64().ok_or_else(|| {
+                            FPuzzlesError::Json(format!("invalid region in r{}c{}", y + 1, x + 1))
+                        })?)
+                    }
+                }
+            }
+        }
+
+        Ok((board, regions))
+    }
+
+    /// Build the rules for the rows, columns and regions of the puzzle.
+    fn regions(&self, regions: Regions) -> Result<Rules, FPuzzlesError> {
+        let mut houses: HashMap<u64, Vec<Cell>> = HashMap::new();
+        let mut irregular = false;
+
+        for (y, row) in regions.iter().enumerate() {
+            for (x, &region) in row.iter().enumerate() {
+                irregular |=
+                    region != Some((SQUARE_SIZE * (y / SQUARE_SIZE) + x / SQUARE_SIZE) as u64);
+
+                if let Some(region) = region {
+                    houses.entry(region).or_default().push((x, y));
+                }
+            }
+        }
+
+        if !irregular {
+            return Ok(Rules::classic());
+        }
+
+        let mut rules = Rules::empty().with(Rows).with(Columns);
+        let mut houses: Vec<_> = houses.into_iter().collect();
+        houses.sort();
+
+        for (_, cells) in houses {
+            rules.add(House::new(&cells).map_err(|e| invalid("region", e))?);
+        }
+
+        Ok(rules)
+    }
+
+    /// Returns the entries of the array stored under `key`.
+    fn entries(&self, key: &str) -> Result<&[Value], FPuzzlesError> {
+        self.object
+            .get(key)
+            .map_or(Ok(&[][..]), |value| json_array(value, key))
+    }
+
+    /// Returns `true` if the `negative` list mentions every one of `kinds`.
+    fn negative(&self, kinds: &[&str]) -> bool {
+        let negative = self.object.get("negative").and_then(Value::as_array);
+
+        kinds
+            .iter()
+            .all(|kind| negative.is_some_and(|list| list.iter().any(|v| v.as_str() == Some(kind))))
+    }
+
+    fn add_constraint(&self, rules: &mut Rules, key: &str) -> Result<(), FPuzzlesError> {
+        match key {
+            "diagonal+" | "diagonal-" => {
+                if key == "diagonal-"
+                    && is_set(self.object.get("diagonal+").unwrap_or(&Value::Null))
+                {
+                    return Ok(());
+                }
+
+                if ["diagonal+", "diagonal-"]
+                    .iter()
+                    .all(|k| self.object.get(*k).is_some_and(is_set))
+                {
+                    rules.add(Diagonals);
+                } else {
+                    let cells: Vec<Cell> = (0..BOARD_SIZE)
+                        .map(|i| {
+                            if key == "diagonal-" {
+                                (i, i)
+                            } else {
+                                (i, BOARD_SIZE - 1 - i)
+                            }
+                        })
+                        .collect();
+                    rules.add(House::new(&cells).map_err(|e| invalid(key, e))?);
+                }
+            }
+            "antiknight" => rules.add(AntiKnight),
+            "antiking" => rules.add(AntiKing),
+            "disjointgroups" => rules.add(DisjointGroups),
+            "nonconsecutive" => rules.add(NonConsecutive),
+            "extraregion" => {
+                for entry in self.entries(key)? {
+                    rules.add(House::new(&cells(entry, "cells")?).map_err(|e| invalid(key, e))?);
+                }
+            }
+            "killercage" => {
+                let mut cages = vec![];
+
+                for entry in self.entries(key)? {
+                    let cells = cells(entry, "cells")?;
+
+                    match number(entry, key)? {
+                        Some(sum) => {
+                            cages.push(Cage::new(&cells, sum).map_err(|e| invalid(key, e))?)
+                        }
+                        None => rules.add(House::new(&cells).map_err(|e| invalid(key, e))?),
+                    }
+                }
+
+                rules.add(Cages::new(cages).map_err(|e| invalid(key, e))?);
+            }
+            "thermometer" => {
+                for entry in self.entries(key)? {
+                    for line in lines(entry)? {
+                        rules.add(Thermometer::new(&line).map_err(|e| invalid(key, e))?);
+                    }
+                }
+            }
+            "arrow" => {
+                let mut arrows = Vec::new();
+
+                for entry in self.entries(key)? {
+                    let circle = cells(entry, "cells")?;
+
+                    for line in lines(entry)? {
+                        let shaft: Vec<_> =
+                            line.into_iter().filter(|c| !circle.contains(c)).collect();
+                        arrows.push(Arrow::new(&circle, &shaft).map_err(|e| invalid(key, e))?);
+                    }
+                }
+
+                rules.add(Arrows::new(arrows));
+            }
+            "whispers" => {
+                for entry in self.entries(key)? {
+                    if number(entry, key)?.is_some_and(|v| v != 5) {
+                        return Err(FPuzzlesError::Unsupported(format!(
+                            "{} with a difference other than 5",
+                            key
+                        )));
+                    }
+
+                    for line in lines(entry)? {
+                        rules.add(Whisper::new(&line).map_err(|e| invalid(key, e))?);
+                    }
+                }
+            }
+            "renban" => {
+                for entry in self.entries(key)? {
+                    for line in lines(entry)? {
+                        rules.add(Renban::new(&line).map_err(|e| invalid(key, e))?);
+                    }
+                }
+            }
+            "palindrome" => {
+                for entry in self.entries(key)? {
+                    for line in lines(entry)? {
+                        rules.add(Palindrome::new(&line).map_err(|e| invalid(key, e))?);
+                    }
+                }
+            }
+            "odd" | "even" => {
+                let parity = if key == "odd" {
+                    Parity::Odd
+                } else {
+                    Parity::Even
+                };
+                let marked: Vec<_> = self
+                    .entries(key)?
+                    .iter()
+                    .map(|entry| Ok((cell(entry, "cell")?, parity)))
+                    .collect::<Result<_, FPuzzlesError>>()?;
+
+                rules.add(OddEven::new(&marked).map_err(|e| invalid(key, e))?);
+            }
+            "difference" | "ratio" => {
+                // Both kinds of dot make up a single set of kropki markers, so that the negative
+                // constraint can apply to both.
+                if key == "ratio" && self.object.get("difference").is_some_and(is_set) {
+                    return Ok(());
+                }
+
+                let mut markers = vec![];
+
+                for (kind, dot, expected) in [
+                    ("difference", KropkiDot::White, 1),
+                    ("ratio", KropkiDot::Black, 2),
+                ] {
+                    for entry in self.entries(kind)? {
+                        if number(entry, kind)?.is_some_and(|v| v != expected) {
+                            return Err(FPuzzlesError::Unsupported(format!(
+                                "{} of {}",
+                                kind, entry["value"]
+                            )));
+                        }
+
+                        let (a, b) = pair(entry)?;
+                        markers.push((a, b, dot));
+                    }
+                }
+
+                let kropki = Kropki::new(&markers).map_err(|e| invalid(key, e))?;
+                rules.add(if self.negative(&["difference", "ratio"]) {
+                    kropki.with_negative_constraint()
+                } else {
+                    kropki
+                });
+            }
+            "xv" => {
+                let mut markers = vec![];
+
+                for entry in self.entries(key)? {
+                    let sign = match entry.get("value").and_then(Value::as_str) {
+                        Some("X") | Some("x") => XVSign::X,
+                        Some("V") | Some("v") => XVSign::V,
+                        _ => return Err(invalid(key, "expected a value of X or V")),
+                    };
+
+                    let (a, b) = pair(entry)?;
+                    markers.push((a, b, sign));
+                }
+
+                let xv = XV::new(&markers).map_err(|e| invalid(key, e))?;
+                rules.add(if self.negative(&["xv"]) {
+                    xv.with_negative_constraint()
+                } else {
+                    xv
+                });
+            }
+            "negative" => {
+                // The negative constraint is added along with the markers when there are any, so
+                // only a grid without markers needs one here.
+                let kinds: Vec<_> = self
+                    .entries(key)?
+                    .iter()
+                    .map(|kind| {
+                        kind.as_str()
+                            .ok_or_else(|| invalid(key, "expected a list of constraint names"))
+                    })
+                    .collect::<Result<_, FPuzzlesError>>()?;
+                let dots = ["difference", "ratio"];
+                let has_markers = |kind: &str| self.object.get(kind).is_some_and(is_set);
+
+                for kind in &kinds {
+                    if !dots.contains(kind) && *kind != "xv" {
+                        return Err(FPuzzlesError::Unsupported(format!("{} {}", key, kind)));
+                    }
+                }
+
+                if dots.iter().any(|d| kinds.contains(d)) && !self.negative(&dots) {
+                    return Err(FPuzzlesError::Unsupported(format!(
+                        "{} with only one kind of kropki dot",
+                        key
+                    )));
+                }
+
+                if self.negative(&dots) && !dots.iter().any(|d| has_markers(d)) {
+                    let markers = Kropki::new(&[]).map_err(|e| invalid(key, e))?;
+                    rules.add(markers.with_negative_constraint());
+                }
+
+                if self.negative(&["xv"]) && !has_markers("xv") {
+                    let markers = XV::new(&[]).map_err(|e| invalid(key, e))?;
+                    rules.add(markers.with_negative_constraint());
+                }
+            }
+            "sandwichsum" => {
+                let mut rows = [None; BOARD_SIZE];
+                let mut columns = [None; BOARD_SIZE];
+
+                for entry in self.entries(key)? {
+                    let name = entry.get("cell").and_then(Value::as_str).unwrap_or("");
+                    let sum = number(entry, key)?;
+
+                    match parse_reference(name) {
+                        Some((0, c)) if (1..=BOARD_SIZE).contains(&c) => columns[c - 1] = sum,
+                        Some((r, 0)) if (1..=BOARD_SIZE).contains(&r) => rows[r - 1] = sum,
+                        _ => return Err(FPuzzlesError::InvalidCell(name.to_string())),
+                    }
+                }
+
+                rules.add(Sandwich::new(rows, columns).map_err(|e| invalid(key, e))?);
+            }
+            "littlekillersum" => {
+                let mut clues = vec![];
+
+                for entry in self.entries(key)? {
+                    let direction = match entry.get("direction").and_then(Value::as_str) {
+                        Some("DR") => Direction::DownRight,
+                        Some("DL") => Direction::DownLeft,
+                        Some("UR") => Direction::UpRight,
+                        Some("UL") => Direction::UpLeft,
+                        _ => return Err(invalid(key, "expected a direction of DR, DL, UR or UL")),
+                    };
+
+                    if let Some(sum) = number(entry, key)? {
+                        let start = cell(entry, "cellStart")?;
+                        clues.push(
+                            LittleKiller::new(start, direction, sum)
+                                .map_err(|e| invalid(key, e))?,
+                        );
+                    }
+                }
+
+                rules.add(LittleKillers::new(clues));
+            }
+            _ => return Err(FPuzzlesError::Unsupported(key.to_string())),
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if a top-level value turns a constraint on: `true`, or a non-empty array.
+fn is_set(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Array(a) => !a.is_empty(),
+        Value::Object(o) => !o.is_empty(),
+        _ => true,
+    }
+}
+
+fn invalid(kind: &str, error: impl std::fmt::Display) -> FPuzzlesError {
+    FPuzzlesError::InvalidConstraint {
+        kind: kind.to_string(),
+        message: error.to_string(),
+    }
+}
+
+fn json_array<'a>(value: &'a Value, key: &str) -> Result<&'a [Value], FPuzzlesError> {
+    value
+        .as_array()
+        .map(Vec::as_slice)
+        .ok_or_else(|| FPuzzlesError::Json(format!("expected `{}` to be an array", key)))
+}
+
+/// Parse the numbers of a cell reference such as `R1C2`, without checking their range.
+fn parse_reference(name: &str) -> Option<Cell> {
+    let (r, c) = name.strip_prefix(['R', 'r'])?.split_once(['C', 'c'])?;
+
+    Some((r.parse().ok()?, c.parse().ok()?))
+}
+
+/// Parse a cell reference such as `R1C2` into the `(x, y)` coordinates of a cell on the board.
+fn parse_cell(name: &str) -> Result<Cell, FPuzzlesError> {
+    match parse_reference(name) {
+        Some((r, c)) if (1..=BOARD_SIZE).contains(&r) && (1..=BOARD_SIZE).contains(&c) => {
+            Ok((c - 1, r - 1))
+        }
+        _ => Err(FPuzzlesError::InvalidCell(name.to_string())),
+    }
+}
+
+/// Returns the cell named by the string stored under `key`.
+fn cell(entry: &Value, key: &str) -> Result<Cell, FPuzzlesError> {
+    match entry.get(key) {
+        Some(Value::String(name)) => parse_cell(name),
+        _ => Err(FPuzzlesError::Json(format!(
+            "expected `{}` to be a cell",
+            key
+        ))),
+    }
+}
+
+/// Returns the cells named by the array of strings stored under `key`.
+fn cells(entry: &Value, key: &str) -> Result<Vec<Cell>, FPuzzlesError> {
+    entry
+        .get(key)
+        .map_or(Ok(Vec::new()), |value| cell_list(value, key))
+}
+
+/// Returns the cells named by `value`, an array of strings found under `key`.
+fn cell_list(value: &Value, key: &str) -> Result<Vec<Cell>, FPuzzlesError> {
+    json_array(value, key)?
+        .iter()
+        .map(|name| match name {
+            Value::String(name) => parse_cell(name),
+            _ => Err(FPuzzlesError::Json(format!(
+                "expected `{}` to hold cells",
+                key
+            ))),
+        })
+        .collect()
+}
+
+/// Returns the lines of an entry, each as a list of cells.
+fn lines(entry: &Value) -> Result<Vec<Vec<Cell>>, FPuzzlesError> {
+    entry
+        .get("lines")
+        .map_or(Ok(&[][..]), |value| json_array(value, "lines"))?
+        .iter()
+        .map(|line| cell_list(line, "lines"))
+        .collect()
+}
+
+/// Returns the two cells of a marker between adjacent cells.
+fn pair(entry: &Value) -> Result<(Cell, Cell), FPuzzlesError> {
+    match cells(entry, "cells")?[..] {
+        [a, b] => Ok((a, b)),
+        _ => Err(FPuzzlesError::Json("expected a pair of cells".to_string())),
+    }
+}
+
+/// Returns the number stored under `value`, which f-puzzles usually writes as a string.  A
+/// missing or empty value gives `None`.
+fn number(entry: &Value, kind: &str) -> Result<Option<u8>, FPuzzlesError> {
+    let value = match entry.get("value") {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(s)) if s.trim().is_empty() => return Ok(None),
+        Some(Value::String(s)) => s.trim().parse().ok(),
+        Some(Value::Number(n)) => n.as_u64().and_then(|n| u8::try_from(n).ok()),
+        Some(_) => None,
+    };
+
+    value
+        .map(Some)
+        .ok_or_else(|| invalid(kind, format!("invalid value {}", entry["value"])))
+}
+
+/// Undo `%`-encoding, as found in links which have been copied from some browsers.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                out.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// The characters of the base64 alphabet used by lz-string.
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Returns the value of a character in either the standard or the URL-safe base64 alphabet.  A
+/// space is treated as `+`, which it becomes when a link is decoded as a form.
+fn base64_value(c: char) -> Option<u32> {
+    match c {
+        ' ' | '+' => Some(62),
+        '/' | '-' => Some(63),
+        '=' | '$' => Some(64),
+        c => BASE64
+            .iter()
+            .position(|&b| b as char == c)
+            .map(|i| i as u32),
+    }
+}
+
+/// Reads values a given number of bits at a time from base64 characters, as lz-string does.
+struct BitReader {
+    values: Vec<u32>,
+    index: usize,
+    value: u32,
+    position: u32,
+}
+
+impl BitReader {
+    /// The bit of each character read first.
+    const RESET: u32 = 32;
+
+    fn read(&mut self, bits: u32) -> u32 {
+        let mut result = 0;
+
+        for i in 0..bits {
+            let bit = self.value & self.position;
+            self.position >>= 1;
+
+            if self.position == 0 {
+                self.position = Self::RESET;
+                self.value = self.values.get(self.index).copied().unwrap_or(0);
+                self.index += 1;
+            }
+
+            if bit != 0 {
+                result |= 1 << i;
+            }
+        }
+
+        result
+    }
+}
+
+/// Decode base64 and decompress the result with lz-string, as lz-string's
+/// `decompressFromBase64` does.  Returns `None` if the data is corrupt.
+pub(crate) fn decompress_from_base64(s: &str) -> Option<String> {
+    let values = s.chars().map(base64_value).collect::<Option<Vec<u32>>>()?;
+
+    if values.is_empty() {
+        return Some(String::new());
+    }
+
+    let length = values.len();
+    let mut reader = BitReader {
+        value: values[0],
+        values,
+        index: 1,
+        position: BitReader::RESET,
+    };
+
+    // The first three entries stand for the codes which introduce a new character or end the
+    // data.
+    let mut dictionary: Vec<Vec<u16>> = vec![vec![], vec![], vec![]];
+    let mut enlarge_in = 4u32;
+    let mut num_bits = 3u32;
+
+    let c = match reader.read(2) {
+        0 => reader.read(8),
+        1 => reader.read(16),
+        2 => return Some(String::new()),
+        _ => return None,
+    } as u16;
+
+    dictionary.push(vec![c]);
+    let mut w = vec![c];
+    let mut result = vec![c];
+
+    loop {
+        if reader.index > length {
+            return None;
+        }
+
+        let mut code = reader.read(num_bits) as usize;
+
+        match code {
+            0 | 1 => {
+                let c = reader.read(if code == 0 { 8 } else { 16 }) as u16;
+                dictionary.push(vec![c]);
+                code = dictionary.len() - 1;
+                enlarge_in -= 1;
+            }
+            2 => return String::from_utf16(&result).ok(),
+            _ => {}
+        }
+
+        if enlarge_in == 0 {
+            enlarge_in = 1 << num_bits;
+            num_bits += 1;
+        }
+
+        let entry = match dictionary.get(code) {
+            Some(entry) => entry.clone(),
+            None if code == dictionary.len() => {
+                let mut entry = w.clone();
+                entry.push(w[0]);
+                entry
+            }
+            None => return None,
+        };
+
+        result.extend_from_slice(&entry);
+
+        let mut next = w;
+        next.push(entry[0]);
+        dictionary.push(next);
+        enlarge_in -= 1;
+        w = entry;
+
+        if enlarge_in == 0 {
+            enlarge_in = 1 << num_bits;
+            num_bits += 1;
+        }
+    }
+}
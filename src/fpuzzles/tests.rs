@@ -0,0 +1,581 @@
+use super::*;
+
+/// The state of lz-string compression, which writes dictionary entries a given number of bits
+/// at a time, with the number of bits growing along with the dictionary.
+struct Compressor {
+    dictionary: HashMap<Vec<u16>, u32>,
+    to_create: HashSet<Vec<u16>>,
+    enlarge_in: u32,
+    num_bits: u32,
+    value: u32,
+    position: u32,
+    out: String,
+}
+
+impl Compressor {
+    /// Each output character holds six bits.
+    const BITS_PER_CHAR: u32 = 6;
+
+    fn write(&mut self, mut value: u32, bits: u32) {
+        for _ in 0..bits {
+            self.value = (self.value << 1) | (value & 1);
+            value >>= 1;
+
+            if self.position == Self::BITS_PER_CHAR - 1 {
+                self.out.push(BASE64[self.value as usize] as char);
+                self.position = 0;
+                self.value = 0;
+            } else {
+                self.position += 1;
+            }
+        }
+    }
+
+    fn enlarge(&mut self) {
+        self.enlarge_in -= 1;
+
+        if self.enlarge_in == 0 {
+            self.enlarge_in = 1 << self.num_bits;
+            self.num_bits += 1;
+        }
+    }
+
+    /// Write the dictionary entry for `w`, first writing its character if it is new.
+    fn emit(&mut self, w: &[u16]) {
+        if self.to_create.remove(w) {
+            let c = w[0] as u32;
+
+            if c < 256 {
+                self.write(0, self.num_bits);
+                self.write(c, 8);
+            } else {
+                self.write(1, self.num_bits);
+                self.write(c, 16);
+            }
+
+            self.enlarge();
+        } else {
+            self.write(self.dictionary[w], self.num_bits);
+        }
+
+        self.enlarge();
+    }
+
+    fn insert(&mut self, entry: Vec<u16>) {
+        let index = self.dictionary.len() as u32 + 3;
+        self.dictionary.insert(entry, index);
+    }
+}
+
+/// Compress a string with lz-string and encode it as base64, as lz-string's `compressToBase64`
+/// does.
+fn compress_to_base64(s: &str) -> String {
+    let mut state = Compressor {
+        dictionary: HashMap::new(),
+        to_create: HashSet::new(),
+        enlarge_in: 2,
+        num_bits: 2,
+        value: 0,
+        position: 0,
+        out: String::new(),
+    };
+    let mut w: Vec<u16> = vec![];
+
+    for c in s.encode_utf16() {
+        if !state.dictionary.contains_key(&[c][..]) {
+            state.insert(vec![c]);
+            state.to_create.insert(vec![c]);
+        }
+
+        let mut wc = w.clone();
+        wc.push(c);
+
+        if state.dictionary.contains_key(&wc) {
+            w = wc;
+        } else {
+            state.emit(&w);
+            state.insert(wc);
+            w = vec![c];
+        }
+    }
+
+    if !w.is_empty() {
+        state.emit(&w);
+    }
+
+    state.write(2, state.num_bits);
+
+    // Flush the last character.
+    loop {
+        state.value <<= 1;
+
+        if state.position == Compressor::BITS_PER_CHAR - 1 {
+            state.out.push(BASE64[state.value as usize] as char);
+            break;
+        }
+
+        state.position += 1;
+    }
+
+    while state.out.len() % 4 != 0 {
+        state.out.push('=');
+    }
+
+    state.out
+}
+
+/// Returns the names of the types of the constraints in `rules`.
+fn constraint_names(rules: &Rules) -> Vec<String> {
+    rules
+        .constraints()
+        .map(|c| {
+            let debug = format!("{:?}", c);
+            debug
+                .split(|c: char| !c.is_alphanumeric())
+                .next()
+                .unwrap()
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn lz_string_round_trip() {
+    for s in [
+        "",
+        "a",
+        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+        "Hello, world!",
+        "abababababababababab cdcdcdcdcd abababab",
+        "ünïcödé → ✓ 数独",
+        r#"{"size":9,"grid":[[{},{},{}]],"title":"Test puzzle"}"#,
+    ] {
+        let compressed = compress_to_base64(s);
+
+        assert!(compressed.len() % 4 == 0);
+        assert_eq!(decompress_from_base64(&compressed).as_deref(), Some(s));
+    }
+
+    let long: String = (0..2000)
+        .map(|i| char::from(b'a' + (i * i % 26) as u8))
+        .collect();
+    assert_eq!(
+        decompress_from_base64(&compress_to_base64(&long)),
+        Some(long)
+    );
+}
+
+#[test]
+fn lz_string_rejects_bad_data() {
+    assert_eq!(decompress_from_base64("!!!!"), None);
+    assert_eq!(decompress_from_base64("A"), None);
+}
+
+fn empty_grid() -> Value {
+    serde_json::json!(vec![vec![serde_json::json!({}); 9]; 9])
+}
+
+#[test]
+fn read_givens_and_metadata() {
+    let mut grid = empty_grid();
+    grid[0][1] = serde_json::json!({"value": 3, "given": true});
+    grid[8][8] = serde_json::json!({"value": 4});
+
+    let json = serde_json::json!({
+        "size": 9,
+        "author": "Someone",
+        "ruleset": "Normal sudoku rules apply.",
+        "grid": grid,
+        "line": [{"lines": [["R1C1", "R2C2"]], "outlineC": "#000000"}],
+        "minimum": [],
+        "antiknight": false,
+    });
+
+    let puzzle = FPuzzle::from_json(&json.to_string()).unwrap();
+
+    assert_eq!(puzzle.board.get_cell(1, 0), 3);
+    assert_eq!(puzzle.board.get_cell(8, 8), 0);
+    assert_eq!(puzzle.title, None);
+    assert_eq!(puzzle.author.as_deref(), Some("Someone"));
+    assert_eq!(
+        constraint_names(&puzzle.rules),
+        vec!["Rows", "Columns", "Squares"]
+    );
+}
+
+#[test]
+fn read_exports_with_solver_settings() {
+    // As exported by f-puzzles, with pencilmarks and colours in the grid and the solver's
+    // settings alongside the puzzle.
+    let mut grid = empty_grid();
+    grid[0][0] = serde_json::json!({"value": 5, "given": true, "region": 0});
+    grid[0][1] = serde_json::json!({"centerPencilMarks": [1, 2], "cornerPencilMarks": [3]});
+    grid[0][2] = serde_json::json!({"c": "#FFA0A0", "highlight": "#A0A0FF"});
+
+    let json = serde_json::json!({
+        "size": 9,
+        "title": "Export",
+        "grid": grid,
+        "solution": [],
+        "disabledlogic": ["Tuples", "Pointing"],
+        "truecandidatesoptions": ["colored"],
+    });
+
+    let puzzle = FPuzzle::from_json(&json.to_string()).unwrap();
+
+    assert_eq!(puzzle.board.get_cell(0, 0), 5);
+    assert_eq!(puzzle.board.get_cell(1, 0), 0);
+    assert_eq!(puzzle.board.get_cell(2, 0), 0);
+    assert_eq!(
+        constraint_names(&puzzle.rules),
+        vec!["Rows", "Columns", "Squares"]
+    );
+}
+
+#[test]
+fn read_constraints() {
+    let json = serde_json::json!({
+        "size": 9,
+        "grid": empty_grid(),
+        "diagonal+": true,
+        "diagonal-": true,
+        "antiking": true,
+        "disjointgroups": true,
+        "nonconsecutive": true,
+        "extraregion": [{"cells": ["R1C1", "R1C2"]}],
+        "killercage": [
+            {"cells": ["R1C1", "R1C2"], "value": "4"},
+            {"cells": ["R9C1", "R9C2"]},
+        ],
+        "thermometer": [{"lines": [["R2C1", "R2C2", "R2C3"], ["R2C1", "R3C1"]]}],
+        "arrow": [{"cells": ["R5C5"], "lines": [["R5C5", "R6C6", "R7C7"]]}],
+        "whispers": [{"lines": [["R8C1", "R8C2"]], "value": 5}],
+        "renban": [{"lines": [["R8C4", "R8C5"]]}],
+        "palindrome": [{"lines": [["R8C7", "R8C8", "R8C9"]]}],
+        "odd": [{"cell": "R4C4"}],
+        "even": [{"cell": "R4C5"}],
+        "difference": [{"cells": ["R3C3", "R3C4"]}],
+        "ratio": [{"cells": ["R3C6", "R3C7"], "value": "2"}],
+        "negative": ["ratio", "difference"],
+        "xv": [{"cells": ["R4C1", "R4C2"], "value": "X"}],
+        "sandwichsum": [{"cell": "R0C3", "value": "10"}, {"cell": "R4C0", "value": 0}],
+        "littlekillersum": [{"cell": "R0C0", "cellStart": "R1C1", "direction": "DR", "value": "45"}],
+    });
+
+    let puzzle = FPuzzle::from_json(&json.to_string()).unwrap();
+    let mut names = constraint_names(&puzzle.rules);
+    names.sort();
+
+    assert_eq!(
+        names,
+        vec![
+            "AntiKing",
+            "Arrows",
+            "Cages",
+            "Columns",
+            "Diagonals",
+            "DisjointGroups",
+            "House",
+            "House",
+            "LittleKillers",
+            "NonConsecutive",
+            "OddEven",
+            "OddEven",
+            "PairMarkers",
+            "PairMarkers",
+            "Palindrome",
+            "Renban",
+            "Rows",
+            "Sandwich",
+            "Squares",
+            "Thermometer",
+            "Thermometer",
+            "Whisper",
+        ]
+    );
+
+    let mut board = Board::default();
+    board.set_cell(0, 0, 1);
+    board.set_cell(1, 0, 3);
+    assert!(puzzle.rules.valid(&board));
+
+    board.set_cell(1, 0, 2);
+    assert!(!puzzle.rules.valid(&board));
+}
+
+#[test]
+fn read_single_diagonal() {
+    let json = serde_json::json!({"grid": empty_grid(), "diagonal+": true});
+    let puzzle = FPuzzle::from_json(&json.to_string()).unwrap();
+
+    let mut board = Board::default();
+    board.set_cell(0, 8, 5);
+    board.set_cell(8, 0, 5);
+    assert!(!puzzle.rules.valid(&board));
+
+    let mut board = Board::default();
+    board.set_cell(0, 0, 5);
+    board.set_cell(8, 8, 5);
+    assert!(puzzle.rules.valid(&board));
+}
+
+#[test]
+fn read_irregular_regions() {
+    // Each row is a region.
+    let mut grid = empty_grid();
+
+    for y in 0..BOARD_SIZE {
+        for x in 0..BOARD_SIZE {
+            grid[y][x] = serde_json::json!({ "region": y });
+        }
+    }
+
+    let json = serde_json::json!({ "grid": grid });
+    let puzzle = FPuzzle::from_json(&json.to_string()).unwrap();
+    let names = constraint_names(&puzzle.rules);
+
+    assert_eq!(names.len(), 11);
+    assert!(!names.contains(&"Squares".to_string()));
+
+    let mut board = Board::default();
+    board.set_cell(0, 0, 5);
+    board.set_cell(1, 1, 5);
+    assert!(puzzle.rules.valid(&board));
+
+    // A region which is too large.
+    grid[1][0] = serde_json::json!({ "region": 0 });
+    let json = serde_json::json!({ "grid": grid });
+    assert!(matches!(
+        FPuzzle::from_json(&json.to_string()),
+        Err(FPuzzlesError::InvalidConstraint { .. })
+    ));
+}
+
+#[test]
+fn read_compressed_links() {
+    let mut grid = empty_grid();
+    grid[4][4] = serde_json::json!({"value": 7, "given": true});
+
+    let json = serde_json::json!({"size": 9, "title": "Link", "grid": grid}).to_string();
+    let data = compress_to_base64(&json);
+
+    for link in [
+        data.clone(),
+        format!("https://www.f-puzzles.com/?load={}", data),
+        format!("https://sudokupad.app/fpuzzles{}", data),
+        format!(
+            "https://www.f-puzzles.com/?load={}&solve=1",
+            data.replace('+', "%2B")
+                .replace('/', "%2F")
+                .replace('=', "%3D")
+        ),
+        data.replace('/', "-").replace('=', "$"),
+    ] {
+        let puzzle: FPuzzle = link.parse().unwrap();
+        assert_eq!(puzzle.title.as_deref(), Some("Link"));
+        assert_eq!(puzzle.board.get_cell(4, 4), 7);
+    }
+
+    assert!(json.parse::<FPuzzle>().is_ok());
+    assert!(matches!(
+        "not compressed!".parse::<FPuzzle>(),
+        Err(FPuzzlesError::Decompression)
+    ));
+}
+
+/// A link to a puzzle whose JSON was compressed outside this crate, by lz-string's JavaScript
+/// `compressToBase64` as f-puzzles uses it, so that the decoder is not only checked against the
+/// compressor above.
+const FIXTURE_LINK: &str = concat!(
+    "https://www.f-puzzles.com/?load=",
+    "N4IgzglgXgpiBcBOANCALhNAbO8QDEIAPNAVwCc5UBDUtACwHtyEQAVGMNEVc0nMDG54AcswC21LAAIw",
+    "pACaMA1qWl8B06gActWAJ4A6HiADm5CPIQBtK6ABuU0rgCsqExDswAdgjR8YAL7IwEEhwaEgDlhOCADM",
+    "bh7evv6hqeHhALrItmn2jrgA7AmePvB+TrmV6SFZOdVVYY2ptU31ba012R25kfkIKKaJpeWB7c1dDZP1",
+    "LRFRMfAATMVJZSlj65113RvjW1PbedG4AIzLwykZWSDyENQmjF5SANTJTqhKEFg45ADGd7i2EA/GBfMD",
+    "WEAAJViAGECsYodCABwgK5zXAgY7OEABK4MGDkcSMcRCfHWUBYCBeTjWKyQxDQ47wxEM+EFFmXHEBIA=",
+);
+
+/// The JSON held by [`FIXTURE_LINK`].
+const FIXTURE_JSON: &str = concat!(
+    r#"{"size":9,"title":"Fixture","author":"Test","ruleset":"Normal sudoku rules apply"#,
+    r#".","grid":[[{"value":5,"given":true},{},{},{},{"value":3,"given":true},{},{},{},"#,
+    r#"{}],[{},{},{"value":7,"given":true},{},{},{},{},{},{}],[{},{},{},{},{},{},{},{},"#,
+    r#"{}],[{},{},{},{},{},{},{},{},{}],[{},{},{},{},{"value":9,"given":true},{},{},{},"#,
+    r#"{}],[{},{},{},{},{},{},{},{},{}],[{},{"value":2,"given":true},{},{},{},{},{},{},"#,
+    r#"{}],[{},{},{},{},{},{},{},{},{}],[{},{},{},{},{},{},{},{},{"value":1,"given":tru"#,
+    r#"e}]],"diagonal+":true,"killercage":[{"cells":["R3C7","R3C8"],"value":"15"}],"the"#,
+    r#"rmometer":[{"lines":[["R9C1","R8C1","R7C1"]]}]}"#,
+);
+
+#[test]
+fn read_fixture_link() {
+    let data = &FIXTURE_LINK[FIXTURE_LINK.find("load=").unwrap() + 5..];
+
+    assert_eq!(decompress_from_base64(data).as_deref(), Some(FIXTURE_JSON));
+    assert_eq!(compress_to_base64(FIXTURE_JSON), data);
+
+    let puzzle: FPuzzle = FIXTURE_LINK.parse().unwrap();
+
+    assert_eq!(puzzle.title.as_deref(), Some("Fixture"));
+    assert_eq!(puzzle.author.as_deref(), Some("Test"));
+
+    let givens: Vec<((usize, usize), u8)> = (0..BOARD_SIZE * BOARD_SIZE)
+        .map(|i| (i % BOARD_SIZE, i / BOARD_SIZE))
+        .map(|(x, y)| ((x, y), puzzle.board.get_cell(x, y)))
+        .filter(|&(_, v)| v != 0)
+        .collect();
+    assert_eq!(
+        givens,
+        vec![
+            ((0, 0), 5),
+            ((4, 0), 3),
+            ((2, 1), 7),
+            ((4, 4), 9),
+            ((1, 6), 2),
+            ((8, 8), 1),
+        ]
+    );
+
+    assert_eq!(
+        constraint_names(&puzzle.rules),
+        vec![
+            "Rows",
+            "Columns",
+            "Squares",
+            "House",
+            "Cages",
+            "Thermometer"
+        ]
+    );
+
+    // The cage in r3c7-r3c8 sums to 15, and the thermometer rises from r9c1 up the first column.
+    let mut board = puzzle.board;
+    board.set_cell(6, 2, 6);
+    board.set_cell(7, 2, 9);
+    board.set_cell(0, 8, 3);
+    board.set_cell(0, 7, 4);
+    assert!(puzzle.rules.valid(&board));
+
+    board.set_cell(7, 2, 8);
+    assert!(!puzzle.rules.valid(&board));
+
+    board.set_cell(7, 2, 9);
+    board.set_cell(0, 8, 8);
+    assert!(!puzzle.rules.valid(&board));
+}
+
+#[test]
+fn report_errors() {
+    assert_eq!(
+        FPuzzle::from_json(r#"{"size": 6}"#).unwrap_err(),
+        FPuzzlesError::UnsupportedSize(6)
+    );
+    assert!(matches!(
+        FPuzzle::from_json("[1, 2]"),
+        Err(FPuzzlesError::Json(_))
+    ));
+    assert!(matches!(
+        FPuzzle::from_json("{"),
+        Err(FPuzzlesError::Json(_))
+    ));
+    assert_eq!(
+        FPuzzle::from_json(r#"{"clone": [{"cells": ["R1C1"], "cloneCells": ["R9C9"]}]}"#)
+            .unwrap_err(),
+        FPuzzlesError::Unsupported("clone".to_string())
+    );
+    assert!(matches!(
+        FPuzzle::from_json(r#"{"whispers": [{"lines": [["R1C1", "R1C2"]], "value": 4}]}"#),
+        Err(FPuzzlesError::Unsupported(_))
+    ));
+    assert_eq!(
+        FPuzzle::from_json(r#"{"odd": [{"cell": "R10C1"}]}"#).unwrap_err(),
+        FPuzzlesError::InvalidCell("R10C1".to_string())
+    );
+    assert_eq!(
+        FPuzzle::from_json(r#"{"killercage": [{"cells": ["R1C1", "R1C2"], "value": "18"}]}"#)
+            .unwrap_err(),
+        FPuzzlesError::InvalidConstraint {
+            kind: "killercage".to_string(),
+            message: "no combination of digits adds to 18".to_string()
+        }
+    );
+}
+
+#[test]
+fn read_negative_constraints_without_markers() {
+    let json = serde_json::json!({"grid": empty_grid(), "negative": ["xv"]});
+    let puzzle = FPuzzle::from_json(&json.to_string()).unwrap();
+
+    assert!(constraint_names(&puzzle.rules).contains(&"PairMarkers".to_string()));
+
+    let mut board = Board::default();
+    board.set_cell(0, 0, 2);
+    board.set_cell(1, 0, 3);
+    assert!(!puzzle.rules.valid(&board));
+
+    board.set_cell(1, 0, 4);
+    assert!(puzzle.rules.valid(&board));
+
+    let json = serde_json::json!({"grid": empty_grid(), "negative": ["ratio", "difference"]});
+    let puzzle = FPuzzle::from_json(&json.to_string()).unwrap();
+
+    let mut board = Board::default();
+    board.set_cell(0, 0, 3);
+    board.set_cell(0, 1, 6);
+    assert!(!puzzle.rules.valid(&board));
+
+    board.set_cell(0, 1, 4);
+    assert!(!puzzle.rules.valid(&board));
+
+    board.set_cell(0, 1, 5);
+    assert!(puzzle.rules.valid(&board));
+}
+
+#[test]
+fn read_negative_constraints_with_markers() {
+    let json = serde_json::json!({
+        "grid": empty_grid(),
+        "xv": [{"cells": ["R1C1", "R1C2"], "value": "V"}],
+        "negative": ["xv"],
+    });
+    let puzzle = FPuzzle::from_json(&json.to_string()).unwrap();
+
+    assert_eq!(
+        constraint_names(&puzzle.rules)
+            .iter()
+            .filter(|name| *name == "PairMarkers")
+            .count(),
+        1
+    );
+
+    let mut board = Board::default();
+    board.set_cell(0, 0, 2);
+    board.set_cell(1, 0, 3);
+    board.set_cell(0, 1, 4);
+    board.set_cell(1, 1, 6);
+    assert!(!puzzle.rules.valid(&board));
+
+    board.set_cell(1, 1, 8);
+    assert!(puzzle.rules.valid(&board));
+}
+
+#[test]
+fn report_one_sided_negative_kropki() {
+    for negative in ["ratio", "difference"] {
+        for dots in [
+            serde_json::json!([]),
+            serde_json::json!([{"cells": ["R1C1", "R1C2"]}]),
+        ] {
+            let json = serde_json::json!({
+                "grid": empty_grid(),
+                "difference": dots,
+                "negative": [negative],
+            });
+
+            assert!(matches!(
+                FPuzzle::from_json(&json.to_string()),
+                Err(FPuzzlesError::Unsupported(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,562 @@
+//! # HoDoKu interchange
+//!
+//! Logical solvers such as HoDoKu and Sudoku Explainer describe a solution as a series of steps,
+//! each applying a named technique to place digits or eliminate candidates.  This module reads
+//! and writes two of HoDoKu's text formats, so that such steps can be exchanged and compared:
+//!
+//! * A [`LibraryEntry`] is one line of a HoDoKu puzzle library, holding a puzzle state, the
+//!   technique which applies to it, the expected result and an optional rating.
+//! * A [`Step`] is one line of a solution log, such as
+//!   `Locked Candidates Type 1 (Pointing): 5 in b4 => r6c7<>5, r6c8<>5`.
+//!
+//! This crate solves puzzles by searching rather than by logic, so it does not produce steps of
+//! its own.  Steps and library entries are only read, written and checked for consistency.
+//!
+//! Cells in all of these formats are named by row and column, counting from 1, so `r1c2` is the cell at
+//! `(1, 0)` in the coordinates used elsewhere in the crate.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let step: Step = "Naked Single: r3c5=7".parse().unwrap();
+//!
+//! assert_eq!(step.technique, "Naked Single");
+//! assert_eq!(step.placements, vec![((4, 2), 7)]);
+//! assert_eq!(step.to_string(), "Naked Single: r3c5=7");
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::str::FromStr;
+
+use super::board::*;
+
+/// A digit in a cell, given as the `(x, y)` coordinates of the cell and the digit.
+pub type CellDigit = ((usize, usize), u8);
+
+/// An error arising from reading a HoDoKu library entry or solution step.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum HodokuError {
+    /// A library entry does not have the right number of `:`-separated fields.
+    WrongFieldCount(usize),
+
+    /// The rating of a library entry is not a whole number, or that of a Sudoku Explainer step
+    /// is not a number with at most one decimal place.
+    InvalidRating(String),
+
+    /// The grid of a library entry does not contain 81 cells.
+    WrongCellCount(usize),
+
+    /// A character in the grid of a library entry could not be understood.
+    InvalidCharacter(char),
+
+    /// A candidate, placement or elimination could not be understood.
+    InvalidCandidate(String),
+
+    /// A solution step does not name its technique.
+    MissingTechnique(String),
+}
+
+impl std::fmt::Display for HodokuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HodokuError::WrongFieldCount(n) => write!(f, "expected 7 or 8 fields, found {}", n),
+            HodokuError::InvalidRating(s) => write!(f, "invalid rating `{}`", s),
+            HodokuError::WrongCellCount(n) => write!(f, "expected 81 cells, found {}", n),
+            HodokuError::InvalidCharacter(c) => write!(f, "invalid character `{}`", c),
+            HodokuError::InvalidCandidate(s) => write!(f, "invalid candidate `{}`", s),
+            HodokuError::MissingTechnique(s) => write!(f, "no technique in step `{}`", s),
+        }
+    }
+}
+
+impl std::error::Error for HodokuError {}
+
+/// Parse a non-empty run of digits from 1 to 9, such as the `78` of `r6c78`.
+fn parse_digits(s: &str, original: &str) -> Result<Vec<u8>, HodokuError> {
+    if s.is_empty() || !s.bytes().all(|b| (b'1'..=b'9').contains(&b)) {
+        return Err(HodokuError::InvalidCandidate(original.to_string()));
+    }
+
+    Ok(s.bytes().map(|b| b - b'0').collect())
+}
+
+/// Parse a space-separated list of candidates in the library notation, in which each candidate
+/// is written as its digit, row and column, so that `512` is the digit 5 in `r1c2`.
+fn parse_candidate_list(s: &str) -> Result<Vec<CellDigit>, HodokuError> {
+    s.split_whitespace()
+        .map(|item| match parse_digits(item, item)?[..] {
+            [d, r, c] => Ok(((c as usize - 1, r as usize - 1), d)),
+            _ => Err(HodokuError::InvalidCandidate(item.to_string())),
+        })
+        .collect()
+}
+
+fn candidate_list_to_string(candidates: &[CellDigit]) -> String {
+    candidates
+        .iter()
+        .map(|&((x, y), d)| format!("{}{}{}", d, y + 1, x + 1))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One line of a HoDoKu puzzle library.
+///
+/// Each line has the form
+/// `:technique:digits:grid:deleted:eliminations:placements:extra:`, where:
+///
+/// * `technique` is HoDoKu's code for the technique, such as `0100` for pointing locked
+///   candidates, optionally followed by a variant tag such as `-x`.
+/// * `digits` lists the digits the technique works on.
+/// * `grid` holds 81 cells: a digit for a given, a digit prefixed with `+` for a placed digit and
+///   `.` or `0` for an unfilled cell.
+/// * `deleted` lists the candidates already eliminated from the grid.
+/// * `eliminations` and `placements` list the candidates the technique should eliminate and the
+///   digits it should place.
+/// * `extra` holds any further information, such as the size of a fish.
+///
+/// A line may end with an eighth field, `:rating:`, giving the difficulty score of the puzzle as
+/// a whole number.  It is only written when the entry has a rating.
+///
+/// Candidates are written as their digit, row and column, so `512` is the digit 5 in `r1c2`.
+///
+/// ## Example
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let line = format!(":0000:5:1+2{}:::512::140:", ".".repeat(79));
+/// let entry: LibraryEntry = line.parse().unwrap();
+///
+/// assert_eq!(entry.technique, "0000");
+/// assert_eq!(entry.givens.get_cell(0, 0), 1);
+/// assert_eq!(entry.placed.get_cell(1, 0), 2);
+/// assert_eq!(entry.placements, vec![((1, 0), 5)]);
+/// assert_eq!(entry.rating, Some(140));
+/// assert_eq!(entry.to_string(), line);
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LibraryEntry {
+    /// HoDoKu's code for the technique, with any variant tag.
+    pub technique: String,
+
+    /// The digits the technique works on.
+    pub digits: Vec<u8>,
+
+    /// The givens of the puzzle.
+    pub givens: Board,
+
+    /// The digits placed so far, not including the givens.
+    pub placed: Board,
+
+    /// The candidates already eliminated.
+    pub deleted: Vec<CellDigit>,
+
+    /// The candidates the technique should eliminate.
+    pub eliminations: Vec<CellDigit>,
+
+    /// The digits the technique should place.
+    pub placements: Vec<CellDigit>,
+
+    /// Any further information.
+    pub extra: String,
+
+    /// The difficulty score of the puzzle, if the entry has one.
+    pub rating: Option<u32>,
+}
+
+impl FromStr for LibraryEntry {
+    type Err = HodokuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix(':').unwrap_or(s);
+        let fields: Vec<&str> = s.strip_suffix(':').unwrap_or(s).split(':').collect();
+
+        if fields.len() != 7 && fields.len() != 8 {
+            return Err(HodokuError::WrongFieldCount(fields.len()));
+        }
+
+        let rating = match fields.get(7) {
+            Some(rating) => Some(
+                rating
+                    .parse()
+                    .map_err(|_| HodokuError::InvalidRating(rating.to_string()))?,
+            ),
+            None => None,
+        };
+
+        let digits = match fields[1] {
+            "" => vec![],
+            digits => parse_digits(digits, digits)?,
+        };
+
+        let mut givens = Board::default();
+        let mut placed = Board::default();
+        let mut count = 0;
+        let mut plus = false;
+
+        for c in fields[2].chars() {
+            let value = match c {
+                '+' if !plus => {
+                    plus = true;
+                    continue;
+                }
+                '.' | '0' if !plus => 0,
+                '1'..='9' => c as u8 - b'0',
+                c => return Err(HodokuError::InvalidCharacter(c)),
+            };
+
+            if count < BOARD_SIZE * BOARD_SIZE {
+                let (x, y) = (count % BOARD_SIZE, count / BOARD_SIZE);
+
+                if plus {
+                    placed.set_cell(x, y, value);
+                } else {
+                    givens.set_cell(x, y, value);
+                }
+            }
+
+            plus = false;
+            count += 1;
+        }
+
+        if plus {
+            return Err(HodokuError::InvalidCharacter('+'));
+        }
+
+        if count != BOARD_SIZE * BOARD_SIZE {
+            return Err(HodokuError::WrongCellCount(count));
+        }
+
+        Ok(Self {
+            technique: fields[0].to_string(),
+            digits,
+            givens,
+            placed,
+            deleted: parse_candidate_list(fields[3])?,
+            eliminations: parse_candidate_list(fields[4])?,
+            placements: parse_candidate_list(fields[5])?,
+            extra: fields[6].to_string(),
+            rating,
+        })
+    }
+}
+
+impl std::fmt::Display for LibraryEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits: String = self.digits.iter().map(|d| d.to_string()).collect();
+        let mut grid = String::new();
+
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                match (self.givens.get_cell(x, y), self.placed.get_cell(x, y)) {
+                    (0, 0) => grid.push('.'),
+                    (0, v) => grid.push_str(&format!("+{}", v)),
+                    (v, _) => grid.push_str(&v.to_string()),
+                }
+            }
+        }
+
+        write!(
+            f,
+            ":{}:{}:{}:{}:{}:{}:{}:",
+            self.technique,
+            digits,
+            grid,
+            candidate_list_to_string(&self.deleted),
+            candidate_list_to_string(&self.eliminations),
+            candidate_list_to_string(&self.placements),
+            self.extra
+        )?;
+
+        match self.rating {
+            Some(rating) => write!(f, "{}:", rating),
+            None => Ok(()),
+        }
+    }
+}
+
+/// One step of a logical solution, as listed in a HoDoKu solution log.
+///
+/// A step is written as the name of the technique, a colon, an optional description of how it
+/// applies, and the digits it places or the candidates it eliminates:
+///
+/// * `Hidden Single: r3c5=7`
+/// * `Locked Candidates Type 1 (Pointing): 5 in b4 => r6c7<>5, r6c8<>5`
+///
+/// Placements are written `r3c5=7` and eliminations `r6c7<>5`.  When reading, several rows,
+/// columns or digits may be combined, so that `r6c78<>58` eliminates both 5 and 8 from both
+/// `r6c7` and `r6c8`.  Each placement and elimination is written separately.  A step may have
+/// no placements or eliminations at all.
+///
+/// ## Example
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let step: Step = "Locked Candidates Type 1 (Pointing): 5 in b4 => r6c78<>5"
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(step.description, "5 in b4");
+/// assert_eq!(step.eliminations, vec![((6, 5), 5), ((7, 5), 5)]);
+///
+/// let mut board = Board::default();
+/// board.set_cell(6, 5, 5);
+/// assert!(!step.consistent_with(&board));
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Step {
+    /// The name of the technique.
+    pub technique: String,
+
+    /// How the technique applies, or an empty string.
+    pub description: String,
+
+    /// The digits placed by the step.
+    pub placements: Vec<CellDigit>,
+
+    /// The candidates eliminated by the step.
+    pub eliminations: Vec<CellDigit>,
+}
+
+impl Step {
+    /// Test whether the step agrees with a board, which is usually a solution: every digit
+    /// placed by the step must be in the board, and no candidate eliminated by the step may be.
+    pub fn consistent_with(&self, board: &Board) -> bool {
+        self.placements
+            .iter()
+            .all(|&((x, y), d)| board.get_cell(x, y) == d)
+            && self
+                .eliminations
+                .iter()
+                .all(|&((x, y), d)| board.get_cell(x, y) != d)
+    }
+
+    /// Parse a comma-separated list of placements and eliminations into the step.  An empty list
+    /// leaves the step unchanged.
+    fn parse_changes(&mut self, s: &str) -> Result<(), HodokuError> {
+        if s.trim().is_empty() {
+            return Ok(());
+        }
+
+        for item in s.split(',').map(str::trim) {
+            let invalid = || HodokuError::InvalidCandidate(item.to_string());
+
+            let (cells, digits, eliminated) = match item.split_once("<>") {
+                Some((cells, digits)) => (cells, digits, true),
+                None => {
+                    let (cells, digits) = item.split_once('=').ok_or_else(invalid)?;
+                    (cells, digits, false)
+                }
+            };
+
+            let (rows, columns) = cells
+                .strip_prefix('r')
+                .and_then(|s| s.split_once('c'))
+                .ok_or_else(invalid)?;
+
+            let rows = parse_digits(rows, item)?;
+            let columns = parse_digits(columns, item)?;
+            let digits = parse_digits(digits, item)?;
+
+            if !eliminated && rows.len() * columns.len() * digits.len() != 1 {
+                return Err(invalid());
+            }
+
+            let changes = if eliminated {
+                &mut self.eliminations
+            } else {
+                &mut self.placements
+            };
+
+            for &r in &rows {
+                for &c in &columns {
+                    for &d in &digits {
+                        changes.push(((c as usize - 1, r as usize - 1), d));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Step {
+    type Err = HodokuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (technique, rest) = s
+            .split_once(':')
+            .filter(|(technique, _)| !technique.trim().is_empty())
+            .ok_or_else(|| HodokuError::MissingTechnique(s.to_string()))?;
+
+        let (description, changes) = match rest.split_once("=>") {
+            Some((description, changes)) => (description.trim(), changes.trim()),
+            None => ("", rest.trim()),
+        };
+
+        let mut step = Self {
+            technique: technique.trim().to_string(),
+            description: description.to_string(),
+            ..Self::default()
+        };
+
+        step.parse_changes(changes)?;
+
+        Ok(step)
+    }
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let changes: Vec<String> = self
+            .placements
+            .iter()
+            .map(|&((x, y), d)| format!("r{}c{}={}", y + 1, x + 1, d))
+            .chain(
+                self.eliminations
+                    .iter()
+                    .map(|&((x, y), d)| format!("r{}c{}<>{}", y + 1, x + 1, d)),
+            )
+            .collect();
+
+        let line = if self.description.is_empty() {
+            format!("{}: {}", self.technique, changes.join(", "))
+        } else {
+            format!(
+                "{}: {} => {}",
+                self.technique,
+                self.description,
+                changes.join(", ")
+            )
+        };
+
+        // A step with no changes has nothing after its separator.
+        write!(f, "{}", line.trim_end())
+    }
+}
+
+/// Read a solution log, one step per line.  Blank lines are ignored.
+pub fn read_step_log(s: &str) -> Result<Vec<Step>, HodokuError> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Write a solution log, one step per line.
+pub fn write_step_log(steps: &[Step]) -> String {
+    steps.iter().map(|step| format!("{}\n", step)).collect()
+}
+
+/// One step of a Sudoku Explainer solution log, as written by its `serate` tool.
+///
+/// Each line holds the rating of the step, a comma, and then the step itself in much the same
+/// form as a [`Step`]:
+///
+/// * `1.2, Hidden Single: r4c3: 5 in block`
+/// * `2.6, Pointing: 5 in b4 ==> r6c7<>5, r6c8<>5`
+///
+/// A single is written as the cell, a colon and the digit placed there, followed by the house in
+/// which it was found; the house is kept as the step's description.  Other steps give their
+/// placements and eliminations after `==>` or `=>`, as for a [`Step`].
+///
+/// ## Example
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let step: ExplainerStep = "1.2, Hidden Single: r4c3: 5 in block".parse().unwrap();
+///
+/// assert_eq!(step.rating, 12);
+/// assert_eq!(step.step.technique, "Hidden Single");
+/// assert_eq!(step.step.placements, vec![((2, 3), 5)]);
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExplainerStep {
+    /// The difficulty of the step in tenths, so that a rating of 2.6 is stored as 26.
+    pub rating: u32,
+
+    /// The technique and what it places or eliminates.
+    pub step: Step,
+}
+
+/// Parse a rating with at most one decimal place, such as `2.6`, as a number of tenths.
+fn parse_tenths(s: &str) -> Option<u32> {
+    let (whole, tenths) = s.split_once('.').unwrap_or((s, "0"));
+
+    if whole.is_empty() || tenths.len() != 1 || !s.bytes().all(|b| b == b'.' || b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    whole
+        .parse::<u32>()
+        .ok()?
+        .checked_mul(10)?
+        .checked_add(tenths.parse().ok()?)
+}
+
+impl FromStr for ExplainerStep {
+    type Err = HodokuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (rating, rest) = s
+            .split_once(',')
+            .ok_or_else(|| HodokuError::InvalidRating(s.to_string()))?;
+        let rating = parse_tenths(rating.trim())
+            .ok_or_else(|| HodokuError::InvalidRating(rating.trim().to_string()))?;
+
+        let (technique, rest) = rest
+            .split_once(':')
+            .filter(|(technique, _)| !technique.trim().is_empty())
+            .ok_or_else(|| HodokuError::MissingTechnique(s.to_string()))?;
+        let rest = rest.trim();
+
+        let mut step = Step {
+            technique: technique.trim().to_string(),
+            ..Step::default()
+        };
+
+        if let Some((description, changes)) =
+            rest.split_once("==>").or_else(|| rest.split_once("=>"))
+        {
+            step.description = description.trim().to_string();
+            step.parse_changes(changes)?;
+        } else if let Some((cell, placed)) = rest.split_once(':') {
+            // A single: the cell, then the digit and the house it was found in.
+            let placed = placed.trim();
+            let (digit, house) = placed.split_once(' ').unwrap_or((placed, ""));
+
+            step.description = house.trim().to_string();
+            step.parse_changes(&format!("{}={}", cell.trim(), digit))?;
+        } else {
+            step.description = rest.to_string();
+        }
+
+        Ok(Self { rating, step })
+    }
+}
+
+/// Read a Sudoku Explainer solution log, one step per line.  Blank lines are ignored.
+pub fn read_explainer_log(s: &str) -> Result<Vec<ExplainerStep>, HodokuError> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
@@ -0,0 +1,228 @@
+use super::*;
+
+#[test]
+fn parse_library_entry() {
+    let grid = format!("+1.3{}", "0".repeat(78));
+    let line = format!(":0100-x:15:{}:211 299:512 513:::", grid);
+    let entry: LibraryEntry = line.parse().unwrap();
+
+    assert_eq!(entry.technique, "0100-x");
+    assert_eq!(entry.digits, vec![1, 5]);
+    assert_eq!(entry.placed.get_cell(0, 0), 1);
+    assert_eq!(entry.givens.get_cell(0, 0), 0);
+    assert_eq!(entry.givens.get_cell(2, 0), 3);
+    assert_eq!(entry.deleted, vec![((0, 0), 2), ((8, 8), 2)]);
+    assert_eq!(entry.eliminations, vec![((1, 0), 5), ((2, 0), 5)]);
+    assert!(entry.placements.is_empty());
+    assert_eq!(entry.extra, "");
+    assert_eq!(entry.rating, None);
+
+    assert_eq!(
+        entry.to_string(),
+        format!(":0100-x:15:+1.3{}:211 299:512 513:::", ".".repeat(78))
+    );
+    assert_eq!(entry.to_string().parse(), Ok(entry));
+}
+
+#[test]
+fn library_entry_ratings() {
+    let grid = ".".repeat(81);
+    let line = format!(":0901:1:{}::::x:1520:", grid);
+    let mut entry: LibraryEntry = line.parse().unwrap();
+
+    assert_eq!(entry.extra, "x");
+    assert_eq!(entry.rating, Some(1520));
+    assert_eq!(entry.to_string(), line);
+    assert_eq!(entry.to_string().parse(), Ok(entry.clone()));
+
+    entry.rating = None;
+    assert_eq!(entry.to_string(), format!(":0901:1:{}::::x:", grid));
+
+    assert_eq!(
+        format!(":0901:1:{}::::x:hard:", grid).parse::<LibraryEntry>(),
+        Err(HodokuError::InvalidRating("hard".to_string()))
+    );
+    assert_eq!(
+        format!(":0901:1:{}::::x:1:2:", grid).parse::<LibraryEntry>(),
+        Err(HodokuError::WrongFieldCount(9))
+    );
+}
+
+#[test]
+fn reject_bad_library_entries() {
+    let grid = ".".repeat(81);
+
+    assert_eq!(
+        "0000:1".parse::<LibraryEntry>(),
+        Err(HodokuError::WrongFieldCount(2))
+    );
+    assert_eq!(
+        format!(":0000:1:{}:::", grid).parse::<LibraryEntry>(),
+        Err(HodokuError::WrongFieldCount(5))
+    );
+    assert_eq!(
+        ":0000:1:123:::::".parse::<LibraryEntry>(),
+        Err(HodokuError::WrongCellCount(3))
+    );
+    assert_eq!(
+        format!(":0000:1:+.{}:::::", &grid[1..]).parse::<LibraryEntry>(),
+        Err(HodokuError::InvalidCharacter('.'))
+    );
+    assert_eq!(
+        format!(":0000:1:{}:12::::", grid).parse::<LibraryEntry>(),
+        Err(HodokuError::InvalidCandidate("12".to_string()))
+    );
+    assert_eq!(
+        format!(":0000:1:{}:120::::", grid).parse::<LibraryEntry>(),
+        Err(HodokuError::InvalidCandidate("120".to_string()))
+    );
+}
+
+#[test]
+fn parse_steps() {
+    let step: Step = "Hidden Single: r1c9=4".parse().unwrap();
+    assert_eq!(step.technique, "Hidden Single");
+    assert_eq!(step.description, "");
+    assert_eq!(step.placements, vec![((8, 0), 4)]);
+    assert!(step.eliminations.is_empty());
+
+    let step: Step = "Naked Pair: 2,7 in r5c13 => r5c2<>27, r46c3<>2"
+        .parse()
+        .unwrap();
+    assert_eq!(step.description, "2,7 in r5c13");
+    assert_eq!(
+        step.eliminations,
+        vec![((1, 4), 2), ((1, 4), 7), ((2, 3), 2), ((2, 5), 2)]
+    );
+    assert_eq!(
+        step.to_string(),
+        "Naked Pair: 2,7 in r5c13 => r5c2<>2, r5c2<>7, r4c3<>2, r6c3<>2"
+    );
+    assert_eq!(step.to_string().parse(), Ok(step));
+}
+
+#[test]
+fn steps_without_changes_round_trip() {
+    let step = Step {
+        technique: "X".to_string(),
+        ..Step::default()
+    };
+    assert_eq!(step.to_string(), "X:");
+    assert_eq!(step.to_string().parse(), Ok(step));
+
+    let step: Step = "Uniqueness Test 1: 1/2 in r1c12,r3c12 => ".parse().unwrap();
+    assert_eq!(step.description, "1/2 in r1c12,r3c12");
+    assert!(step.placements.is_empty() && step.eliminations.is_empty());
+    assert_eq!(step.to_string().parse(), Ok(step));
+
+    assert_eq!(
+        "Hidden Single: r1c1=4,".parse::<Step>(),
+        Err(HodokuError::InvalidCandidate("".to_string()))
+    );
+}
+
+#[test]
+fn reject_bad_steps() {
+    assert_eq!(
+        "r1c1=4".parse::<Step>(),
+        Err(HodokuError::MissingTechnique("r1c1=4".to_string()))
+    );
+    assert_eq!(
+        "Hidden Single: r1c12=4".parse::<Step>(),
+        Err(HodokuError::InvalidCandidate("r1c12=4".to_string()))
+    );
+    assert_eq!(
+        "Hidden Single: r1c0=4".parse::<Step>(),
+        Err(HodokuError::InvalidCandidate("r1c0=4".to_string()))
+    );
+    assert_eq!(
+        "Hidden Single: somewhere".parse::<Step>(),
+        Err(HodokuError::InvalidCandidate("somewhere".to_string()))
+    );
+}
+
+#[test]
+fn step_logs() {
+    let log = "Full House: r9c9=8\n\nNaked Single: r1c1=5\n";
+    let steps = read_step_log(log).unwrap();
+
+    assert_eq!(steps.len(), 2);
+    assert_eq!(write_step_log(&steps), log.replace("\n\n", "\n"));
+    assert!(read_step_log("Full House: r9c9\n").is_err());
+}
+
+#[test]
+fn steps_are_checked_against_solutions() {
+    let solution = crate::solve(&Board::default()).unwrap();
+    let digit = solution.get_cell(3, 4);
+    let other = digit % 9 + 1;
+
+    let step = Step {
+        technique: "Naked Single".to_string(),
+        placements: vec![((3, 4), digit)],
+        eliminations: vec![((3, 4), other)],
+        ..Step::default()
+    };
+    assert!(step.consistent_with(&solution));
+
+    let step = Step {
+        eliminations: vec![((3, 4), digit)],
+        ..step
+    };
+    assert!(!step.consistent_with(&solution));
+}
+
+#[test]
+fn parse_explainer_steps() {
+    let step: ExplainerStep = "1.5, Hidden Single: r2c2: 4 in row".parse().unwrap();
+    assert_eq!(step.rating, 15);
+    assert_eq!(step.step.technique, "Hidden Single");
+    assert_eq!(step.step.description, "in row");
+    assert_eq!(step.step.placements, vec![((1, 1), 4)]);
+    assert!(step.step.eliminations.is_empty());
+
+    let step: ExplainerStep = "2.6, Pointing: 5 in b4 ==> r6c7<>5, r6c8<>5"
+        .parse()
+        .unwrap();
+    assert_eq!(step.rating, 26);
+    assert_eq!(step.step.technique, "Pointing");
+    assert_eq!(step.step.description, "5 in b4");
+    assert_eq!(step.step.eliminations, vec![((6, 5), 5), ((7, 5), 5)]);
+
+    let step: ExplainerStep = "10, Bidirectional Cycle: r1c1 => r1c1<>9".parse().unwrap();
+    assert_eq!(step.rating, 100);
+    assert_eq!(step.step.eliminations, vec![((0, 0), 9)]);
+}
+
+#[test]
+fn reject_bad_explainer_steps() {
+    assert_eq!(
+        "Hidden Single: r2c2: 4 in row".parse::<ExplainerStep>(),
+        Err(HodokuError::InvalidRating(
+            "Hidden Single: r2c2: 4 in row".to_string()
+        ))
+    );
+    assert_eq!(
+        "1.25, Naked Single: r1c1: 5".parse::<ExplainerStep>(),
+        Err(HodokuError::InvalidRating("1.25".to_string()))
+    );
+    assert_eq!(
+        "2.0, : r1c1<>5".parse::<ExplainerStep>(),
+        Err(HodokuError::MissingTechnique("2.0, : r1c1<>5".to_string()))
+    );
+    assert_eq!(
+        "2.3, Naked Single: r1c0: 5".parse::<ExplainerStep>(),
+        Err(HodokuError::InvalidCandidate("r1c0=5".to_string()))
+    );
+}
+
+#[test]
+fn explainer_logs() {
+    let log = "1.2, Hidden Single: r4c3: 5 in block\n\n2.6, Pointing: 5 in b4 ==> r6c7<>5\n";
+    let steps = read_explainer_log(log).unwrap();
+
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps.iter().map(|step| step.rating).max(), Some(26));
+    assert_eq!(steps[1].step.to_string(), "Pointing: 5 in b4 => r6c7<>5");
+    assert!(read_explainer_log("1.2, Naked Single: r1c1: x\n").is_err());
+}
@@ -0,0 +1,314 @@
+//! # Killer sudoku cages
+//!
+//! A killer sudoku adds cages to the board.  A cage is a group of cells whose digits must add up to
+//! a given total, and no digit may be repeated within a cage.
+//!
+//! Cages are collected into a [`Cages`] value, which implements [`Constraint`] so that it can be
+//! added to the [`Rules`] used by the solver.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let cages = Cages::new(vec![
+//!     Cage::new(&[(0, 0), (1, 0)], 3).unwrap(),
+//!     Cage::new(&[(0, 1), (1, 1)], 17).unwrap(),
+//! ])
+//! .unwrap();
+//!
+//! let solution = SolutionIter::with_rules(&Board::default(), Rules::classic().with(cages))
+//!     .next()
+//!     .unwrap();
+//!
+//! assert_eq!(solution.get_cell(0, 0) + solution.get_cell(1, 0), 3);
+//! assert_eq!(solution.get_cell(0, 1) + solution.get_cell(1, 1), 17);
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+use super::constraint::*;
+
+/// An error arising from an invalid cage definition.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CageError {
+    /// A cage contains no cells.
+    Empty,
+
+    /// A cage contains more cells than there are distinct digits.
+    TooManyCells(usize),
+
+    /// A cell lies outside the board.
+    CellOutOfRange(usize, usize),
+
+    /// A cell appears more than once in the same cage.
+    DuplicateCell(usize, usize),
+
+    /// No set of distinct digits of the right size adds up to the cage total.
+    ImpossibleSum(u8),
+
+    /// A cell belongs to more than one cage.
+    Overlap(usize, usize),
+
+    /// A cell does not belong to any cage.
+    Uncovered(usize, usize),
+}
+
+impl std::fmt::Display for CageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CageError::Empty => write!(f, "cage contains no cells"),
+            CageError::TooManyCells(n) => write!(f, "cage contains too many cells ({})", n),
+            CageError::CellOutOfRange(x, y) => write!(f, "cell ({}, {}) is off the board", x, y),
+            CageError::DuplicateCell(x, y) => {
+                write!(f, "cell ({}, {}) appears more than once in a cage", x, y)
+            }
+            CageError::ImpossibleSum(sum) => write!(f, "no combination of digits adds to {}", sum),
+            CageError::Overlap(x, y) => write!(f, "cell ({}, {}) is in more than one cage", x, y),
+            CageError::Uncovered(x, y) => write!(f, "cell ({}, {}) is not in any cage", x, y),
+        }
+    }
+}
+
+impl std::error::Error for CageError {}
+
+/// A group of cells whose digits must be distinct and add up to a given total.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Cage {
+    cells: Vec<(usize, usize)>,
+    sum: u8,
+    combinations: Vec<u16>,
+}
+
+impl Cage {
+    /// Create a `Cage` covering the given cells, whose digits must add up to `sum`.
+    ///
+    /// Cells are given as `(x, y)` pairs, using the same coordinates as [`Board::get_cell()`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let cage = Cage::new(&[(0, 0), (1, 0)], 17).unwrap();
+    /// assert_eq!(cage.sum(), 17);
+    ///
+    /// // Two distinct digits cannot add up to 18.
+    /// assert_eq!(Cage::new(&[(0, 0), (1, 0)], 18), Err(CageError::ImpossibleSum(18)));
+    /// # }
+    /// ```
+    pub fn new(cells: &[(usize, usize)], sum: u8) -> Result<Self, CageError> {
+        if cells.is_empty() {
+            return Err(CageError::Empty);
+        }
+
+        if cells.len() > BOARD_SIZE {
+            return Err(CageError::TooManyCells(cells.len()));
+        }
+
+        for (i, &(x, y)) in cells.iter().enumerate() {
+            if x >= BOARD_SIZE || y >= BOARD_SIZE {
+                return Err(CageError::CellOutOfRange(x, y));
+            }
+
+            if cells[..i].contains(&(x, y)) {
+                return Err(CageError::DuplicateCell(x, y));
+            }
+        }
+
+        let combinations = combinations(cells.len(), sum);
+
+        if combinations.is_empty() {
+            return Err(CageError::ImpossibleSum(sum));
+        }
+
+        Ok(Self {
+            cells: cells.to_vec(),
+            sum,
+            combinations,
+        })
+    }
+
+    /// The cells covered by the cage, as `(x, y)` pairs.
+    #[inline]
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    /// The total which the digits in the cage must add up to.
+    #[inline]
+    pub fn sum(&self) -> u8 {
+        self.sum
+    }
+
+    /// Every set of distinct digits which fills the cage and adds up to its total.
+    ///
+    /// Each set is represented as a mask in which bit `n` is set if digit `n` is a member.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let cage = Cage::new(&[(0, 0), (1, 0)], 4).unwrap();
+    /// assert_eq!(cage.combinations(), &[0b00_0000_1010]); // {1, 3}
+    /// # }
+    /// ```
+    #[inline]
+    pub fn combinations(&self) -> &[u16] {
+        &self.combinations
+    }
+
+    /// Returns the set of digits already placed in the cage, or `None` if a digit is repeated.
+    fn placed(&self, b: &Board) -> Option<u16> {
+        let mut placed = 0;
+
+        for &(x, y) in &self.cells {
+            let mask = b.get_cell_as_mask(x, y);
+
+            if mask != 1 {
+                if placed & mask != 0 {
+                    return None;
+                }
+
+                placed |= mask;
+            }
+        }
+
+        Some(placed)
+    }
+
+    /// Test whether the digits placed in the cage so far can still be completed to a valid
+    /// combination.
+    pub(crate) fn valid(&self, b: &Board) -> bool {
+        match self.placed(b) {
+            Some(placed) => self.combinations.iter().any(|&c| c & placed == placed),
+            None => false,
+        }
+    }
+
+    /// Returns a mask of the digits which may still be placed in the empty cells of the cage.
+    pub(crate) fn candidates(&self, b: &Board) -> u16 {
+        match self.placed(b) {
+            Some(placed) => self
+                .combinations
+                .iter()
+                .filter(|&&c| c & placed == placed)
+                .fold(0, |acc, &c| acc | (c & !placed)),
+            None => 0,
+        }
+    }
+}
+
+/// A set of non-overlapping cages.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Cages {
+    cages: Vec<Cage>,
+    index: [[Option<u8>; BOARD_SIZE]; BOARD_SIZE],
+}
+
+impl Cages {
+    /// Create a `Cages` value from a list of cages, checking that no two cages overlap.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let cages = Cages::new(vec![
+    ///     Cage::new(&[(0, 0), (1, 0)], 3).unwrap(),
+    ///     Cage::new(&[(1, 0), (2, 0)], 5).unwrap(),
+    /// ]);
+    ///
+    /// assert_eq!(cages, Err(CageError::Overlap(1, 0)));
+    /// # }
+    /// ```
+    pub fn new(cages: Vec<Cage>) -> Result<Self, CageError> {
+        let mut index = [[None; BOARD_SIZE]; BOARD_SIZE];
+
+        for (i, cage) in cages.iter().enumerate() {
+            for &(x, y) in cage.cells() {
+                if index[y][x].is_some() {
+                    return Err(CageError::Overlap(x, y));
+                }
+
+                index[y][x] = Some(i as u8);
+            }
+        }
+
+        Ok(Self { cages, index })
+    }
+
+    /// The cages in the set.
+    #[inline]
+    pub fn cages(&self) -> &[Cage] {
+        &self.cages
+    }
+
+    /// Returns `true` if the set contains no cages.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cages.is_empty()
+    }
+
+    /// Returns the cage containing the cell at column `x` and row `y`, if any.
+    #[inline]
+    pub fn cage_at(&self, x: usize, y: usize) -> Option<&Cage> {
+        self.index[y][x].map(|i| &self.cages[i as usize])
+    }
+
+    /// Check that every cell of the board belongs to a cage, as is usual for killer sudoku.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let cages = Cages::new(vec![Cage::new(&[(0, 0), (1, 0)], 3).unwrap()]).unwrap();
+    ///
+    /// assert_eq!(cages.check_coverage(), Err(CageError::Uncovered(2, 0)));
+    /// # }
+    /// ```
+    pub fn check_coverage(&self) -> Result<(), CageError> {
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                if self.index[y][x].is_none() {
+                    return Err(CageError::Uncovered(x, y));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Constraint for Cages {
+    #[inline]
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        match self.cage_at(x, y) {
+            Some(cage) => cage.candidates(board),
+            None => ALL_CANDIDATES,
+        }
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        self.cages.iter().all(|cage| cage.valid(board))
+    }
+}
+
+/// Returns every set of `size` distinct digits from 1 to 9 which add up to `sum`.
+fn combinations(size: usize, sum: u8) -> Vec<u16> {
+    (0u16..(1 << BOARD_SIZE))
+        .map(|set| set << 1)
+        .filter(|set| set.count_ones() as usize == size)
+        .filter(|set| {
+            (1..=BOARD_SIZE)
+                .filter(|&d| set & (1 << d) != 0)
+                .sum::<usize>()
+                == sum as usize
+        })
+        .collect()
+}
@@ -0,0 +1,96 @@
+use super::*;
+
+#[test]
+fn invalid_cages_are_rejected() {
+    assert_eq!(Cage::new(&[], 0), Err(CageError::Empty));
+    assert_eq!(
+        Cage::new(&[(0, 0), (9, 0)], 3),
+        Err(CageError::CellOutOfRange(9, 0))
+    );
+    assert_eq!(
+        Cage::new(&[(0, 0), (1, 0), (0, 0)], 6),
+        Err(CageError::DuplicateCell(0, 0))
+    );
+    assert_eq!(
+        Cage::new(&[(0, 0), (1, 0)], 2),
+        Err(CageError::ImpossibleSum(2))
+    );
+
+    let cells: Vec<(usize, usize)> = (0..BOARD_SIZE + 1).map(|i| (i % 9, i / 9)).collect();
+    assert_eq!(Cage::new(&cells, 45), Err(CageError::TooManyCells(10)));
+}
+
+#[test]
+fn cage_combinations_are_correct() {
+    let cage = Cage::new(&[(0, 0)], 7).unwrap();
+    assert_eq!(cage.combinations(), &[0b00_1000_0000]);
+
+    let cage = Cage::new(&[(0, 0), (1, 0), (2, 0)], 7).unwrap();
+    assert_eq!(cage.combinations(), &[0b00_0001_0110]); // {1, 2, 4}
+
+    let cage = Cage::new(&[(0, 0), (1, 0), (2, 0)], 10).unwrap();
+    assert_eq!(cage.combinations().len(), 4); // {1, 2, 7}, {1, 3, 6}, {1, 4, 5}, {2, 3, 5}
+
+    let cells: Vec<(usize, usize)> = (0..BOARD_SIZE).map(|x| (x, 0)).collect();
+    let cage = Cage::new(&cells, 45).unwrap();
+    assert_eq!(cage.combinations(), &[0b11_1111_1110]);
+}
+
+#[test]
+fn overlapping_cages_are_rejected() {
+    let cages = Cages::new(vec![
+        Cage::new(&[(0, 0), (0, 1)], 3).unwrap(),
+        Cage::new(&[(1, 1), (0, 1), (2, 1)], 6).unwrap(),
+    ]);
+
+    assert_eq!(cages, Err(CageError::Overlap(0, 1)));
+}
+
+#[test]
+fn coverage_is_checked() {
+    let cages: Vec<Cage> = (0..BOARD_SIZE)
+        .map(|y| {
+            let cells: Vec<(usize, usize)> = (0..BOARD_SIZE).map(|x| (x, y)).collect();
+            Cage::new(&cells, 45).unwrap()
+        })
+        .collect();
+
+    let complete = Cages::new(cages.clone()).unwrap();
+    assert_eq!(complete.check_coverage(), Ok(()));
+
+    let incomplete = Cages::new(cages[..8].to_vec()).unwrap();
+    assert_eq!(incomplete.check_coverage(), Err(CageError::Uncovered(0, 8)));
+}
+
+#[test]
+fn cage_at_works() {
+    let cage = Cage::new(&[(4, 4), (5, 4)], 3).unwrap();
+    let cages = Cages::new(vec![cage.clone()]).unwrap();
+
+    assert_eq!(cages.cage_at(4, 4), Some(&cage));
+    assert_eq!(cages.cage_at(5, 4), Some(&cage));
+    assert_eq!(cages.cage_at(4, 5), None);
+}
+
+#[test]
+fn cage_candidates_are_pruned_by_sum() {
+    let cage = Cage::new(&[(0, 0), (1, 0), (2, 0)], 7).unwrap();
+    let mut board = Board::default();
+
+    assert_eq!(cage.candidates(&board), 0b00_0001_0110);
+
+    board.set_cell(0, 0, 4);
+    assert_eq!(cage.candidates(&board), 0b00_0000_0110);
+    assert!(cage.valid(&board));
+
+    board.set_cell(1, 0, 2);
+    assert_eq!(cage.candidates(&board), 0b00_0000_0010);
+    assert!(cage.valid(&board));
+
+    board.set_cell(2, 0, 3);
+    assert!(!cage.valid(&board));
+
+    board.set_cell(2, 0, 4);
+    assert!(!cage.valid(&board));
+    assert_eq!(cage.candidates(&board), 0);
+}
@@ -53,11 +53,90 @@
 //! assert_eq!(solutions.count(), 2);
 //! # }
 //! ```
+//!
+//! Variants of sudoku are supported by adding constraints to a [`Rules`] value, and passing it to
+//! [`SolutionIter::with_rules()`].  For example, an X-sudoku adds the [`Diagonals`] constraint to
+//! the classic rules.  Likewise, [`Windows`] adds the four extra regions of a Windoku, and
+//! [`DisjointGroups`] makes a house of the cells in the same position in each square.  Any other
+//! group of cells which may not repeat a digit can be added as a [`House`]:
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let rules = Rules::classic().with(Diagonals);
+//!
+//! let solution = SolutionIter::with_rules(&Board::default(), rules.clone()).next().unwrap();
+//!
+//! assert!(valid_with_rules(&solution, &rules));
+//! # }
+//! ```
+//!
+//! New variants can be added by implementing the [`Constraint`] trait.  Puzzles made of several
+//! overlapping grids, such as Samurai sudoku, are described by a [`MultiBoard`] and solved by a
+//! [`MultiSolutionIter`].
+//!
+//! Pencilmarks are held in a [`CandidateGrid`], which records the digits each cell may still take
+//! and can be turned back into a [`Board`] once every cell has a single candidate.  Sukaku
+//! puzzles, which give such a grid in place of clues, are solved with
+//! [`SolutionIter::from_candidates()`].
+//!
+//! Puzzles can be read from and written to the file formats of several popular sudoku programs
+//! using the functions in the [`formats`] module.  The [`hodoku`] module exchanges puzzle library
+//! entries and solution steps with logical solvers such as HoDoKu, and reads Sudoku Explainer's
+//! solution logs.  Boards, along with the cages and thermometers of variant puzzles, can be drawn
+//! as SVG images using [`Svg`], and collections of puzzles can be printed as a PDF [`Booklet`]
+//! with an answer key.
+//!
+//! The optional `serde` feature implements `Serialize` and `Deserialize` for boards, candidate
+//! grids and constraints; see the `serialization` module.  The optional `fpuzzles` feature adds
+//! `FPuzzle`, which reads variant puzzles shared from the f-puzzles editor or SudokuPad.
 
 #![doc(html_root_url = "https://docs.rs/sudoku-solver/0.6.1")]
 
+pub mod arrow;
 pub mod board;
+pub mod booklet;
+pub mod candidates;
+pub mod chess;
+pub mod comparison;
+pub mod consecutive;
+pub mod constraint;
+pub mod formats;
+#[cfg(feature = "fpuzzles")]
+pub mod fpuzzles;
+pub mod hodoku;
+pub mod killer;
+pub mod lines;
+pub mod little_killer;
+pub mod markers;
+pub mod multigrid;
+pub mod parity;
+pub mod sandwich;
+#[cfg(feature = "serde")]
+pub mod serialization;
 pub mod solver;
+pub mod svg;
+pub mod thermo;
 
+pub use arrow::*;
 pub use board::*;
+pub use booklet::*;
+pub use candidates::*;
+pub use chess::*;
+pub use comparison::*;
+pub use consecutive::*;
+pub use constraint::*;
+pub use formats::*;
+#[cfg(feature = "fpuzzles")]
+pub use fpuzzles::*;
+pub use hodoku::*;
+pub use killer::*;
+pub use lines::*;
+pub use little_killer::*;
+pub use markers::*;
+pub use multigrid::*;
+pub use parity::*;
+pub use sandwich::*;
 pub use solver::*;
+pub use svg::*;
+pub use thermo::*;
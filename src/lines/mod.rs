@@ -0,0 +1,373 @@
+//! # Line constraints
+//!
+//! Many modern puzzles draw lines through the grid, each imposing a rule on the digits along it:
+//!
+//! * On a German whispers line ([`Whisper`]), neighbouring digits differ by at least 5.
+//! * A renban line ([`Renban`]) holds a set of consecutive digits, in any order.
+//! * A palindrome line ([`Palindrome`]) reads the same from either end.
+//!
+//! Like thermometers, each line is a [`Constraint`] in its own right, and is built from a path
+//! of cells in which each cell is adjacent, orthogonally or diagonally, to the one before it.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let rules = Rules::classic()
+//!     .with(Whisper::new(&[(0, 0), (1, 0), (2, 0)]).unwrap())
+//!     .with(Renban::new(&[(0, 1), (1, 1), (2, 1), (3, 1)]).unwrap())
+//!     .with(Palindrome::new(&[(0, 2), (1, 3), (2, 4)]).unwrap());
+//!
+//! let solution = SolutionIter::with_rules(&Board::default(), rules).next().unwrap();
+//!
+//! assert!(solution.get_cell(0, 0).abs_diff(solution.get_cell(1, 0)) >= 5);
+//! assert_eq!(solution.get_cell(0, 2), solution.get_cell(2, 4));
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+use super::constraint::*;
+
+/// An error arising from an invalid line definition.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LineError {
+    /// A line contains no cells.
+    Empty,
+
+    /// A line is longer than the number of distinct digits it must hold.
+    TooManyCells(usize),
+
+    /// A cell lies outside the board.
+    CellOutOfRange(usize, usize),
+
+    /// A cell appears more than once on the same line.
+    DuplicateCell(usize, usize),
+
+    /// A cell is not adjacent, orthogonally or diagonally, to the previous cell on the line.
+    NotAdjacent(usize, usize),
+}
+
+impl std::fmt::Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineError::Empty => write!(f, "line contains no cells"),
+            LineError::TooManyCells(n) => write!(f, "line contains too many cells ({})", n),
+            LineError::CellOutOfRange(x, y) => write!(f, "cell ({}, {}) is off the board", x, y),
+            LineError::DuplicateCell(x, y) => {
+                write!(f, "cell ({}, {}) appears more than once on a line", x, y)
+            }
+            LineError::NotAdjacent(x, y) => {
+                write!(
+                    f,
+                    "cell ({}, {}) is not adjacent to the previous cell",
+                    x, y
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LineError {}
+
+/// Check that `cells` is a path of at most `max_len` distinct, adjacent cells on the board.
+fn check_path(cells: &[(usize, usize)], max_len: usize) -> Result<(), LineError> {
+    if cells.is_empty() {
+        return Err(LineError::Empty);
+    }
+
+    if cells.len() > max_len {
+        return Err(LineError::TooManyCells(cells.len()));
+    }
+
+    for (i, &(x, y)) in cells.iter().enumerate() {
+        if x >= BOARD_SIZE || y >= BOARD_SIZE {
+            return Err(LineError::CellOutOfRange(x, y));
+        }
+
+        if cells[..i].contains(&(x, y)) {
+            return Err(LineError::DuplicateCell(x, y));
+        }
+
+        if i > 0 {
+            let (px, py) = cells[i - 1];
+
+            if px.abs_diff(x) > 1 || py.abs_diff(y) > 1 {
+                return Err(LineError::NotAdjacent(x, y));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns a mask of the digits which differ by at least 5 from some digit in `mask`.
+#[inline]
+fn whisper_partners(mask: u16) -> u16 {
+    (1..=BOARD_SIZE)
+        .filter(|&d| mask & (1 << d) != 0)
+        .fold(0, |acc, d| {
+            acc | (1..=BOARD_SIZE)
+                .filter(|&e| d.abs_diff(e) >= 5)
+                .fold(0, |acc, e| acc | (1 << e))
+        })
+}
+
+/// A German whispers line: digits next to each other on the line differ by at least 5.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Whisper {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Whisper {
+    /// Create a `Whisper` line from a path of cells.  The line may be any length, as digits can
+    /// repeat along it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// assert!(Whisper::new(&[(0, 0), (1, 1), (2, 2)]).is_ok());
+    /// assert_eq!(
+    ///     Whisper::new(&[(0, 0), (2, 0)]),
+    ///     Err(LineError::NotAdjacent(2, 0))
+    /// );
+    /// # }
+    /// ```
+    pub fn new(cells: &[(usize, usize)]) -> Result<Self, LineError> {
+        check_path(cells, BOARD_SIZE * BOARD_SIZE)?;
+
+        Ok(Self {
+            cells: cells.to_vec(),
+        })
+    }
+
+    /// The cells on the line, as `(x, y)` pairs.
+    #[inline]
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    /// Iterate over the positions of the neighbours on the line of the cell at position `k`.
+    fn neighbours(&self, k: usize) -> impl Iterator<Item = usize> {
+        let n = self.cells.len();
+
+        [k.checked_sub(1), Some(k + 1).filter(|&j| j < n)]
+            .into_iter()
+            .flatten()
+    }
+}
+
+impl Constraint for Whisper {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        let k = match self.cells.iter().position(|&c| c == (x, y)) {
+            Some(k) => k,
+            None => return ALL_CANDIDATES,
+        };
+
+        self.neighbours(k).fold(ALL_CANDIDATES, |cs, j| {
+            let (nx, ny) = self.cells[j];
+
+            cs & match board.get_cell(nx, ny) {
+                0 => whisper_partners(ALL_CANDIDATES),
+                v => whisper_partners(1 << v),
+            }
+        })
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        self.cells.windows(2).all(|pair| {
+            let a = board.get_cell(pair[0].0, pair[0].1);
+            let b = board.get_cell(pair[1].0, pair[1].1);
+
+            a == 0 || b == 0 || a.abs_diff(b) >= 5
+        })
+    }
+
+    fn propagates(&self) -> bool {
+        true
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        let mut changed = false;
+
+        for (k, &(x, y)) in self.cells.iter().enumerate() {
+            if board.get_cell(x, y) != 0 {
+                continue;
+            }
+
+            for j in self.neighbours(k) {
+                let (nx, ny) = self.cells[j];
+                let keep = whisper_partners(masks[ny][nx]);
+
+                if masks[y][x] & !keep != 0 {
+                    masks[y][x] &= keep;
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+}
+
+/// A renban line: the digits on the line are distinct and form a consecutive run, in any order.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Renban {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Renban {
+    /// Create a `Renban` line from a path of cells.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// assert!(Renban::new(&[(0, 0), (0, 1), (0, 2)]).is_ok());
+    /// assert_eq!(Renban::new(&[]), Err(LineError::Empty));
+    /// # }
+    /// ```
+    pub fn new(cells: &[(usize, usize)]) -> Result<Self, LineError> {
+        check_path(cells, BOARD_SIZE)?;
+
+        Ok(Self {
+            cells: cells.to_vec(),
+        })
+    }
+
+    /// The cells on the line, as `(x, y)` pairs.
+    #[inline]
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+}
+
+impl Constraint for Renban {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        if !self.cells.contains(&(x, y)) {
+            return ALL_CANDIDATES;
+        }
+
+        let used = used_digits(board, self.cells.iter().copied());
+
+        if used == 0 {
+            return ALL_CANDIDATES;
+        }
+
+        // Every digit must lie within a run of the line's length covering the placed digits.
+        let span = self.cells.len() - 1;
+        let lowest = used.trailing_zeros() as usize;
+        let highest = 15 - used.leading_zeros() as usize;
+        let lo = highest.saturating_sub(span).max(1);
+        let hi = (lowest + span).min(BOARD_SIZE);
+
+        if lo > hi {
+            return 0;
+        }
+
+        (((1 << (hi + 1)) - (1 << lo)) as u16) & !used
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        let used = used_digits(board, self.cells.iter().copied());
+
+        distinct_digits(board, self.cells.iter().copied())
+            && (used == 0
+                || (15 - used.leading_zeros() as usize) - (used.trailing_zeros() as usize)
+                    < self.cells.len())
+    }
+}
+
+/// A palindrome line: the digits read the same from either end of the line.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Palindrome {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Palindrome {
+    /// Create a `Palindrome` line from a path of cells.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// assert!(Palindrome::new(&[(0, 0), (1, 0), (2, 1), (3, 1)]).is_ok());
+    /// assert_eq!(
+    ///     Palindrome::new(&[(0, 0), (1, 0), (0, 0)]),
+    ///     Err(LineError::DuplicateCell(0, 0))
+    /// );
+    /// # }
+    /// ```
+    pub fn new(cells: &[(usize, usize)]) -> Result<Self, LineError> {
+        check_path(cells, BOARD_SIZE * BOARD_SIZE)?;
+
+        Ok(Self {
+            cells: cells.to_vec(),
+        })
+    }
+
+    /// The cells on the line, as `(x, y)` pairs.
+    #[inline]
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    /// Iterate over the pairs of cells which mirror each other along the line.
+    fn pairs(&self) -> impl Iterator<Item = ((usize, usize), (usize, usize))> + '_ {
+        let n = self.cells.len();
+
+        (0..n / 2).map(move |i| (self.cells[i], self.cells[n - 1 - i]))
+    }
+}
+
+impl Constraint for Palindrome {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        self.pairs()
+            .find_map(|(a, b)| match (x, y) {
+                c if c == a => Some(b),
+                c if c == b => Some(a),
+                _ => None,
+            })
+            .map_or(ALL_CANDIDATES, |(mx, my)| match board.get_cell(mx, my) {
+                0 => ALL_CANDIDATES,
+                v => 1 << v,
+            })
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        self.pairs().all(|((ax, ay), (bx, by))| {
+            let a = board.get_cell(ax, ay);
+            let b = board.get_cell(bx, by);
+
+            a == 0 || b == 0 || a == b
+        })
+    }
+
+    fn propagates(&self) -> bool {
+        true
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        let mut changed = false;
+
+        for ((ax, ay), (bx, by)) in self.pairs() {
+            let both = masks[ay][ax] & masks[by][bx];
+
+            for (x, y) in [(ax, ay), (bx, by)] {
+                if board.get_cell(x, y) == 0 && masks[y][x] != both {
+                    masks[y][x] = both;
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+}
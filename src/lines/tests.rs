@@ -0,0 +1,89 @@
+use super::*;
+
+#[test]
+fn invalid_lines_are_rejected() {
+    assert_eq!(Whisper::new(&[]), Err(LineError::Empty));
+    assert_eq!(
+        Palindrome::new(&[(8, 8), (9, 9)]),
+        Err(LineError::CellOutOfRange(9, 9))
+    );
+    assert_eq!(
+        Renban::new(&[(0, 0), (0, 1), (0, 3)]),
+        Err(LineError::NotAdjacent(0, 3))
+    );
+
+    let cells: Vec<(usize, usize)> = (0..BOARD_SIZE + 1).map(|i| (i % 9, i / 9)).collect();
+    assert_eq!(Renban::new(&cells), Err(LineError::TooManyCells(10)));
+}
+
+#[test]
+fn whisper_candidates_differ_by_five() {
+    let whisper = Whisper::new(&[(0, 0), (1, 0), (2, 0)]).unwrap();
+    let mut board = Board::default();
+
+    // 5 can never sit on a whispers line.
+    assert_eq!(whisper.candidates(&board, 1, 0), ALL_CANDIDATES & !(1 << 5));
+    assert_eq!(whisper.candidates(&board, 5, 5), ALL_CANDIDATES);
+
+    board.set_cell(0, 0, 3);
+    assert_eq!(whisper.candidates(&board, 1, 0), 0b11_0000_0000); // {8, 9}
+
+    board.set_cell(2, 0, 7);
+    assert_eq!(whisper.candidates(&board, 1, 0), 0);
+
+    board.set_cell(1, 0, 9);
+    assert!(!whisper.valid(&board));
+
+    board.set_cell(2, 0, 4);
+    assert!(whisper.valid(&board));
+}
+
+#[test]
+fn whisper_propagation_narrows_neighbours() {
+    let whisper = Whisper::new(&[(0, 0), (1, 0)]).unwrap();
+    let board = Board::default();
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+
+    masks[0][0] = 1 << 4 | 1 << 6;
+    assert!(whisper.propagate(&board, &mut masks));
+    assert_eq!(masks[0][1], 0b00_0000_0010 | 1 << 9); // {1, 9}
+}
+
+#[test]
+fn renban_candidates_form_a_run() {
+    let renban = Renban::new(&[(0, 0), (1, 0), (2, 0)]).unwrap();
+    let mut board = Board::default();
+    assert_eq!(renban.candidates(&board, 1, 0), ALL_CANDIDATES);
+
+    board.set_cell(0, 0, 5);
+    assert_eq!(renban.candidates(&board, 1, 0), 0b00_1101_1000); // {3, 4, 6, 7}
+
+    board.set_cell(2, 0, 6);
+    assert_eq!(renban.candidates(&board, 1, 0), 0b00_1001_0000); // {4, 7}
+
+    board.set_cell(1, 0, 8);
+    assert!(!renban.valid(&board));
+
+    board.set_cell(1, 0, 4);
+    assert!(renban.valid(&board));
+}
+
+#[test]
+fn palindromes_mirror_digits() {
+    let palindrome = Palindrome::new(&[(0, 0), (1, 0), (2, 0), (3, 0)]).unwrap();
+    let mut board = Board::default();
+
+    board.set_cell(0, 0, 7);
+    assert_eq!(palindrome.candidates(&board, 3, 0), 1 << 7);
+    assert_eq!(palindrome.candidates(&board, 2, 0), ALL_CANDIDATES);
+
+    board.set_cell(3, 0, 2);
+    assert!(!palindrome.valid(&board));
+
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+    masks[0][1] = 0b00_0000_0110;
+    masks[0][2] = 0b00_0000_1100;
+    assert!(palindrome.propagate(&Board::default(), &mut masks));
+    assert_eq!(masks[0][1], 1 << 2);
+    assert_eq!(masks[0][2], 1 << 2);
+}
@@ -0,0 +1,318 @@
+//! # Little killer sudoku
+//!
+//! A little killer clue is an arrow outside the grid, pointing along a diagonal.  The digits on
+//! that diagonal must add up to the total written by the arrow.  Unlike a killer cage, digits may
+//! repeat along a diagonal, as long as the usual sudoku rules allow it.
+//!
+//! Clues are collected into a [`LittleKillers`] value, which implements [`Constraint`].  Each
+//! clue is given by the first cell of its diagonal, which must lie on the edge of the board, and
+//! the [`Direction`] the arrow points in.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! // An arrow above the seventh column, pointing down and to the right.
+//! let clues = LittleKillers::new(vec![
+//!     LittleKiller::new((6, 0), Direction::DownRight, 24).unwrap(),
+//! ]);
+//!
+//! let solution = SolutionIter::with_rules(&Board::default(), Rules::classic().with(clues))
+//!     .next()
+//!     .unwrap();
+//!
+//! assert_eq!(solution.get_cell(6, 0) + solution.get_cell(7, 1) + solution.get_cell(8, 2), 24);
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+use super::constraint::*;
+
+/// The direction in which a little killer arrow points.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Towards higher rows and columns.
+    DownRight,
+
+    /// Towards higher rows and lower columns.
+    DownLeft,
+
+    /// Towards lower rows and higher columns.
+    UpRight,
+
+    /// Towards lower rows and columns.
+    UpLeft,
+}
+
+impl Direction {
+    /// The change in column and row for one step in this direction.
+    #[inline]
+    fn step(self) -> (isize, isize) {
+        match self {
+            Direction::DownRight => (1, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::UpRight => (1, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+}
+
+/// An error arising from an invalid little killer clue.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LittleKillerError {
+    /// A cell lies outside the board.
+    CellOutOfRange(usize, usize),
+
+    /// The first cell of a diagonal is not on the edge of the board facing the arrow.
+    NotOnEdge(usize, usize),
+
+    /// No digits on the diagonal can add up to the total.
+    ImpossibleSum(u8),
+}
+
+impl std::fmt::Display for LittleKillerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LittleKillerError::CellOutOfRange(x, y) => {
+                write!(f, "cell ({}, {}) is off the board", x, y)
+            }
+            LittleKillerError::NotOnEdge(x, y) => {
+                write!(f, "cell ({}, {}) does not start a diagonal", x, y)
+            }
+            LittleKillerError::ImpossibleSum(sum) => {
+                write!(f, "no digits on the diagonal add to {}", sum)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LittleKillerError {}
+
+/// A little killer clue: the digits along a diagonal must add up to a given total.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct LittleKiller {
+    start: (usize, usize),
+    direction: Direction,
+    sum: u8,
+    cells: Vec<(usize, usize)>,
+}
+
+impl LittleKiller {
+    /// Create a `LittleKiller` clue for the diagonal which starts at `start` and runs in the given
+    /// direction to the edge of the board.
+    ///
+    /// The cell before `start` in that direction must lie off the board, where the arrow is drawn.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let clue = LittleKiller::new((6, 0), Direction::DownRight, 12).unwrap();
+    /// assert_eq!(clue.cells(), &[(6, 0), (7, 1), (8, 2)]);
+    ///
+    /// assert_eq!(
+    ///     LittleKiller::new((4, 4), Direction::DownRight, 12),
+    ///     Err(LittleKillerError::NotOnEdge(4, 4))
+    /// );
+    /// assert_eq!(
+    ///     LittleKiller::new((6, 0), Direction::DownRight, 28),
+    ///     Err(LittleKillerError::ImpossibleSum(28))
+    /// );
+    /// # }
+    /// ```
+    pub fn new(
+        start: (usize, usize),
+        direction: Direction,
+        sum: u8,
+    ) -> Result<Self, LittleKillerError> {
+        if start.0 >= BOARD_SIZE || start.1 >= BOARD_SIZE {
+            return Err(LittleKillerError::CellOutOfRange(start.0, start.1));
+        }
+
+        let (dx, dy) = direction.step();
+
+        if offset(start, (-dx, -dy)).is_some() {
+            return Err(LittleKillerError::NotOnEdge(start.0, start.1));
+        }
+
+        let cells: Vec<(usize, usize)> =
+            std::iter::successors(Some(start), |&c| offset(c, (dx, dy))).collect();
+
+        if (sum as usize) < cells.len() || sum as usize > BOARD_SIZE * cells.len() {
+            return Err(LittleKillerError::ImpossibleSum(sum));
+        }
+
+        Ok(Self {
+            start,
+            direction,
+            sum,
+            cells,
+        })
+    }
+
+    /// The first cell of the diagonal, next to the arrow.
+    #[inline]
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    /// The direction in which the arrow points.
+    #[inline]
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// The total which the digits on the diagonal must add up to.
+    #[inline]
+    pub fn sum(&self) -> u8 {
+        self.sum
+    }
+
+    /// The cells on the diagonal, as `(x, y)` pairs, starting next to the arrow.
+    #[inline]
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    /// Returns the total of the placed digits, and the number of empty cells.
+    fn placed(&self, b: &Board) -> (usize, usize) {
+        self.cells.iter().fold((0, 0), |(sum, empty), &(x, y)| {
+            match b.get_cell(x, y) as usize {
+                0 => (sum, empty + 1),
+                v => (sum + v, empty),
+            }
+        })
+    }
+
+    /// Test whether the placed digits can still be completed to the total.
+    pub(crate) fn valid(&self, b: &Board) -> bool {
+        let (sum, empty) = self.placed(b);
+        let total = self.sum as usize;
+
+        sum + empty <= total && total <= sum + BOARD_SIZE * empty
+    }
+
+    /// Returns a mask of the digits which may be placed in one of the diagonal's empty cells,
+    /// leaving the other empty cells room to make up the total.
+    pub(crate) fn candidates(&self, b: &Board) -> u16 {
+        let (sum, empty) = self.placed(b);
+        let total = self.sum as usize;
+        let others = empty.saturating_sub(1);
+
+        (1..=BOARD_SIZE)
+            .filter(|&d| sum + d + others <= total && total <= sum + d + BOARD_SIZE * others)
+            .fold(0, |acc, d| acc | (1 << d))
+    }
+}
+
+/// A set of little killer clues.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LittleKillers {
+    clues: Vec<LittleKiller>,
+}
+
+impl LittleKillers {
+    /// Create a `LittleKillers` value from a list of clues.  Clues may share cells.
+    pub fn new(clues: Vec<LittleKiller>) -> Self {
+        Self { clues }
+    }
+
+    /// The clues in the set.
+    #[inline]
+    pub fn clues(&self) -> &[LittleKiller] {
+        &self.clues
+    }
+
+    /// Returns `true` if the set contains no clues.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.clues.is_empty()
+    }
+
+    /// Returns the clues whose diagonals cannot add up to their totals, given the digits placed
+    /// so far.  On a completed board, these are the clues whose totals are wrong.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let clues = LittleKillers::new(vec![
+    ///     LittleKiller::new((7, 0), Direction::DownRight, 10).unwrap(),
+    ///     LittleKiller::new((0, 2), Direction::UpRight, 3).unwrap(),
+    /// ]);
+    ///
+    /// let mut board = Board::default();
+    /// board.set_cell(7, 0, 4);
+    /// board.set_cell(8, 1, 6);
+    /// board.set_cell(0, 2, 5);
+    ///
+    /// assert_eq!(clues.violations(&board), vec![&clues.clues()[1]]);
+    /// # }
+    /// ```
+    pub fn violations(&self, board: &Board) -> Vec<&LittleKiller> {
+        self.clues.iter().filter(|c| !c.valid(board)).collect()
+    }
+}
+
+impl Constraint for LittleKillers {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        self.clues
+            .iter()
+            .filter(|c| c.cells.contains(&(x, y)))
+            .fold(ALL_CANDIDATES, |cs, c| cs & c.candidates(board))
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        self.clues.iter().all(|c| c.valid(board))
+    }
+
+    fn propagates(&self) -> bool {
+        true
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        let mut changed = false;
+
+        for clue in &self.clues {
+            let total = clue.sum as usize;
+            let lowest = |m: u16| m.trailing_zeros() as usize;
+            let highest = |m: u16| 15 - m.leading_zeros() as usize;
+
+            if clue.cells.iter().any(|&(x, y)| masks[y][x] == 0) {
+                continue;
+            }
+
+            let min: usize = clue.cells.iter().map(|&(x, y)| lowest(masks[y][x])).sum();
+            let max: usize = clue.cells.iter().map(|&(x, y)| highest(masks[y][x])).sum();
+
+            // Each cell must leave room for the others, at their most extreme, to make up the
+            // total.
+            for &(x, y) in &clue.cells {
+                if board.get_cell(x, y) != 0 {
+                    continue;
+                }
+
+                let mask = masks[y][x];
+                let others_min = min - lowest(mask);
+                let others_max = max - highest(mask);
+                let keep = (1..=BOARD_SIZE)
+                    .filter(|&d| others_min + d <= total && total <= others_max + d)
+                    .fold(0, |acc, d| acc | (1 << d));
+
+                if mask & !keep != 0 {
+                    masks[y][x] &= keep;
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+}
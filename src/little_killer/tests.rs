@@ -0,0 +1,68 @@
+use super::*;
+
+#[test]
+fn invalid_clues_are_rejected() {
+    assert_eq!(
+        LittleKiller::new((9, 0), Direction::DownLeft, 10),
+        Err(LittleKillerError::CellOutOfRange(9, 0))
+    );
+    assert_eq!(
+        LittleKiller::new((3, 8), Direction::DownLeft, 10),
+        Err(LittleKillerError::NotOnEdge(3, 8))
+    );
+    assert_eq!(
+        LittleKiller::new((0, 1), Direction::UpRight, 1),
+        Err(LittleKillerError::ImpossibleSum(1))
+    );
+}
+
+#[test]
+fn diagonals_run_to_the_edge() {
+    let clue = LittleKiller::new((8, 6), Direction::DownLeft, 10).unwrap();
+    assert_eq!(clue.cells(), &[(8, 6), (7, 7), (6, 8)]);
+
+    let clue = LittleKiller::new((3, 8), Direction::UpLeft, 10).unwrap();
+    assert_eq!(clue.cells(), &[(3, 8), (2, 7), (1, 6), (0, 5)]);
+
+    let clue = LittleKiller::new((0, 8), Direction::UpRight, 45).unwrap();
+    assert_eq!(clue.cells().len(), BOARD_SIZE);
+}
+
+#[test]
+fn candidates_are_pruned_by_sum() {
+    let clues = LittleKillers::new(vec![
+        LittleKiller::new((6, 0), Direction::DownRight, 5).unwrap()
+    ]);
+    let mut board = Board::default();
+
+    // Three cells adding up to 5 leave at most 3 for any one of them.
+    assert_eq!(clues.candidates(&board, 7, 1), 0b00_0000_1110); // {1, 2, 3}
+    assert_eq!(clues.candidates(&board, 0, 0), ALL_CANDIDATES);
+
+    board.set_cell(6, 0, 2);
+    board.set_cell(7, 1, 2);
+    assert_eq!(clues.candidates(&board, 8, 2), 0b00_0000_0010); // {1}
+
+    board.set_cell(8, 2, 3);
+    assert!(!clues.valid(&board));
+    assert_eq!(clues.violations(&board).len(), 1);
+
+    board.set_cell(8, 2, 1);
+    assert!(clues.valid(&board));
+    assert!(clues.violations(&board).is_empty());
+}
+
+#[test]
+fn propagation_uses_candidate_bounds() {
+    let clues = LittleKillers::new(vec![
+        LittleKiller::new((7, 0), Direction::DownRight, 17).unwrap()
+    ]);
+    let board = Board::default();
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+
+    // With at most 8 in one cell, the other must be 9.
+    masks[0][7] = 0b01_1111_1110;
+    assert!(clues.propagate(&board, &mut masks));
+    assert_eq!(masks[0][7], 1 << 8);
+    assert_eq!(masks[1][8], 1 << 9);
+}
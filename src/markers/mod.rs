@@ -0,0 +1,280 @@
+//! # Kropki and XV sudoku
+//!
+//! Kropki and XV puzzles place markers between pairs of orthogonally adjacent cells:
+//!
+//! * A white kropki dot means the two digits differ by 1.
+//! * A black kropki dot means one digit is double the other.
+//! * An X means the two digits add up to 10.
+//! * A V means the two digits add up to 5.
+//!
+//! Many puzzles also apply a negative constraint, under which every adjacent pair *without* a
+//! marker must not satisfy any of the relations.
+//!
+//! Both kinds of puzzle are handled by [`PairMarkers`], which is generic over the [`Relation`]
+//! used by its markers.  The [`Kropki`] and [`XV`] aliases cover the common cases.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let xv = XV::new(&[((0, 0), (1, 0), XVSign::X), ((0, 1), (1, 1), XVSign::V)])
+//!     .unwrap()
+//!     .with_negative_constraint();
+//!
+//! let solution = SolutionIter::with_rules(&Board::default(), Rules::classic().with(xv))
+//!     .next()
+//!     .unwrap();
+//!
+//! assert_eq!(solution.get_cell(0, 0) + solution.get_cell(1, 0), 10);
+//! assert_eq!(solution.get_cell(0, 1) + solution.get_cell(1, 1), 5);
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+use super::constraint::*;
+
+/// A relation between the digits in two adjacent cells, indicated by a marker.
+pub trait Relation: Copy + std::fmt::Debug + Eq + std::hash::Hash + Send + Sync + 'static {
+    /// Every kind of marker in the family.  These are the relations forbidden between unmarked
+    /// pairs when the negative constraint applies.
+    const ALL: &'static [Self];
+
+    /// Test whether digits `a` and `b` satisfy the relation.  The relation must be symmetric.
+    fn holds(self, a: u8, b: u8) -> bool;
+}
+
+/// The kinds of kropki dot.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KropkiDot {
+    /// The two digits differ by 1.
+    White,
+
+    /// One digit is double the other.
+    Black,
+}
+
+impl Relation for KropkiDot {
+    const ALL: &'static [Self] = &[KropkiDot::White, KropkiDot::Black];
+
+    #[inline]
+    fn holds(self, a: u8, b: u8) -> bool {
+        match self {
+            KropkiDot::White => a.abs_diff(b) == 1,
+            KropkiDot::Black => a == 2 * b || b == 2 * a,
+        }
+    }
+}
+
+/// The kinds of XV sign.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum XVSign {
+    /// The two digits add up to 10.
+    X,
+
+    /// The two digits add up to 5.
+    V,
+}
+
+impl Relation for XVSign {
+    const ALL: &'static [Self] = &[XVSign::X, XVSign::V];
+
+    #[inline]
+    fn holds(self, a: u8, b: u8) -> bool {
+        match self {
+            XVSign::X => a + b == 10,
+            XVSign::V => a + b == 5,
+        }
+    }
+}
+
+/// Kropki dots between adjacent cells.
+pub type Kropki = PairMarkers<KropkiDot>;
+
+/// XV signs between adjacent cells.
+pub type XV = PairMarkers<XVSign>;
+
+/// A marker, given as the two cells it sits between and the relation it indicates.
+pub type Marker<R> = ((usize, usize), (usize, usize), R);
+
+/// An error arising from an invalid marker definition.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MarkerError {
+    /// A cell lies outside the board.
+    CellOutOfRange(usize, usize),
+
+    /// The two cells of a marker are not orthogonally adjacent.
+    NotAdjacent((usize, usize), (usize, usize)),
+
+    /// More than one marker sits between the same pair of cells.
+    DuplicateMarker((usize, usize), (usize, usize)),
+}
+
+impl std::fmt::Display for MarkerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarkerError::CellOutOfRange(x, y) => write!(f, "cell ({}, {}) is off the board", x, y),
+            MarkerError::NotAdjacent(a, b) => {
+                write!(f, "cells {:?} and {:?} are not adjacent", a, b)
+            }
+            MarkerError::DuplicateMarker(a, b) => {
+                write!(f, "more than one marker between cells {:?} and {:?}", a, b)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MarkerError {}
+
+/// A set of markers between adjacent cells, with an optional negative constraint.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PairMarkers<R: Relation> {
+    markers: Vec<Marker<R>>,
+    edges: [[[Option<R>; 4]; BOARD_SIZE]; BOARD_SIZE],
+    negative: bool,
+}
+
+impl<R: Relation> PairMarkers<R> {
+    /// Create a set of markers.  The negative constraint does not apply.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// assert!(Kropki::new(&[((0, 0), (0, 1), KropkiDot::Black)]).is_ok());
+    ///
+    /// assert_eq!(
+    ///     Kropki::new(&[((0, 0), (1, 1), KropkiDot::White)]),
+    ///     Err(MarkerError::NotAdjacent((0, 0), (1, 1)))
+    /// );
+    /// # }
+    /// ```
+    pub fn new(markers: &[Marker<R>]) -> Result<Self, MarkerError> {
+        let mut edges = [[[None; 4]; BOARD_SIZE]; BOARD_SIZE];
+
+        for &(a, b, relation) in markers {
+            for (x, y) in [a, b] {
+                if x >= BOARD_SIZE || y >= BOARD_SIZE {
+                    return Err(MarkerError::CellOutOfRange(x, y));
+                }
+            }
+
+            let d = (0..4)
+                .find(|&d| neighbour(a.0, a.1, d) == Some(b))
+                .ok_or(MarkerError::NotAdjacent(a, b))?;
+
+            if edges[a.1][a.0][d].is_some() {
+                return Err(MarkerError::DuplicateMarker(a, b));
+            }
+
+            edges[a.1][a.0][d] = Some(relation);
+            edges[b.1][b.0][(d + 2) % 4] = Some(relation);
+        }
+
+        Ok(Self {
+            markers: markers.to_vec(),
+            edges,
+            negative: false,
+        })
+    }
+
+    /// Apply the negative constraint, so that no unmarked pair of adjacent cells may satisfy any
+    /// relation in the family.
+    pub fn with_negative_constraint(mut self) -> Self {
+        self.negative = true;
+        self
+    }
+
+    /// The markers in the set.
+    #[inline]
+    pub fn markers(&self) -> &[Marker<R>] {
+        &self.markers
+    }
+
+    /// Returns `true` if the negative constraint applies.
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns a mask of the digits `d` for which `pass(d, e)` holds for some digit `e` which may
+    /// appear in the neighbouring cell.
+    #[inline]
+    fn partners(neighbour: u8, pass: impl Fn(u8, u8) -> bool) -> u16 {
+        (1..=BOARD_SIZE as u8)
+            .filter(|&d| {
+                if neighbour == 0 {
+                    (1..=BOARD_SIZE as u8).any(|e| pass(d, e))
+                } else {
+                    pass(d, neighbour)
+                }
+            })
+            .fold(0, |acc, d| acc | (1 << d))
+    }
+}
+
+impl<R: Relation> Constraint for PairMarkers<R> {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        let mut cs = ALL_CANDIDATES;
+
+        for (d, edge) in self.edges[y][x].iter().enumerate() {
+            let (nx, ny) = match neighbour(x, y, d) {
+                Some(n) => n,
+                None => continue,
+            };
+
+            let v = board.get_cell(nx, ny);
+
+            match edge {
+                Some(relation) => cs &= Self::partners(v, |a, b| relation.holds(a, b)),
+                None if self.negative && v != 0 => {
+                    cs &= Self::partners(v, |a, b| !R::ALL.iter().any(|r| r.holds(a, b)))
+                }
+                None => {}
+            }
+        }
+
+        cs
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let a = board.get_cell(x, y);
+
+                if a == 0 {
+                    continue;
+                }
+
+                // Only look right and down, so that each pair is checked once.
+                for d in 0..2 {
+                    let b = match neighbour(x, y, d) {
+                        Some((nx, ny)) => board.get_cell(nx, ny),
+                        None => continue,
+                    };
+
+                    if b == 0 {
+                        continue;
+                    }
+
+                    let ok = match self.edges[y][x][d] {
+                        Some(relation) => relation.holds(a, b),
+                        None => !self.negative || !R::ALL.iter().any(|r| r.holds(a, b)),
+                    };
+
+                    if !ok {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+}
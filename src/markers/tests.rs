@@ -0,0 +1,87 @@
+use super::*;
+
+#[test]
+fn relations_are_correct() {
+    assert!(KropkiDot::White.holds(4, 5));
+    assert!(KropkiDot::White.holds(5, 4));
+    assert!(!KropkiDot::White.holds(4, 6));
+    assert!(KropkiDot::Black.holds(3, 6));
+    assert!(KropkiDot::Black.holds(8, 4));
+    assert!(!KropkiDot::Black.holds(3, 9));
+
+    assert!(XVSign::X.holds(3, 7));
+    assert!(!XVSign::X.holds(3, 6));
+    assert!(XVSign::V.holds(1, 4));
+    assert!(!XVSign::V.holds(1, 5));
+}
+
+#[test]
+fn invalid_markers_are_rejected() {
+    assert_eq!(
+        Kropki::new(&[((8, 0), (9, 0), KropkiDot::White)]),
+        Err(MarkerError::CellOutOfRange(9, 0))
+    );
+    assert_eq!(
+        Kropki::new(&[((0, 0), (2, 0), KropkiDot::White)]),
+        Err(MarkerError::NotAdjacent((0, 0), (2, 0)))
+    );
+    assert_eq!(
+        XV::new(&[((0, 0), (1, 0), XVSign::X), ((1, 0), (0, 0), XVSign::V)]),
+        Err(MarkerError::DuplicateMarker((1, 0), (0, 0)))
+    );
+}
+
+#[test]
+fn kropki_candidates_are_correct() {
+    let kropki = Kropki::new(&[
+        ((0, 0), (1, 0), KropkiDot::White),
+        ((0, 0), (0, 1), KropkiDot::Black),
+    ])
+    .unwrap();
+
+    let mut board = Board::default();
+
+    // Only digits with a partner under doubling are allowed next to a black dot.
+    assert_eq!(kropki.candidates(&board, 0, 1), 0b01_0101_1110); // {1, 2, 3, 4, 6, 8}
+    assert_eq!(kropki.candidates(&board, 1, 0), ALL_CANDIDATES);
+    assert_eq!(kropki.candidates(&board, 0, 0), 0b01_0101_1110);
+
+    board.set_cell(0, 0, 4);
+    assert_eq!(kropki.candidates(&board, 1, 0), 0b00_0010_1000); // {3, 5}
+    assert_eq!(kropki.candidates(&board, 0, 1), 0b01_0000_0100); // {2, 8}
+
+    // Without the negative constraint, unmarked neighbours are unaffected.
+    assert_eq!(kropki.candidates(&board, 2, 0), ALL_CANDIDATES);
+
+    let kropki = kropki.with_negative_constraint();
+    assert_eq!(kropki.candidates(&board, 1, 0), 0b00_0010_1000); // {3, 5}
+
+    board.set_cell(1, 0, 3);
+    assert_eq!(kropki.candidates(&board, 2, 0), 0b11_1010_1010); // {1, 3, 5, 7, 8, 9}
+}
+
+#[test]
+fn xv_violations_are_detected() {
+    let xv = XV::new(&[((4, 4), (5, 4), XVSign::X), ((4, 4), (4, 5), XVSign::V)]).unwrap();
+    let mut board = Board::default();
+    assert!(xv.valid(&board));
+
+    board.set_cell(4, 4, 3);
+    board.set_cell(5, 4, 7);
+    board.set_cell(4, 5, 2);
+    assert!(xv.valid(&board));
+
+    board.set_cell(4, 5, 1);
+    assert!(!xv.valid(&board));
+
+    board.set_cell(4, 5, 2);
+    board.set_cell(3, 4, 7);
+    assert!(xv.valid(&board));
+
+    let xv = xv.with_negative_constraint();
+    assert!(xv.is_negative());
+    assert!(!xv.valid(&board));
+
+    board.set_cell(3, 4, 8);
+    assert!(xv.valid(&board));
+}
@@ -0,0 +1,550 @@
+//! # Multi-grid sudoku
+//!
+//! Puzzles such as Samurai sudoku are made of several overlapping nine-by-nine grids.  A
+//! [`MultiBoard`] places each grid at an offset on a larger canvas; cells at the same canvas
+//! position are shared by every grid which covers them.  Each grid keeps its own [`Board`], so the
+//! usual [`Rules`] can be applied to it, and a [`MultiSolutionIter`] searches for solutions to the
+//! whole composite.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! // Two grids sharing a single corner square.
+//! let board = MultiBoard::new(&[(0, 0), (6, 6)]).unwrap();
+//! let solution = MultiSolutionIter::new(&board).next().unwrap();
+//!
+//! for y in 6..9 {
+//!     for x in 6..9 {
+//!         assert_eq!(solution.grids()[0].get_cell(x, y), solution.grids()[1].get_cell(x - 6, y - 6));
+//!     }
+//! }
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::iter::FusedIterator;
+
+use super::board::*;
+use super::constraint::*;
+use bit_iter::BitIter;
+
+/// The offsets of the five grids of a Samurai sudoku.
+const SAMURAI: [(usize, usize); 5] = [(0, 0), (12, 0), (6, 6), (0, 12), (12, 12)];
+
+/// An error arising from an invalid grid layout, from conflicting givens, or from a mismatched
+/// set of rules.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MultiBoardError {
+    /// The layout contains no grids.
+    Empty,
+
+    /// Two grids are placed at the same offset.
+    DuplicateGrid(usize, usize),
+
+    /// There is no grid with the given index.
+    GridOutOfRange(usize),
+
+    /// A grid is placed further from the origin than a layout with this many grids can reach, so
+    /// that most of the canvas would be empty.
+    OffsetTooLarge(usize, usize),
+
+    /// A grid neither overlaps nor touches any other grid, even at a corner.
+    Disconnected(usize, usize),
+
+    /// A shared cell would be given two different digits.  The coordinates are on the canvas.
+    Conflict(usize, usize),
+
+    /// The number of [`Rules`] values given (the first value) differs from the number of grids
+    /// (the second).
+    RuleCount(usize, usize),
+}
+
+impl std::fmt::Display for MultiBoardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MultiBoardError::Empty => write!(f, "a multi-grid board must have at least one grid"),
+            MultiBoardError::DuplicateGrid(x, y) => {
+                write!(f, "more than one grid is placed at ({}, {})", x, y)
+            }
+            MultiBoardError::GridOutOfRange(i) => write!(f, "there is no grid {}", i),
+            MultiBoardError::OffsetTooLarge(x, y) => {
+                write!(f, "a grid at ({}, {}) is too far from the origin", x, y)
+            }
+            MultiBoardError::Disconnected(x, y) => {
+                write!(f, "the grid at ({}, {}) is not joined to the others", x, y)
+            }
+            MultiBoardError::Conflict(x, y) => {
+                write!(f, "conflicting digits for the shared cell ({}, {})", x, y)
+            }
+            MultiBoardError::RuleCount(rules, grids) => {
+                write!(f, "{} sets of rules were given for {} grids", rules, grids)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MultiBoardError {}
+
+/// A puzzle or solution made of several overlapping grids.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MultiBoard {
+    offsets: Vec<(usize, usize)>,
+    grids: Vec<Board>,
+    width: usize,
+    height: usize,
+}
+
+impl MultiBoard {
+    /// Create an empty `MultiBoard` with a grid at each of the given offsets.  An offset is the
+    /// canvas position of the grid's top-left cell.  The layout must have at least one grid and no
+    /// two grids at the same offset.  Every grid must overlap or touch another, so that the grids
+    /// form a single puzzle, and no grid may lie further from the origin than the grids laid end
+    /// to end would reach.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let board = MultiBoard::new(&[(0, 0), (6, 6)]).unwrap();
+    ///
+    /// assert_eq!((board.width(), board.height()), (15, 15));
+    /// assert!(board.contains(7, 7));
+    /// assert!(!board.contains(12, 0));
+    ///
+    /// assert_eq!(
+    ///     MultiBoard::new(&[(0, 0), (0, 0)]),
+    ///     Err(MultiBoardError::DuplicateGrid(0, 0))
+    /// );
+    /// assert_eq!(
+    ///     MultiBoard::new(&[(0, 0), (6, 6), (16, 6)]),
+    ///     Err(MultiBoardError::Disconnected(16, 6))
+    /// );
+    /// # }
+    /// ```
+    pub fn new(offsets: &[(usize, usize)]) -> Result<Self, MultiBoardError> {
+        if offsets.is_empty() {
+            return Err(MultiBoardError::Empty);
+        }
+
+        for (i, &offset) in offsets.iter().enumerate() {
+            if offsets[..i].contains(&offset) {
+                return Err(MultiBoardError::DuplicateGrid(offset.0, offset.1));
+            }
+        }
+
+        // A layout whose grids all join up spans at most this many cells in each direction.
+        let limit = BOARD_SIZE.saturating_mul(offsets.len());
+
+        for &(x, y) in offsets {
+            if x > limit - BOARD_SIZE || y > limit - BOARD_SIZE {
+                return Err(MultiBoardError::OffsetTooLarge(x, y));
+            }
+        }
+
+        let touching = |(ax, ay): (usize, usize), (bx, by): (usize, usize)| {
+            ax.abs_diff(bx) <= BOARD_SIZE && ay.abs_diff(by) <= BOARD_SIZE
+        };
+
+        // Find every grid joined to the first, one grid at a time.
+        let mut joined = vec![false; offsets.len()];
+        let mut pending = vec![0];
+        joined[0] = true;
+
+        while let Some(i) = pending.pop() {
+            for (j, &offset) in offsets.iter().enumerate() {
+                if !joined[j] && touching(offsets[i], offset) {
+                    joined[j] = true;
+                    pending.push(j);
+                }
+            }
+        }
+
+        if let Some(j) = joined.iter().position(|&joined| !joined) {
+            return Err(MultiBoardError::Disconnected(offsets[j].0, offsets[j].1));
+        }
+
+        let width = offsets
+            .iter()
+            .map(|&(x, _)| x + BOARD_SIZE)
+            .max()
+            .unwrap_or(0);
+        let height = offsets
+            .iter()
+            .map(|&(_, y)| y + BOARD_SIZE)
+            .max()
+            .unwrap_or(0);
+
+        Ok(Self {
+            offsets: offsets.to_vec(),
+            grids: vec![Board::default(); offsets.len()],
+            width,
+            height,
+        })
+    }
+
+    /// Create an empty Samurai sudoku: four grids at the corners of a 21×21 canvas, each sharing
+    /// a corner square with a fifth grid in the middle.
+    pub fn samurai() -> Self {
+        Self::new(&SAMURAI).unwrap()
+    }
+
+    /// The canvas offset of each grid.
+    #[inline]
+    pub fn offsets(&self) -> &[(usize, usize)] {
+        &self.offsets
+    }
+
+    /// The contents of each grid.
+    #[inline]
+    pub fn grids(&self) -> &[Board] {
+        &self.grids
+    }
+
+    /// The width of the canvas.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of the canvas.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Iterate over the grids covering canvas cell `(x, y)`, as the grid index and the cell's
+    /// coordinates within that grid.
+    fn covering(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.offsets
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, &(ox, oy))| {
+                let gx = x.checked_sub(ox)?;
+                let gy = y.checked_sub(oy)?;
+
+                if gx < BOARD_SIZE && gy < BOARD_SIZE {
+                    Some((i, gx, gy))
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Test whether canvas cell `(x, y)` belongs to any grid.
+    #[inline]
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        self.covering(x, y).next().is_some()
+    }
+
+    /// Get the contents of canvas cell `(x, y)`.  Cells which belong to no grid read as zero.
+    pub fn get_cell(&self, x: usize, y: usize) -> u8 {
+        self.covering(x, y)
+            .next()
+            .map_or(0, |(i, gx, gy)| self.grids[i].get_cell(gx, gy))
+    }
+
+    /// Set the contents of canvas cell `(x, y)` in every grid which covers it.  Cells which belong
+    /// to no grid are ignored.
+    pub fn set_cell(&mut self, x: usize, y: usize, value: u8) {
+        let mask = 1 << value;
+        self.set_cell_as_mask(x, y, mask);
+    }
+
+    fn set_cell_as_mask(&mut self, x: usize, y: usize, mask: u16) {
+        for (i, gx, gy) in self.covering(x, y).collect::<Vec<_>>() {
+            self.grids[i].set_cell_as_mask(gx, gy, mask);
+        }
+    }
+
+    /// Copy the filled cells of `board` into grid `index`, and into any other grid which shares
+    /// them.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let mut board = MultiBoard::new(&[(0, 0), (6, 6)]).unwrap();
+    /// let mut grid = Board::default();
+    ///
+    /// grid.set_cell(0, 0, 5);
+    /// board.set_grid(1, &grid).unwrap();
+    /// assert_eq!(board.grids()[0].get_cell(6, 6), 5);
+    ///
+    /// grid.set_cell(0, 0, 4);
+    /// assert_eq!(board.set_grid(1, &grid), Err(MultiBoardError::Conflict(6, 6)));
+    /// # }
+    /// ```
+    pub fn set_grid(&mut self, index: usize, board: &Board) -> Result<(), MultiBoardError> {
+        let &(ox, oy) = self
+            .offsets
+            .get(index)
+            .ok_or(MultiBoardError::GridOutOfRange(index))?;
+
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let v = board.get_cell(x, y);
+                let current = self.grids[index].get_cell(x, y);
+
+                if v != 0 && current != 0 && v != current {
+                    return Err(MultiBoardError::Conflict(ox + x, oy + y));
+                }
+            }
+        }
+
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                let v = board.get_cell(x, y);
+
+                if v != 0 {
+                    self.set_cell(ox + x, oy + y, v);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for MultiBoard {
+    /// Format the canvas in the same style as a [`Board`], leaving cells outside every grid blank.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = Vec::with_capacity(self.height);
+
+        for y in 0..self.height {
+            let mut s = String::new();
+
+            for x in 0..self.width {
+                if x != 0 {
+                    s.push(' ');
+                }
+
+                s.push(match self.covering(x, y).next() {
+                    Some((i, gx, gy)) => self.grids[i].cell_char(gx, gy, '-'),
+                    None => ' ',
+                });
+            }
+
+            lines.push(s.trim_end().to_string());
+        }
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// An iterator which produces the solutions to a multi-grid puzzle.
+///
+/// Each grid is solved under its own [`Rules`], and every grid covering a shared cell must agree
+/// on its digit.
+///
+/// ## Example
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let mut board = MultiBoard::new(&[(0, 0), (6, 6)]).unwrap();
+/// board.set_cell(0, 0, 9);
+///
+/// // The second grid must also be an X-sudoku.
+/// let rules = vec![Rules::classic(), Rules::classic().with(Diagonals)];
+/// let solution = MultiSolutionIter::with_rules(&board, rules)
+///     .unwrap()
+///     .next()
+///     .unwrap();
+///
+/// assert_eq!(solution.get_cell(0, 0), 9);
+/// assert!(valid_with_rules(&solution.grids()[1], &Rules::classic().with(Diagonals)));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MultiSolutionIter {
+    first: bool,
+    board: MultiBoard,
+    rules: Vec<Rules>,
+    stack: Vec<(usize, usize, BitIter<u16>)>,
+    /// The grids covering each canvas cell, as from `MultiBoard::covering`, in row-major order.
+    cells: Vec<Vec<(usize, usize, usize)>>,
+    /// The candidates of each canvas cell, reused from one step to the next.
+    masks: Vec<u16>,
+}
+
+impl MultiSolutionIter {
+    /// Construct a `MultiSolutionIter` which applies the classic sudoku rules to every grid.
+    pub fn new(board: &MultiBoard) -> Self {
+        let rules = vec![Rules::default(); board.grids.len()];
+        let cells = (0..board.height)
+            .flat_map(|y| (0..board.width).map(move |x| board.covering(x, y).collect()))
+            .collect();
+
+        Self {
+            first: true,
+            board: board.clone(),
+            rules,
+            stack: Vec::with_capacity(board.width * board.height),
+            cells,
+            masks: vec![0; board.width * board.height],
+        }
+    }
+
+    /// Construct a `MultiSolutionIter` which applies the given [`Rules`] to each grid in turn.
+    ///
+    /// Returns [`MultiBoardError::RuleCount`] if the number of `Rules` values differs from the
+    /// number of grids.
+    pub fn with_rules(board: &MultiBoard, rules: Vec<Rules>) -> Result<Self, MultiBoardError> {
+        if rules.len() != board.grids.len() {
+            return Err(MultiBoardError::RuleCount(rules.len(), board.grids.len()));
+        }
+
+        Ok(Self {
+            rules,
+            ..Self::new(board)
+        })
+    }
+
+    fn valid(&self) -> bool {
+        self.board
+            .grids
+            .iter()
+            .zip(&self.rules)
+            .all(|(grid, rules)| rules.valid(grid))
+    }
+
+    /// Find the empty cell with the fewest candidates across all the grids which cover it.
+    fn cell_with_fewest_candidates(&mut self) -> Option<(usize, usize, u16)> {
+        let b = &self.board;
+        let masks = &mut self.masks;
+
+        for (mask, cells) in masks.iter_mut().zip(&self.cells) {
+            let Some(&(i, gx, gy)) = cells.first() else {
+                *mask = 0;
+                continue;
+            };
+
+            *mask = match b.grids[i].get_cell_as_mask(gx, gy) {
+                1 => cells.iter().fold(ALL_CANDIDATES, |cs, &(i, gx, gy)| {
+                    cs & self.rules[i].candidates(&b.grids[i], gx, gy)
+                }),
+                value => value,
+            };
+
+            if *mask == 0 {
+                // No valid choices for this empty cell, so we need to backtrack.
+                return None;
+            }
+        }
+
+        // Overlapping grids constrain each other through their shared cells, which candidates
+        // alone don't capture, so always propagate within each grid until nothing changes.
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for (i, &(ox, oy)) in b.offsets.iter().enumerate() {
+                let mut grid_masks: CandidateMasks = std::array::from_fn(|y| {
+                    std::array::from_fn(|x| masks[(oy + y) * b.width + ox + x])
+                });
+
+                self.rules[i].propagate(&b.grids[i], &mut grid_masks);
+
+                for (y, row) in grid_masks.iter().enumerate() {
+                    for (x, &mask) in row.iter().enumerate() {
+                        let m = &mut masks[(oy + y) * b.width + ox + x];
+                        let new = *m & mask;
+
+                        if new != *m {
+                            *m = new;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut min = (0, 0, 0);
+        let mut min_count = BOARD_SIZE + 1;
+
+        for (n, (&mask, cells)) in masks.iter().zip(&self.cells).enumerate() {
+            match cells.first() {
+                Some(&(i, gx, gy)) if b.grids[i].get_cell(gx, gy) == 0 => {
+                    let count = mask.count_ones() as usize;
+
+                    if count == 0 {
+                        return None;
+                    } else if count < min_count {
+                        min = (n % b.width, n / b.width, mask);
+                        min_count = count;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(min)
+    }
+}
+
+/// `From` implementation for `MultiSolutionIter`.
+impl From<MultiBoard> for MultiSolutionIter {
+    fn from(board: MultiBoard) -> Self {
+        Self::new(&board)
+    }
+}
+
+/// `Iterator` implementation for `MultiSolutionIter`.
+impl Iterator for MultiSolutionIter {
+    type Item = MultiBoard;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.first {
+            self.first = false;
+
+            if self.valid() {
+                if let Some((x, y, values)) = self.cell_with_fewest_candidates() {
+                    if values == 0 {
+                        return Some(self.board.clone());
+                    }
+
+                    self.stack.push((x, y, values.into()));
+                }
+            }
+        }
+
+        let (mut x, mut y, mut values) = self.stack.pop()?;
+
+        loop {
+            if let Some(value) = values.next() {
+                self.board.set_cell(x, y, value as u8);
+
+                if let Some(cs) = self.cell_with_fewest_candidates() {
+                    if cs.2 == 0 {
+                        if self.valid() {
+                            self.stack.push((x, y, values));
+                            return Some(self.board.clone());
+                        }
+                    } else {
+                        self.stack.push((x, y, values));
+                        x = cs.0;
+                        y = cs.1;
+                        values = cs.2.into();
+                    }
+                }
+            } else {
+                self.board.set_cell_as_mask(x, y, 1);
+
+                let cs = self.stack.pop()?;
+                x = cs.0;
+                y = cs.1;
+                values = cs.2;
+            }
+        }
+    }
+}
+
+/// `FusedIterator` implementation for `MultiSolutionIter`.
+impl FusedIterator for MultiSolutionIter {}
@@ -0,0 +1,224 @@
+use super::*;
+use crate::solver::*;
+
+#[test]
+fn layouts_are_checked() {
+    assert_eq!(MultiBoard::new(&[]), Err(MultiBoardError::Empty));
+    assert_eq!(
+        MultiBoard::new(&[(0, 0), (6, 6), (6, 6)]),
+        Err(MultiBoardError::DuplicateGrid(6, 6))
+    );
+    assert_eq!(
+        MultiBoard::new(&[(0, 0), (usize::MAX - 3, 0)]),
+        Err(MultiBoardError::OffsetTooLarge(usize::MAX - 3, 0))
+    );
+    assert_eq!(
+        MultiBoard::new(&[(0, 0), (usize::MAX / 2, usize::MAX / 2)]),
+        Err(MultiBoardError::OffsetTooLarge(
+            usize::MAX / 2,
+            usize::MAX / 2
+        ))
+    );
+    assert_eq!(
+        MultiBoard::new(&[(0, 0), (100_000, 100_000)]),
+        Err(MultiBoardError::OffsetTooLarge(100_000, 100_000))
+    );
+    assert_eq!(
+        MultiBoard::new(&[(100, 100)]),
+        Err(MultiBoardError::OffsetTooLarge(100, 100))
+    );
+    assert_eq!(
+        MultiBoard::new(&[(0, 0), (6, 6), (16, 6)]),
+        Err(MultiBoardError::Disconnected(16, 6))
+    );
+    assert!(MultiBoard::new(&[(0, 0), (9, 9)]).is_ok());
+
+    let board = MultiBoard::samurai();
+    assert_eq!(board.offsets().len(), 5);
+    assert_eq!((board.width(), board.height()), (21, 21));
+    assert!(board.contains(10, 7));
+    assert!(!board.contains(10, 3));
+    assert!(!board.contains(21, 0));
+}
+
+#[test]
+fn shared_cells_are_set_in_every_grid() {
+    let mut board = MultiBoard::samurai();
+    board.set_cell(7, 7, 4);
+
+    assert_eq!(board.get_cell(7, 7), 4);
+    assert_eq!(board.grids()[0].get_cell(7, 7), 4);
+    assert_eq!(board.grids()[2].get_cell(1, 1), 4);
+    assert!(board.grids()[1..]
+        .iter()
+        .skip(2)
+        .all(|g| *g == Board::default()));
+
+    // Cells outside every grid are ignored.
+    board.set_cell(10, 2, 5);
+    assert_eq!(board.get_cell(10, 2), 0);
+
+    assert_eq!(
+        board.set_grid(5, &Board::default()),
+        Err(MultiBoardError::GridOutOfRange(5))
+    );
+}
+
+#[test]
+fn boards_are_displayed_on_the_canvas() {
+    let mut board = MultiBoard::new(&[(0, 0), (3, 9)]).unwrap();
+    board.set_cell(0, 0, 1);
+    board.set_cell(11, 17, 9);
+
+    let text = board.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert_eq!(lines.len(), 18);
+    assert_eq!(lines[0], "1 - - - - - - - -");
+    assert_eq!(lines[9], "      - - - - - - - - -");
+    assert_eq!(lines[17], "      - - - - - - - - 9");
+
+    // Values which are not digits are shown as `?`.
+    board.set_cell(0, 0, 12);
+    assert!(board.to_string().starts_with("? - - - - - - - -\n"));
+}
+
+#[test]
+fn rules_are_needed_for_every_grid() {
+    let board = MultiBoard::samurai();
+
+    assert_eq!(
+        MultiSolutionIter::with_rules(&board, vec![Rules::classic()]).err(),
+        Some(MultiBoardError::RuleCount(1, 5))
+    );
+    assert!(MultiSolutionIter::with_rules(&board, vec![Rules::classic(); 5]).is_ok());
+}
+
+/// A constraint whose propagation hands back more candidates than it was given, while reporting
+/// that it removed none.
+#[derive(Debug)]
+struct Widening;
+
+impl Constraint for Widening {
+    fn candidates(&self, _board: &Board, _x: usize, _y: usize) -> u16 {
+        ALL_CANDIDATES
+    }
+
+    fn valid(&self, _board: &Board) -> bool {
+        true
+    }
+
+    fn propagates(&self) -> bool {
+        true
+    }
+
+    fn propagate(&self, _board: &Board, masks: &mut CandidateMasks) -> bool {
+        masks[0][0] = ALL_CANDIDATES;
+        false
+    }
+}
+
+#[test]
+fn widening_propagation_terminates() {
+    let board = MultiBoard::new(&[(0, 0), (6, 6)]).unwrap();
+    let rules = vec![Rules::classic().with(Widening), Rules::classic()];
+    let solution = MultiSolutionIter::with_rules(&board, rules)
+        .unwrap()
+        .next()
+        .unwrap();
+
+    assert!(solution.grids().iter().all(valid));
+}
+
+#[test]
+fn solves_samurai_puzzles() {
+    let grids = [
+        // top left
+        Board::from(&[
+            [0, 0, 0, 3, 4, 0, 0, 0, 9], // row 1
+            [3, 0, 5, 0, 0, 0, 1, 0, 0], // row 2
+            [6, 0, 0, 1, 2, 0, 0, 0, 0], // row 3
+            [1, 0, 3, 0, 0, 0, 0, 0, 0], // row 4
+            [9, 7, 0, 0, 6, 0, 0, 0, 3], // row 5
+            [0, 0, 6, 0, 0, 0, 2, 0, 0], // row 6
+            [2, 0, 0, 0, 7, 0, 0, 0, 0], // row 7
+            [0, 0, 0, 9, 0, 0, 0, 3, 0], // row 8
+            [0, 0, 7, 0, 0, 0, 5, 0, 0], // row 9
+        ]),
+        // top right
+        Board::from(&[
+            [6, 0, 0, 1, 0, 0, 0, 8, 0], // row 1
+            [0, 4, 0, 6, 0, 9, 1, 0, 0], // row 2
+            [0, 0, 0, 2, 0, 0, 4, 5, 0], // row 3
+            [0, 0, 2, 5, 0, 4, 0, 0, 0], // row 4
+            [0, 0, 6, 7, 0, 0, 0, 0, 0], // row 5
+            [0, 9, 0, 0, 0, 1, 0, 0, 0], // row 6
+            [5, 0, 0, 0, 2, 0, 0, 0, 8], // row 7
+            [0, 0, 0, 9, 0, 0, 0, 3, 0], // row 8
+            [0, 0, 9, 0, 0, 0, 5, 0, 0], // row 9
+        ]),
+        // centre
+        Board::from(&[
+            [0, 0, 0, 0, 1, 0, 5, 0, 0], // row 1
+            [0, 3, 0, 0, 0, 9, 0, 0, 0], // row 2
+            [5, 0, 0, 4, 7, 0, 0, 0, 9], // row 3
+            [4, 5, 0, 0, 0, 2, 0, 0, 0], // row 4
+            [1, 0, 0, 0, 3, 0, 0, 0, 8], // row 5
+            [0, 0, 0, 7, 0, 0, 0, 1, 0], // row 6
+            [2, 0, 0, 0, 4, 0, 0, 0, 5], // row 7
+            [0, 0, 0, 9, 0, 0, 0, 0, 0], // row 8
+            [0, 0, 0, 0, 0, 0, 3, 0, 0], // row 9
+        ]),
+        // bottom left
+        Board::from(&[
+            [0, 0, 7, 0, 0, 0, 2, 0, 0], // row 1
+            [2, 8, 0, 0, 0, 7, 0, 0, 0], // row 2
+            [1, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+            [0, 0, 0, 4, 0, 1, 0, 9, 0], // row 4
+            [3, 9, 0, 0, 0, 2, 0, 0, 0], // row 5
+            [0, 0, 0, 5, 9, 0, 0, 2, 0], // row 6
+            [8, 7, 2, 6, 0, 3, 0, 0, 0], // row 7
+            [0, 0, 0, 7, 0, 0, 0, 3, 0], // row 8
+            [0, 5, 0, 0, 0, 4, 0, 0, 0], // row 9
+        ]),
+        // bottom right
+        Board::from(&[
+            [0, 0, 5, 3, 0, 0, 1, 0, 0], // row 1
+            [0, 0, 0, 0, 0, 9, 0, 0, 0], // row 2
+            [3, 0, 0, 1, 2, 0, 0, 0, 7], // row 3
+            [0, 6, 9, 8, 4, 0, 3, 0, 0], // row 4
+            [1, 0, 0, 6, 9, 0, 4, 0, 2], // row 5
+            [0, 0, 7, 2, 0, 0, 9, 0, 0], // row 6
+            [5, 0, 0, 0, 3, 0, 0, 0, 8], // row 7
+            [0, 0, 4, 0, 0, 0, 6, 0, 0], // row 8
+            [6, 0, 0, 0, 8, 0, 0, 0, 5], // row 9
+        ]),
+    ];
+
+    let mut board = MultiBoard::samurai();
+
+    for (i, grid) in grids.iter().enumerate() {
+        board.set_grid(i, grid).unwrap();
+    }
+
+    let centre_solution = Board::from(&[
+        [8, 9, 4, 2, 1, 3, 5, 6, 7], // row 1
+        [7, 3, 2, 5, 6, 9, 1, 8, 4], // row 2
+        [5, 1, 6, 4, 7, 8, 2, 3, 9], // row 3
+        [4, 5, 7, 1, 8, 2, 6, 9, 3], // row 4
+        [1, 2, 9, 6, 3, 4, 7, 5, 8], // row 5
+        [3, 6, 8, 7, 9, 5, 4, 1, 2], // row 6
+        [2, 8, 1, 3, 4, 6, 9, 7, 5], // row 7
+        [6, 4, 3, 9, 5, 7, 8, 2, 1], // row 8
+        [9, 7, 5, 8, 2, 1, 3, 4, 6], // row 9
+    ]);
+
+    let mut solutions = MultiSolutionIter::new(&board);
+    let solution = solutions.next().unwrap();
+
+    assert_eq!(solution.grids()[2], centre_solution);
+    assert!(solution.grids().iter().all(|grid| {
+        valid(grid) && (0..BOARD_SIZE).all(|y| (0..BOARD_SIZE).all(|x| grid.get_cell(x, y) != 0))
+    }));
+    assert_eq!(solutions.next(), None);
+}
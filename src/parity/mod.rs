@@ -0,0 +1,228 @@
+//! # Odd/even sudoku
+//!
+//! Some cells are marked to say whether their digit is odd or even.  Conventionally, even cells
+//! are shaded with a grey square and odd cells are marked with a circle.
+//!
+//! The [`OddEven`] constraint holds the markings.  It can be read from and written to a text
+//! pattern in the same layout as a [`Board`], using `o` for odd cells, `e` for even cells and `-`
+//! (or `.`) for unmarked cells.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let odd_even: OddEven = "
+//!     e e e e - - - - -
+//!     - - - - - - - - -
+//!     - - - - - - - - -
+//!     - - - - - - - - -
+//!     - - - - - - - - -
+//!     - - - - - - - - -
+//!     - - - - - - - - -
+//!     - - - - - - - - -
+//!     - - - - - - - - o
+//! "
+//! .parse()
+//! .unwrap();
+//!
+//! let solution = SolutionIter::with_rules(&Board::default(), Rules::classic().with(odd_even))
+//!     .next()
+//!     .unwrap();
+//!
+//! for x in 0..4 {
+//!     assert_eq!(solution.get_cell(x, 0) % 2, 0);
+//! }
+//!
+//! assert_eq!(solution.get_cell(8, 8) % 2, 1);
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::str::FromStr;
+
+use super::board::*;
+use super::constraint::*;
+
+/// Whether a cell must hold an odd or an even digit.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Parity {
+    /// The digit is 1, 3, 5, 7 or 9.  Usually marked with a circle.
+    Odd,
+
+    /// The digit is 2, 4, 6 or 8.  Usually marked with a shaded square.
+    Even,
+}
+
+impl Parity {
+    /// Returns a mask of the digits with this parity.
+    #[inline]
+    pub fn mask(self) -> u16 {
+        match self {
+            Parity::Odd => 0b10_1010_1010,
+            Parity::Even => 0b01_0101_0100,
+        }
+    }
+
+    /// Test whether digit `v` has this parity.
+    #[inline]
+    pub fn matches(self, v: u8) -> bool {
+        self.mask() & (1 << v) != 0
+    }
+}
+
+/// An error arising from an invalid odd/even definition.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ParityError {
+    /// A cell lies outside the board.
+    CellOutOfRange(usize, usize),
+
+    /// A cell is marked more than once.
+    DuplicateCell(usize, usize),
+
+    /// A text pattern contains a character other than `o`, `e`, `-` or `.`.
+    InvalidCharacter(char),
+
+    /// A text pattern does not mark exactly 81 cells.  Holds the number of cells found.
+    WrongCellCount(usize),
+}
+
+impl std::fmt::Display for ParityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParityError::CellOutOfRange(x, y) => write!(f, "cell ({}, {}) is off the board", x, y),
+            ParityError::DuplicateCell(x, y) => {
+                write!(f, "cell ({}, {}) is marked more than once", x, y)
+            }
+            ParityError::InvalidCharacter(c) => write!(f, "invalid character `{}`", c),
+            ParityError::WrongCellCount(n) => write!(f, "expected 81 cells, found {}", n),
+        }
+    }
+}
+
+impl std::error::Error for ParityError {}
+
+/// Cells which must hold odd or even digits.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct OddEven {
+    cells: [[Option<Parity>; BOARD_SIZE]; BOARD_SIZE],
+}
+
+impl OddEven {
+    /// Create an `OddEven` constraint from a list of marked cells.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let odd_even = OddEven::new(&[((0, 0), Parity::Odd), ((1, 0), Parity::Even)]).unwrap();
+    /// assert_eq!(odd_even.get(1, 0), Some(Parity::Even));
+    /// assert_eq!(odd_even.get(2, 0), None);
+    ///
+    /// assert_eq!(
+    ///     OddEven::new(&[((0, 0), Parity::Odd), ((0, 0), Parity::Odd)]),
+    ///     Err(ParityError::DuplicateCell(0, 0))
+    /// );
+    /// # }
+    /// ```
+    pub fn new(cells: &[((usize, usize), Parity)]) -> Result<Self, ParityError> {
+        let mut result = Self::default();
+
+        for &((x, y), parity) in cells {
+            if x >= BOARD_SIZE || y >= BOARD_SIZE {
+                return Err(ParityError::CellOutOfRange(x, y));
+            }
+
+            if result.cells[y][x].is_some() {
+                return Err(ParityError::DuplicateCell(x, y));
+            }
+
+            result.cells[y][x] = Some(parity);
+        }
+
+        Ok(result)
+    }
+
+    /// The marking of the cell at column `x` and row `y`, if any.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<Parity> {
+        self.cells[y][x]
+    }
+}
+
+impl Constraint for OddEven {
+    #[inline]
+    fn candidates(&self, _board: &Board, x: usize, y: usize) -> u16 {
+        self.cells[y][x].map_or(ALL_CANDIDATES, Parity::mask)
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        (0..BOARD_SIZE).all(|y| {
+            (0..BOARD_SIZE).all(|x| {
+                let v = board.get_cell(x, y);
+
+                v == 0 || self.cells[y][x].is_none_or(|p| p.matches(v))
+            })
+        })
+    }
+}
+
+impl FromStr for OddEven {
+    type Err = ParityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::default();
+        let mut count = 0;
+
+        for c in s.chars().filter(|c| !c.is_whitespace()) {
+            let parity = match c {
+                'o' | 'O' => Some(Parity::Odd),
+                'e' | 'E' => Some(Parity::Even),
+                '-' | '.' => None,
+                c => return Err(ParityError::InvalidCharacter(c)),
+            };
+
+            if count < BOARD_SIZE * BOARD_SIZE {
+                result.cells[count / BOARD_SIZE][count % BOARD_SIZE] = parity;
+            }
+
+            count += 1;
+        }
+
+        if count != BOARD_SIZE * BOARD_SIZE {
+            return Err(ParityError::WrongCellCount(count));
+        }
+
+        Ok(result)
+    }
+}
+
+impl std::fmt::Display for OddEven {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = String::new();
+
+        for (y, row) in self.cells.iter().enumerate() {
+            if y != 0 {
+                s.push('\n');
+            }
+
+            for (x, cell) in row.iter().enumerate() {
+                if x != 0 {
+                    s.push(' ');
+                }
+
+                s.push(match cell {
+                    Some(Parity::Odd) => 'o',
+                    Some(Parity::Even) => 'e',
+                    None => '-',
+                });
+            }
+        }
+
+        write!(f, "{}", s)
+    }
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+#[test]
+fn marked_cells_are_restricted() {
+    let odd_even = OddEven::new(&[((0, 0), Parity::Odd), ((8, 8), Parity::Even)]).unwrap();
+    let board = Board::default();
+
+    assert_eq!(odd_even.candidates(&board, 0, 0), 0b10_1010_1010); // {1, 3, 5, 7, 9}
+    assert_eq!(odd_even.candidates(&board, 8, 8), 0b01_0101_0100); // {2, 4, 6, 8}
+    assert_eq!(odd_even.candidates(&board, 4, 4), ALL_CANDIDATES);
+}
+
+#[test]
+fn filled_cells_are_checked() {
+    let odd_even = OddEven::new(&[((0, 0), Parity::Odd)]).unwrap();
+    let mut board = Board::default();
+    assert!(odd_even.valid(&board));
+
+    board.set_cell(0, 0, 7);
+    board.set_cell(1, 0, 2);
+    assert!(odd_even.valid(&board));
+
+    board.set_cell(0, 0, 4);
+    assert!(!odd_even.valid(&board));
+}
+
+#[test]
+fn cells_are_checked() {
+    assert_eq!(
+        OddEven::new(&[((9, 0), Parity::Even)]),
+        Err(ParityError::CellOutOfRange(9, 0))
+    );
+}
+
+#[test]
+fn patterns_round_trip_through_text() {
+    let text = "o - - - - - - - e
+- - - - - - - - -
+- - - - - - - - -
+- - - - e - - - -
+- - - - - - - - -
+- - - - - - - - -
+- - - - - - - - -
+- - - - - - - - -
+e - - - - - - - o";
+
+    let odd_even: OddEven = text.parse().unwrap();
+    assert_eq!(odd_even.get(0, 0), Some(Parity::Odd));
+    assert_eq!(odd_even.get(8, 0), Some(Parity::Even));
+    assert_eq!(odd_even.get(4, 3), Some(Parity::Even));
+    assert_eq!(odd_even.get(1, 0), None);
+    assert_eq!(odd_even.to_string(), text);
+
+    // Dots and compact rows are also accepted.
+    let compact: OddEven = text.replace(' ', "").replace('-', ".").parse().unwrap();
+    assert_eq!(compact, odd_even);
+}
+
+#[test]
+fn malformed_patterns_are_rejected() {
+    assert_eq!(
+        "o e".parse::<OddEven>(),
+        Err(ParityError::WrongCellCount(2))
+    );
+    assert_eq!(
+        "x".repeat(81).parse::<OddEven>(),
+        Err(ParityError::InvalidCharacter('x'))
+    );
+}
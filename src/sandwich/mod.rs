@@ -0,0 +1,563 @@
+//! # Sandwich sudoku
+//!
+//! A sandwich sudoku gives a clue outside some rows and columns.  The clue is the sum of the
+//! digits lying between the 1 and the 9 in that row or column.
+//!
+//! The [`Sandwich`] constraint holds the clues.  A [`SandwichPuzzle`] pairs them with the board of
+//! givens, and can be read from and written to a simple text format:
+//!
+//! ```text
+//!    .  .  10 .  .  .  .  .  .
+//! 5  -  -  -  -  -  -  -  -  -
+//! .  -  -  3  -  -  -  -  -  -
+//! ...
+//! ```
+//!
+//! The first line holds the column clues, and each following line holds a row clue and the nine
+//! cells of that row.  A `.` marks a missing clue, and a `-`, `.` or `0` marks an empty cell.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let mut rows = [None; BOARD_SIZE];
+//! rows[0] = Some(0);
+//!
+//! let sandwich = Sandwich::new(rows, [None; BOARD_SIZE]).unwrap();
+//! let solution = SolutionIter::with_rules(&Board::default(), Rules::classic().with(sandwich))
+//!     .next()
+//!     .unwrap();
+//!
+//! // With a clue of zero, the 1 and the 9 must be next to each other.
+//! let one = (0..BOARD_SIZE).find(|&x| solution.get_cell(x, 0) == 1).unwrap();
+//! let nine = (0..BOARD_SIZE).find(|&x| solution.get_cell(x, 0) == 9).unwrap();
+//! assert_eq!(one.abs_diff(nine), 1);
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::str::FromStr;
+
+use super::board::*;
+use super::constraint::*;
+
+/// The largest possible sandwich clue: the sum of the digits 2 to 8.
+pub const MAX_SANDWICH_CLUE: u8 = 35;
+
+/// An error arising from an invalid sandwich clue, or from parsing a sandwich puzzle.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SandwichError {
+    /// A clue is larger than [`MAX_SANDWICH_CLUE`].
+    ClueOutOfRange(u8),
+
+    /// The text does not contain a line of column clues followed by nine rows.
+    WrongLineCount(usize),
+
+    /// A line does not contain the right number of entries.
+    WrongEntryCount {
+        /// The line number, counting from 1.
+        line: usize,
+        /// The number of entries found.
+        count: usize,
+    },
+
+    /// An entry could not be understood.
+    InvalidEntry {
+        /// The line number, counting from 1.
+        line: usize,
+        /// The offending entry.
+        entry: String,
+    },
+}
+
+impl std::fmt::Display for SandwichError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SandwichError::ClueOutOfRange(clue) => {
+                write!(f, "sandwich clue {} is out of range", clue)
+            }
+            SandwichError::WrongLineCount(n) => write!(f, "expected 10 lines, found {}", n),
+            SandwichError::WrongEntryCount { line, count } => {
+                write!(f, "line {}: unexpected number of entries ({})", line, count)
+            }
+            SandwichError::InvalidEntry { line, entry } => {
+                write!(f, "line {}: invalid entry `{}`", line, entry)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SandwichError {}
+
+/// Sandwich clues for the rows and columns of a board.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Sandwich {
+    rows: [Option<u8>; BOARD_SIZE],
+    columns: [Option<u8>; BOARD_SIZE],
+}
+
+/// Mask of the digits which may lie between the 1 and the 9.
+const FILLING: u16 = ALL_CANDIDATES & !(1 << 1) & !(1 << 9);
+
+/// Returns the smallest and largest totals of `count` distinct digits from 2 to 8, excluding
+/// those in `used`, or `None` if there are not enough digits.
+fn filling_range(used: u16, count: usize) -> Option<(usize, usize)> {
+    let available = FILLING & !used;
+
+    if count > available.count_ones() as usize {
+        return None;
+    }
+
+    let digits = (2..BOARD_SIZE).filter(|&d| available & (1 << d) != 0);
+
+    Some((
+        digits.clone().take(count).sum(),
+        digits.rev().take(count).sum(),
+    ))
+}
+
+/// Test whether the digits strictly between positions `a` and `b` of a line can add up to
+/// `clue`.
+fn feasible(line: &[u8; BOARD_SIZE], a: usize, b: usize, clue: u8) -> bool {
+    let (lo, hi) = if a < b { (a, b) } else { (b, a) };
+    let mut used = 0u16;
+    let mut sum = 0;
+    let mut empty = 0;
+
+    for &v in &line[lo + 1..hi] {
+        match v {
+            0 => empty += 1,
+            1 | 9 => return false,
+            v => {
+                if used & (1 << v) != 0 {
+                    return false;
+                }
+
+                used |= 1 << v;
+                sum += v as usize;
+            }
+        }
+    }
+
+    match filling_range(used, empty) {
+        Some((min, max)) => sum + min <= clue as usize && clue as usize <= sum + max,
+        None => false,
+    }
+}
+
+/// Returns a mask of the digits permitted at position `q` of a line with the given clue.
+fn line_candidates(line: &[u8; BOARD_SIZE], q: usize, clue: u8) -> u16 {
+    let one = line.iter().position(|&v| v == 1);
+    let nine = line.iter().position(|&v| v == 9);
+
+    // Test whether the 1 or 9 could go at `q`, with its partner already at `p` or, if `p` is
+    // `None`, in some empty cell.
+    let endpoint = |p: Option<usize>| match p {
+        Some(p) => feasible(line, p, q, clue),
+        None => (0..BOARD_SIZE).any(|r| r != q && line[r] == 0 && feasible(line, q, r, clue)),
+    };
+
+    let mut cs = ALL_CANDIDATES;
+
+    if let (Some(a), Some(b)) = (one, nine) {
+        if a.min(b) < q && q < a.max(b) {
+            let mut trial = *line;
+
+            for d in 1..=BOARD_SIZE {
+                trial[q] = d as u8;
+
+                if !feasible(&trial, a, b, clue) {
+                    cs &= !(1 << d);
+                }
+            }
+        }
+    } else {
+        if one.is_none() && !endpoint(nine) {
+            cs &= !(1 << 1);
+        }
+
+        if nine.is_none() && !endpoint(one) {
+            cs &= !(1 << 9);
+        }
+    }
+
+    cs
+}
+
+/// Test whether a line can still satisfy its clue.
+fn line_valid(line: &[u8; BOARD_SIZE], clue: u8) -> bool {
+    let one = line.iter().position(|&v| v == 1);
+    let nine = line.iter().position(|&v| v == 9);
+
+    match (one, nine) {
+        (Some(a), Some(b)) => feasible(line, a, b, clue),
+        (Some(p), None) | (None, Some(p)) => {
+            (0..BOARD_SIZE).any(|r| line[r] == 0 && feasible(line, p, r, clue))
+        }
+        (None, None) => true,
+    }
+}
+
+impl Sandwich {
+    /// Create a `Sandwich` from the clues for each row (top to bottom) and column (left to right).
+    /// A `None` entry means the line has no clue.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let mut columns = [None; BOARD_SIZE];
+    /// columns[4] = Some(36);
+    ///
+    /// assert_eq!(
+    ///     Sandwich::new([None; BOARD_SIZE], columns),
+    ///     Err(SandwichError::ClueOutOfRange(36))
+    /// );
+    /// # }
+    /// ```
+    pub fn new(
+        rows: [Option<u8>; BOARD_SIZE],
+        columns: [Option<u8>; BOARD_SIZE],
+    ) -> Result<Self, SandwichError> {
+        for clue in rows.iter().chain(columns.iter()).flatten() {
+            if *clue > MAX_SANDWICH_CLUE {
+                return Err(SandwichError::ClueOutOfRange(*clue));
+            }
+        }
+
+        Ok(Self { rows, columns })
+    }
+
+    /// The clues for each row, from top to bottom.
+    #[inline]
+    pub fn rows(&self) -> &[Option<u8>; BOARD_SIZE] {
+        &self.rows
+    }
+
+    /// The clues for each column, from left to right.
+    #[inline]
+    pub fn columns(&self) -> &[Option<u8>; BOARD_SIZE] {
+        &self.columns
+    }
+}
+
+/// Test whether cells with the given candidates, together with digits `used` adding up to `sum`,
+/// can make up the filling of a sandwich with the given clue.
+fn filling_fits(used: u16, sum: usize, open: &[u16], clue: usize) -> bool {
+    let lowest = |m: u16| m.trailing_zeros() as usize;
+    let highest = |m: u16| 15 - m.leading_zeros() as usize;
+
+    let Some((min, max)) = filling_range(used, open.len()) else {
+        return false;
+    };
+
+    let min = min.max(open.iter().map(|&m| lowest(m)).sum());
+    let max = max.min(open.iter().map(|&m| highest(m)).sum());
+
+    sum + min <= clue && clue <= sum + max
+}
+
+/// Narrow down the candidates of a line, given as its cells' masks, by trying every placement of
+/// the 1 and the 9.  Returns the digits each cell may hold under at least one placement.
+fn line_propagate(masks: &[u16; BOARD_SIZE], clue: u8) -> [u16; BOARD_SIZE] {
+    let clue = clue as usize;
+    let mut allowed = [0; BOARD_SIZE];
+
+    for a in (0..BOARD_SIZE).filter(|&a| masks[a] & (1 << 1) != 0) {
+        for b in (0..BOARD_SIZE).filter(|&b| b != a && masks[b] & (1 << 9) != 0) {
+            let between = a.min(b) + 1..a.max(b);
+            let filling: Vec<u16> = masks[between.clone()]
+                .iter()
+                .map(|&m| m & FILLING)
+                .collect();
+
+            if filling.contains(&0) {
+                continue;
+            }
+
+            // Cells down to a single digit count towards the sum; the rest are open.
+            let mut used = 0;
+            let mut sum = 0;
+            let mut open = Vec::with_capacity(filling.len());
+            let mut repeated = false;
+
+            for &m in &filling {
+                if m.count_ones() == 1 {
+                    repeated |= used & m != 0;
+                    used |= m;
+                    sum += m.trailing_zeros() as usize;
+                } else {
+                    open.push(m);
+                }
+            }
+
+            if repeated || !filling_fits(used, sum, &open, clue) {
+                continue;
+            }
+
+            allowed[a] |= 1 << 1;
+            allowed[b] |= 1 << 9;
+
+            for (c, allowed) in allowed.iter_mut().enumerate() {
+                if !between.contains(&c) {
+                    if c != a && c != b {
+                        *allowed |= masks[c] & FILLING;
+                    }
+
+                    continue;
+                }
+
+                let m = filling[c - between.start];
+
+                if m.count_ones() == 1 {
+                    *allowed |= m;
+                    continue;
+                }
+
+                // Try each candidate in turn, leaving the other open cells as they are.
+                let i = open.iter().position(|&o| o == m).unwrap();
+                let mut rest = open.clone();
+                rest.remove(i);
+
+                for d in (2..BOARD_SIZE).filter(|&d| m & (1 << d) != 0 && used & (1 << d) == 0) {
+                    if filling_fits(used | (1 << d), sum + d, &rest, clue) {
+                        *allowed |= 1 << d;
+                    }
+                }
+            }
+        }
+    }
+
+    allowed
+}
+
+fn row(b: &Board, y: usize) -> [u8; BOARD_SIZE] {
+    std::array::from_fn(|x| b.get_cell(x, y))
+}
+
+fn column(b: &Board, x: usize) -> [u8; BOARD_SIZE] {
+    std::array::from_fn(|y| b.get_cell(x, y))
+}
+
+impl Constraint for Sandwich {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        let mut cs = ALL_CANDIDATES;
+
+        if let Some(clue) = self.rows[y] {
+            cs &= line_candidates(&row(board, y), x, clue);
+        }
+
+        if let Some(clue) = self.columns[x] {
+            cs &= line_candidates(&column(board, x), y, clue);
+        }
+
+        cs
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        (0..BOARD_SIZE).all(|i| {
+            self.rows[i].is_none_or(|clue| line_valid(&row(board, i), clue))
+                && self.columns[i].is_none_or(|clue| line_valid(&column(board, i), clue))
+        })
+    }
+
+    fn propagates(&self) -> bool {
+        true
+    }
+
+    fn propagate(&self, board: &Board, masks: &mut CandidateMasks) -> bool {
+        let mut changed = false;
+
+        for (y, clue) in self.rows.iter().enumerate() {
+            if let Some(clue) = *clue {
+                let allowed = line_propagate(&masks[y], clue);
+
+                for (x, &allowed) in allowed.iter().enumerate() {
+                    if board.get_cell(x, y) == 0 && masks[y][x] & !allowed != 0 {
+                        masks[y][x] &= allowed;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        for (i, clue) in self.columns.iter().enumerate() {
+            if let Some(clue) = *clue {
+                let allowed = line_propagate(&std::array::from_fn(|y| masks[y][i]), clue);
+
+                for (y, &allowed) in allowed.iter().enumerate() {
+                    if board.get_cell(i, y) == 0 && masks[y][i] & !allowed != 0 {
+                        masks[y][i] &= allowed;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        changed
+    }
+}
+
+/// A sandwich sudoku: a board of givens, together with the clues outside it.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SandwichPuzzle {
+    /// The givens.
+    pub board: Board,
+
+    /// The clues outside the rows and columns.
+    pub clues: Sandwich,
+}
+
+impl SandwichPuzzle {
+    /// The rules of the puzzle: the classic sudoku constraints plus the sandwich clues.
+    pub fn rules(&self) -> crate::Rules {
+        crate::Rules::classic().with(self.clues.clone())
+    }
+}
+
+fn parse_clue(entry: &str, line: usize) -> Result<Option<u8>, SandwichError> {
+    if entry == "." {
+        return Ok(None);
+    }
+
+    match entry.parse::<u8>() {
+        Ok(clue) if clue <= MAX_SANDWICH_CLUE => Ok(Some(clue)),
+        Ok(clue) => Err(SandwichError::ClueOutOfRange(clue)),
+        Err(_) => Err(SandwichError::InvalidEntry {
+            line,
+            entry: entry.to_string(),
+        }),
+    }
+}
+
+fn parse_cell(entry: &str, line: usize) -> Result<u8, SandwichError> {
+    match entry {
+        "-" | "." | "0" => Ok(0),
+        _ => match entry.parse::<u8>() {
+            Ok(v) if (1..=9).contains(&v) => Ok(v),
+            _ => Err(SandwichError::InvalidEntry {
+                line,
+                entry: entry.to_string(),
+            }),
+        },
+    }
+}
+
+impl FromStr for SandwichPuzzle {
+    type Err = SandwichError;
+
+    /// Parse a sandwich puzzle from the text format described in the [module
+    /// documentation](self).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let puzzle: SandwichPuzzle = "
+    ///        .  .  .  .  .  .  .  .  35
+    ///     0  -  -  -  -  -  -  -  -  -
+    ///     .  -  -  -  -  -  -  -  -  -
+    ///     .  -  -  -  -  -  -  -  -  -
+    ///     .  -  -  -  -  -  -  -  -  -
+    ///     .  -  -  -  -  -  -  -  -  -
+    ///     .  -  -  -  -  -  -  -  -  -
+    ///     .  -  -  -  -  -  -  -  -  -
+    ///     .  -  -  -  -  -  -  -  -  -
+    ///     .  -  -  -  -  -  -  -  -  5
+    /// "
+    /// .parse()
+    /// .unwrap();
+    ///
+    /// assert_eq!(puzzle.clues.rows()[0], Some(0));
+    /// assert_eq!(puzzle.clues.columns()[8], Some(35));
+    /// assert_eq!(puzzle.board.get_cell(8, 8), 5);
+    /// # }
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<(usize, &str)> = s
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !l.is_empty())
+            .collect();
+
+        if lines.len() != BOARD_SIZE + 1 {
+            return Err(SandwichError::WrongLineCount(lines.len()));
+        }
+
+        let mut columns = [None; BOARD_SIZE];
+        let mut rows = [None; BOARD_SIZE];
+        let mut board = Board::default();
+
+        let (line, header) = lines[0];
+        let entries: Vec<&str> = header.split_whitespace().collect();
+
+        if entries.len() != BOARD_SIZE {
+            return Err(SandwichError::WrongEntryCount {
+                line,
+                count: entries.len(),
+            });
+        }
+
+        for (x, entry) in entries.iter().enumerate() {
+            columns[x] = parse_clue(entry, line)?;
+        }
+
+        for (y, &(line, text)) in lines[1..].iter().enumerate() {
+            let entries: Vec<&str> = text.split_whitespace().collect();
+
+            if entries.len() != BOARD_SIZE + 1 {
+                return Err(SandwichError::WrongEntryCount {
+                    line,
+                    count: entries.len(),
+                });
+            }
+
+            rows[y] = parse_clue(entries[0], line)?;
+
+            for (x, entry) in entries[1..].iter().enumerate() {
+                board.set_cell(x, y, parse_cell(entry, line)?);
+            }
+        }
+
+        Ok(Self {
+            board,
+            clues: Sandwich::new(rows, columns)?,
+        })
+    }
+}
+
+fn clue_to_string(clue: Option<u8>) -> String {
+    clue.map_or_else(|| ".".to_string(), |c| c.to_string())
+}
+
+impl std::fmt::Display for SandwichPuzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = String::from("  ");
+
+        for clue in self.clues.columns() {
+            s.push_str(&format!(" {:<2}", clue_to_string(*clue)));
+        }
+
+        for y in 0..BOARD_SIZE {
+            s.push('\n');
+            s.push_str(&format!("{:<2}", clue_to_string(self.clues.rows()[y])));
+
+            for x in 0..BOARD_SIZE {
+                s.push_str(&format!(" {:<2}", self.board.cell_char(x, y, '-')));
+            }
+        }
+
+        write!(
+            f,
+            "{}",
+            s.lines().map(str::trim_end).collect::<Vec<_>>().join("\n")
+        )
+    }
+}
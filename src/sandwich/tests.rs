@@ -0,0 +1,152 @@
+use super::*;
+
+fn clues(row: usize, clue: u8) -> Sandwich {
+    let mut rows = [None; BOARD_SIZE];
+    rows[row] = Some(clue);
+
+    Sandwich::new(rows, [None; BOARD_SIZE]).unwrap()
+}
+
+#[test]
+fn clues_are_checked() {
+    assert!(Sandwich::new([Some(35); BOARD_SIZE], [Some(0); BOARD_SIZE]).is_ok());
+    assert_eq!(
+        Sandwich::new([Some(36); BOARD_SIZE], [None; BOARD_SIZE]),
+        Err(SandwichError::ClueOutOfRange(36))
+    );
+}
+
+#[test]
+fn candidates_between_the_crusts_are_pruned() {
+    let sandwich = clues(0, 5);
+    let mut board = Board::default();
+    board.set_cell(0, 0, 1);
+    board.set_cell(3, 0, 9);
+
+    // Two cells adding up to 5: {2, 3}.
+    assert_eq!(sandwich.candidates(&board, 1, 0), 0b00_0000_1100);
+    assert_eq!(sandwich.candidates(&board, 4, 0), ALL_CANDIDATES);
+
+    board.set_cell(1, 0, 3);
+    assert_eq!(sandwich.candidates(&board, 2, 0), 0b00_0000_0100);
+}
+
+#[test]
+fn crusts_are_placed_at_feasible_distances() {
+    let sandwich = clues(0, 35);
+    let mut board = Board::default();
+
+    // The 1 and 9 must be at either end of the row.
+    assert_eq!(sandwich.candidates(&board, 0, 0), ALL_CANDIDATES);
+    assert_eq!(sandwich.candidates(&board, 4, 0), 0b01_1111_1100);
+
+    board.set_cell(8, 0, 9);
+    assert_eq!(sandwich.candidates(&board, 0, 0), ALL_CANDIDATES);
+    assert_eq!(sandwich.candidates(&board, 1, 0), 0b11_1111_1100);
+}
+
+#[test]
+fn invalid_boards_are_rejected() {
+    let sandwich = clues(0, 0);
+    let mut board = Board::default();
+    board.set_cell(0, 0, 1);
+    assert!(sandwich.valid(&board));
+
+    board.set_cell(2, 0, 9);
+    assert!(!sandwich.valid(&board));
+
+    // Without a clue, anything goes.
+    assert!(Sandwich::default().valid(&board));
+}
+
+#[test]
+fn propagation_places_the_crusts() {
+    let board = Board::default();
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+
+    assert!(clues(0, 35).propagate(&board, &mut masks));
+    assert_eq!(masks[0][0], 0b10_0000_0010);
+    assert_eq!(masks[0][4], 0b01_1111_1100);
+    assert_eq!(masks[0][8], 0b10_0000_0010);
+    assert_eq!(masks[1][4], ALL_CANDIDATES);
+
+    // With the row's 2 fixed in the middle, the 1 and 9 must surround it.
+    let mut masks = [[ALL_CANDIDATES; BOARD_SIZE]; BOARD_SIZE];
+    masks[0] = [ALL_CANDIDATES & !(1 << 2); BOARD_SIZE];
+    masks[0][4] = 1 << 2;
+
+    assert!(clues(0, 2).propagate(&board, &mut masks));
+    assert_eq!(masks[0][3], 0b10_0000_0010);
+    assert_eq!(masks[0][5], 0b10_0000_0010);
+    assert_eq!(masks[0][0], 0b01_1111_1000);
+}
+
+#[test]
+fn puzzles_round_trip_through_text() {
+    let text = "   .  .  10 .  .  .  .  .  .
+5  1  -  -  -  -  -  -  -  -
+.  -  -  -  -  -  -  -  -  -
+.  -  -  -  -  -  -  -  -  -
+.  -  -  -  -  -  -  -  -  -
+.  -  -  -  -  -  -  -  -  -
+.  -  -  -  -  -  -  -  -  -
+.  -  -  -  -  -  -  -  -  -
+.  -  -  -  -  -  -  -  -  -
+35 -  -  -  -  -  -  -  -  9";
+
+    let puzzle: SandwichPuzzle = text.parse().unwrap();
+    assert_eq!(puzzle.clues.rows()[0], Some(5));
+    assert_eq!(puzzle.clues.rows()[8], Some(35));
+    assert_eq!(puzzle.clues.columns()[2], Some(10));
+    assert_eq!(puzzle.board.get_cell(0, 0), 1);
+    assert_eq!(puzzle.board.get_cell(8, 8), 9);
+    assert_eq!(puzzle.to_string(), text);
+}
+
+#[test]
+fn out_of_range_values_are_shown_as_question_marks() {
+    let mut board = Board::default();
+    board.set_cell(0, 0, 12);
+
+    let puzzle = SandwichPuzzle {
+        board,
+        clues: clues(0, 5),
+    };
+    let text = puzzle.to_string();
+
+    assert_eq!(text.lines().nth(1), Some("5  ?  -  -  -  -  -  -  -  -"));
+}
+
+#[test]
+fn malformed_puzzles_are_rejected() {
+    assert_eq!(
+        "".parse::<SandwichPuzzle>(),
+        Err(SandwichError::WrongLineCount(0))
+    );
+
+    let mut lines = vec![". . . . . . . . ."];
+    lines.extend([". - - - - - - - - -"; BOARD_SIZE]);
+    assert!(lines.join("\n").parse::<SandwichPuzzle>().is_ok());
+
+    lines[0] = ". . . . . . . .";
+    assert_eq!(
+        lines.join("\n").parse::<SandwichPuzzle>(),
+        Err(SandwichError::WrongEntryCount { line: 1, count: 8 })
+    );
+
+    lines[0] = ". . . . . . . . 40";
+    assert_eq!(
+        lines.join("\n").parse::<SandwichPuzzle>(),
+        Err(SandwichError::ClueOutOfRange(40))
+    );
+
+    lines[0] = ". . . . . . . . .";
+    lines[3] = ". - - x - - - - - -";
+    assert_eq!(
+        lines.join("\n").parse::<SandwichPuzzle>(),
+        Err(SandwichError::InvalidEntry {
+            line: 4,
+            entry: "x".to_string()
+        })
+    );
+}
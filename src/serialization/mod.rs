@@ -0,0 +1,392 @@
+//! # Serialization
+//!
+//! With the `serde` feature enabled, boards, candidate grids and the definitions of the variant
+//! constraints implement `Serialize` and `Deserialize`.
+//!
+//! A [`Board`] is written as a nine-by-nine array of digits, with zeroes for unfilled cells.  In
+//! human-readable formats such as JSON it can be read either from such an array or from a string
+//! of 81 cells in any form accepted by its [`FromStr`](std::str::FromStr) implementation, while
+//! compact binary formats, which do not describe their own contents, accept only the form that
+//! was written.  Fields which should be written as a string instead can use the
+//! [`board_as_string`] module with `#[serde(with = ...)]`.
+//!
+//! A [`CandidateGrid`] is written in the 729-character Sukaku format and an [`OddEven`] in its
+//! text layout.  Other constraints are written as the arguments of their constructors, which
+//! are used to check them when they are read back.  [`Rules`] cannot be serialized, since it may
+//! hold any [`Constraint`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Puzzle {
+//!     #[serde(with = "sudoku_solver::serialization::board_as_string")]
+//!     givens: Board,
+//!     cages: Cages,
+//! }
+//!
+//! let mut givens = Board::default();
+//! givens.set_cell(0, 0, 1);
+//!
+//! let puzzle = Puzzle {
+//!     givens,
+//!     cages: Cages::new(vec![Cage::new(&[(1, 0), (2, 0)], 5).unwrap()]).unwrap(),
+//! };
+//!
+//! let json = serde_json::to_string(&puzzle).unwrap();
+//! assert_eq!(
+//!     json,
+//!     format!(
+//!         r#"{{"givens":"1{}","cages":[{{"cells":[[1,0],[2,0]],"sum":5}}]}}"#,
+//!         ".".repeat(80)
+//!     )
+//! );
+//!
+//! let puzzle: Puzzle = serde_json::from_str(&json).unwrap();
+//! assert_eq!(puzzle.givens, givens);
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::arrow::*;
+use super::board::*;
+use super::candidates::*;
+use super::comparison::*;
+use super::constraint::*;
+use super::killer::*;
+use super::lines::*;
+use super::little_killer::*;
+use super::markers::*;
+use super::multigrid::*;
+use super::parity::*;
+use super::sandwich::*;
+use super::thermo::*;
+
+/// Implement `Serialize` and `Deserialize` for `$ty` by way of the plain type `$def`.  A value is
+/// converted to `$def` by `$to`, and back again by `$from`, which returns a `Result` whose error
+/// is reported through the deserializer.
+macro_rules! serde_via {
+    ($ty:ty, $def:ty, |$v:ident| $to:expr, |$d:ident| $from:expr) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let $v = self;
+                let def: $def = $to;
+                def.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let $d = <$def>::deserialize(deserializer)?;
+                $from.map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+/// Serialize and deserialize a [`Board`] as a string of 81 cells, with `.` for unfilled cells.
+///
+/// For use with `#[serde(with = "sudoku_solver::serialization::board_as_string")]`.  Either form
+/// of board is accepted when deserializing from a human-readable format.
+pub mod board_as_string {
+    use super::*;
+
+    /// Write a board as a string of 81 cells.
+    pub fn serialize<S: Serializer>(board: &Board, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&board.display(BoardStyle::Line))
+    }
+
+    /// Read a board from a string of 81 cells or a nine-by-nine array.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+        if deserializer.is_human_readable() {
+            Board::deserialize(deserializer)
+        } else {
+            deserializer.deserialize_str(BoardVisitor)
+        }
+    }
+}
+
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut rows = [[0; BOARD_SIZE]; BOARD_SIZE];
+
+        for (y, row) in rows.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                *cell = self.get_cell(x, y);
+            }
+        }
+
+        rows.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(BoardVisitor)
+        } else {
+            deserializer.deserialize_tuple(BOARD_SIZE, BoardVisitor)
+        }
+    }
+}
+
+/// Reads a [`Board`] from either a string or a nine-by-nine array.
+struct BoardVisitor;
+
+impl<'de> Visitor<'de> for BoardVisitor {
+    type Value = Board;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "a string of 81 cells or a 9x9 array of digits")
+    }
+
+    fn visit_str<E: Error>(self, s: &str) -> Result<Board, E> {
+        s.parse().map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Board, A::Error> {
+        let mut board = Board::default();
+
+        for y in 0..BOARD_SIZE {
+            let row: [u8; BOARD_SIZE] = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(y, &self))?;
+
+            for (x, &v) in row.iter().enumerate() {
+                if v as usize > BOARD_SIZE {
+                    return Err(A::Error::custom(format!("invalid digit {}", v)));
+                }
+
+                board.set_cell(x, y, v);
+            }
+        }
+
+        if seq.next_element::<[u8; BOARD_SIZE]>()?.is_some() {
+            return Err(A::Error::invalid_length(BOARD_SIZE + 1, &self));
+        }
+
+        Ok(board)
+    }
+}
+
+serde_via!(CandidateGrid, String, |grid| grid.to_string(), |s| {
+    s.parse::<CandidateGrid>()
+});
+
+serde_via!(OddEven, String, |odd_even| odd_even.to_string(), |s| {
+    s.parse::<OddEven>()
+});
+
+#[derive(Serialize, Deserialize)]
+struct CellsDef {
+    cells: Vec<(usize, usize)>,
+}
+
+serde_via!(
+    House,
+    CellsDef,
+    |house| CellsDef {
+        cells: house.cells().to_vec()
+    },
+    |def| House::new(&def.cells)
+);
+
+serde_via!(
+    Thermometer,
+    CellsDef,
+    |thermometer| CellsDef {
+        cells: thermometer.cells().to_vec()
+    },
+    |def| Thermometer::new(&def.cells)
+);
+
+serde_via!(
+    Whisper,
+    CellsDef,
+    |whisper| CellsDef {
+        cells: whisper.cells().to_vec()
+    },
+    |def| Whisper::new(&def.cells)
+);
+
+serde_via!(
+    Renban,
+    CellsDef,
+    |renban| CellsDef {
+        cells: renban.cells().to_vec()
+    },
+    |def| Renban::new(&def.cells)
+);
+
+serde_via!(
+    Palindrome,
+    CellsDef,
+    |palindrome| CellsDef {
+        cells: palindrome.cells().to_vec()
+    },
+    |def| Palindrome::new(&def.cells)
+);
+
+#[derive(Serialize, Deserialize)]
+struct ArrowDef {
+    circle: Vec<(usize, usize)>,
+    shaft: Vec<(usize, usize)>,
+}
+
+serde_via!(
+    Arrow,
+    ArrowDef,
+    |arrow| ArrowDef {
+        circle: arrow.circle().to_vec(),
+        shaft: arrow.shaft().to_vec(),
+    },
+    |def| Arrow::new(&def.circle, &def.shaft)
+);
+
+#[derive(Serialize, Deserialize)]
+struct CageDef {
+    cells: Vec<(usize, usize)>,
+    sum: u8,
+}
+
+serde_via!(
+    Cage,
+    CageDef,
+    |cage| CageDef {
+        cells: cage.cells().to_vec(),
+        sum: cage.sum(),
+    },
+    |def| Cage::new(&def.cells, def.sum)
+);
+
+serde_via!(
+    Arrows,
+    Vec<Arrow>,
+    |arrows| arrows.arrows().to_vec(),
+    |arrows| Ok::<_, ArrowError>(Arrows::new(arrows))
+);
+
+serde_via!(Cages, Vec<Cage>, |cages| cages.cages().to_vec(), |cages| {
+    Cages::new(cages)
+});
+
+#[derive(Serialize, Deserialize)]
+struct SandwichDef {
+    rows: [Option<u8>; BOARD_SIZE],
+    columns: [Option<u8>; BOARD_SIZE],
+}
+
+serde_via!(
+    Sandwich,
+    SandwichDef,
+    |sandwich| SandwichDef {
+        rows: *sandwich.rows(),
+        columns: *sandwich.columns(),
+    },
+    |def| Sandwich::new(def.rows, def.columns)
+);
+
+serde_via!(
+    Inequalities,
+    Vec<Sign>,
+    |inequalities| inequalities.signs().to_vec(),
+    |signs| Inequalities::new(&signs)
+);
+
+#[derive(Serialize, Deserialize)]
+struct LittleKillerDef {
+    start: (usize, usize),
+    direction: Direction,
+    sum: u8,
+}
+
+serde_via!(
+    LittleKiller,
+    LittleKillerDef,
+    |clue| LittleKillerDef {
+        start: clue.start(),
+        direction: clue.direction(),
+        sum: clue.sum(),
+    },
+    |def| LittleKiller::new(def.start, def.direction, def.sum)
+);
+
+serde_via!(
+    LittleKillers,
+    Vec<LittleKiller>,
+    |clues| clues.clues().to_vec(),
+    |clues| Ok::<_, LittleKillerError>(LittleKillers::new(clues))
+);
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound(serialize = "R: Serialize", deserialize = "R: Deserialize<'de>"))]
+struct PairMarkersDef<R> {
+    markers: Vec<Marker<R>>,
+    #[serde(default)]
+    negative: bool,
+}
+
+impl<R: Relation + Serialize> Serialize for PairMarkers<R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PairMarkersDef {
+            markers: self.markers().to_vec(),
+            negative: self.is_negative(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, R: Relation + Deserialize<'de>> Deserialize<'de> for PairMarkers<R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let def = PairMarkersDef::<R>::deserialize(deserializer)?;
+        let markers = PairMarkers::new(&def.markers).map_err(D::Error::custom)?;
+
+        Ok(if def.negative {
+            markers.with_negative_constraint()
+        } else {
+            markers
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct MultiBoardDef {
+    offsets: Vec<(usize, usize)>,
+    grids: Vec<Board>,
+}
+
+serde_via!(
+    MultiBoard,
+    MultiBoardDef,
+    |board| MultiBoardDef {
+        offsets: board.offsets().to_vec(),
+        grids: board.grids().to_vec(),
+    },
+    |def| multi_board(&def)
+);
+
+/// Rebuild a [`MultiBoard`] from its offsets and the contents of each grid.
+fn multi_board(def: &MultiBoardDef) -> Result<MultiBoard, String> {
+    if def.grids.len() != def.offsets.len() {
+        return Err(format!(
+            "expected {} grids, found {}",
+            def.offsets.len(),
+            def.grids.len()
+        ));
+    }
+
+    let mut board = MultiBoard::new(&def.offsets).map_err(|e| e.to_string())?;
+
+    for (i, grid) in def.grids.iter().enumerate() {
+        board.set_grid(i, grid).map_err(|e| e.to_string())?;
+    }
+
+    Ok(board)
+}
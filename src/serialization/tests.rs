@@ -0,0 +1,220 @@
+use super::*;
+use crate::booklet::*;
+use crate::formats::*;
+use crate::hodoku::*;
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+}
+
+#[test]
+fn board_as_array() {
+    let mut board = Board::default();
+    board.set_cell(1, 0, 7);
+
+    let json = serde_json::to_string(&board).unwrap();
+    assert!(json.starts_with("[[0,7,0,0,0,0,0,0,0],[0,0,0,0,0,0,0,0,0],"));
+    assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+}
+
+#[test]
+fn board_from_string() {
+    let mut board = Board::default();
+    board.set_cell(8, 8, 3);
+
+    let json = format!("\"{}3\"", "0".repeat(80));
+    assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Wrapper {
+        #[serde(with = "board_as_string")]
+        board: Board,
+    }
+
+    let wrapper = Wrapper { board };
+    let json = serde_json::to_string(&wrapper).unwrap();
+    assert_eq!(json, format!(r#"{{"board":"{}3"}}"#, ".".repeat(80)));
+    assert_eq!(round_trip(&wrapper), wrapper);
+}
+
+#[test]
+fn board_in_binary_format() {
+    let mut board = Board::default();
+    board.set_cell(0, 0, 5);
+    board.set_cell(8, 8, 9);
+
+    let bytes = bincode::serialize(&board).unwrap();
+    assert_eq!(bytes.len(), BOARD_SIZE * BOARD_SIZE);
+    assert_eq!(bincode::deserialize::<Board>(&bytes).unwrap(), board);
+
+    let mut rows = [[0u8; BOARD_SIZE]; BOARD_SIZE];
+    rows[4][4] = 10;
+    let bytes = bincode::serialize(&rows).unwrap();
+    assert!(bincode::deserialize::<Board>(&bytes).is_err());
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Wrapper {
+        #[serde(with = "board_as_string")]
+        board: Board,
+        cages: Cages,
+    }
+
+    let wrapper = Wrapper {
+        board,
+        cages: Cages::new(vec![Cage::new(&[(1, 0), (2, 0)], 5).unwrap()]).unwrap(),
+    };
+    let bytes = bincode::serialize(&wrapper).unwrap();
+    assert_eq!(bincode::deserialize::<Wrapper>(&bytes).unwrap(), wrapper);
+}
+
+#[test]
+fn reject_bad_boards() {
+    assert!(serde_json::from_str::<Board>("\"123\"").is_err());
+    assert!(serde_json::from_str::<Board>("[[0,0,0,0,0,0,0,0,0]]").is_err());
+
+    let mut rows = vec![[0u8; BOARD_SIZE]; BOARD_SIZE];
+    rows[4][4] = 10;
+    let json = serde_json::to_string(&rows).unwrap();
+    assert!(serde_json::from_str::<Board>(&json).is_err());
+
+    let rows = vec![[0u8; BOARD_SIZE]; BOARD_SIZE + 1];
+    let json = serde_json::to_string(&rows).unwrap();
+    assert!(serde_json::from_str::<Board>(&json).is_err());
+}
+
+#[test]
+fn solutions_round_trip() {
+    let solutions: Vec<Board> = crate::SolutionIter::new(&Board::default())
+        .take(3)
+        .collect();
+
+    assert_eq!(round_trip(&solutions), solutions);
+    assert_eq!(round_trip(&Some(solutions[0])), Some(solutions[0]));
+}
+
+#[test]
+fn candidate_grid_as_string() {
+    let mut grid = CandidateGrid::default();
+    grid.set_candidates(0, 0, 1 << 4);
+
+    let json = serde_json::to_string(&grid).unwrap();
+    assert_eq!(json, format!("\"...4.....{}\"", "123456789".repeat(80)));
+    assert_eq!(round_trip(&grid), grid);
+}
+
+#[test]
+fn constraints_round_trip() {
+    let cages = Cages::new(vec![
+        Cage::new(&[(0, 0), (1, 0)], 3).unwrap(),
+        Cage::new(&[(0, 1), (1, 1), (2, 1)], 24).unwrap(),
+    ])
+    .unwrap();
+    assert_eq!(round_trip(&cages), cages);
+
+    let house = House::new(&[(0, 0), (4, 4), (8, 8)]).unwrap();
+    assert_eq!(round_trip(&house), house);
+
+    let thermometer = Thermometer::new(&[(0, 0), (1, 0), (2, 0)]).unwrap();
+    assert_eq!(round_trip(&thermometer), thermometer);
+
+    let arrow = Arrow::new(&[(0, 0)], &[(1, 1), (2, 2)]).unwrap();
+    assert_eq!(round_trip(&arrow), arrow);
+
+    let arrows = Arrows::new(vec![arrow.clone()]);
+    assert_eq!(round_trip(&arrows), arrows);
+
+    let whisper = Whisper::new(&[(0, 0), (1, 1)]).unwrap();
+    assert_eq!(round_trip(&whisper), whisper);
+
+    let renban = Renban::new(&[(0, 0), (1, 1)]).unwrap();
+    assert_eq!(round_trip(&renban), renban);
+
+    let palindrome = Palindrome::new(&[(0, 0), (1, 1), (2, 2)]).unwrap();
+    assert_eq!(round_trip(&palindrome), palindrome);
+
+    let sandwich = Sandwich::new([Some(10); BOARD_SIZE], [None; BOARD_SIZE]).unwrap();
+    assert_eq!(round_trip(&sandwich), sandwich);
+
+    let inequalities = Inequalities::new(&[((0, 0), (1, 0)), ((1, 1), (1, 0))]).unwrap();
+    assert_eq!(round_trip(&inequalities), inequalities);
+
+    let little_killers =
+        LittleKillers::new(vec![
+            LittleKiller::new((6, 0), Direction::DownRight, 24).unwrap()
+        ]);
+    assert_eq!(round_trip(&little_killers), little_killers);
+
+    let kropki = Kropki::new(&[((0, 0), (1, 0), KropkiDot::Black)])
+        .unwrap()
+        .with_negative_constraint();
+    assert_eq!(round_trip(&kropki), kropki);
+
+    let xv = XV::new(&[((0, 0), (0, 1), XVSign::V)]).unwrap();
+    assert_eq!(round_trip(&xv), xv);
+
+    let odd_even = OddEven::new(&[((3, 3), Parity::Even)]).unwrap();
+    assert_eq!(round_trip(&odd_even), odd_even);
+
+    assert_eq!(round_trip(&Diagonals), Diagonals);
+}
+
+#[test]
+fn constraints_are_checked() {
+    assert!(serde_json::from_str::<Cage>(r#"{"cells":[[0,0],[1,0]],"sum":18}"#).is_err());
+    assert!(serde_json::from_str::<Cages>(
+        r#"[{"cells":[[0,0],[1,0]],"sum":3},{"cells":[[1,0],[2,0]],"sum":3}]"#
+    )
+    .is_err());
+    assert!(serde_json::from_str::<Whisper>(r#"{"cells":[[0,0],[2,2]]}"#).is_err());
+    assert!(serde_json::from_str::<Kropki>(r#"{"markers":[[[0,0],[1,1],"White"]]}"#).is_err());
+    assert!(serde_json::from_str::<Kropki>(r#"{"markers":[[[0,0],[1,0],"White"]]}"#).is_ok());
+}
+
+#[test]
+fn multi_board_round_trip() {
+    let mut board = MultiBoard::samurai();
+    board.set_cell(6, 6, 5);
+    assert_eq!(round_trip(&board), board);
+
+    let mut json: serde_json::Value = serde_json::to_value(&board).unwrap();
+    json["grids"].as_array_mut().unwrap().pop();
+    assert!(serde_json::from_value::<MultiBoard>(json).is_err());
+}
+
+#[test]
+fn puzzle_records_round_trip() {
+    let mut givens = Board::default();
+    givens.set_cell(0, 0, 5);
+    let mut placed = Board::default();
+    placed.set_cell(1, 0, 3);
+
+    let sadman = SadManPuzzle { givens, placed };
+    assert_eq!(round_trip(&sadman), sadman);
+
+    let entry = LibraryEntry {
+        technique: "0000".to_string(),
+        givens,
+        placements: vec![((1, 0), 3)],
+        rating: Some(140),
+        ..Default::default()
+    };
+    assert_eq!(round_trip(&entry), entry);
+
+    let step = Step {
+        technique: "Hidden Single".to_string(),
+        eliminations: vec![((6, 5), 5)],
+        ..Default::default()
+    };
+    assert_eq!(round_trip(&step), step);
+
+    let booklet_entry = BookletEntry {
+        title: "Example".to_string(),
+        difficulty: Some("Easy".to_string()),
+        puzzle: givens,
+        solution: None,
+    };
+    assert_eq!(round_trip(&booklet_entry), booklet_entry);
+}
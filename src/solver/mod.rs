@@ -6,6 +6,8 @@ mod tests;
 use std::iter::FusedIterator;
 
 use super::board::*;
+use super::candidates::*;
+use super::constraint::*;
 use bit_iter::BitIter;
 
 /// Test whether a sudoku board state obeys the contraints of the game.
@@ -32,90 +34,77 @@ use bit_iter::BitIter;
 /// # }
 /// ```
 pub fn valid(b: &Board) -> bool {
-    const PRECALC_MASKS: [u64; BOARD_SIZE + 1] = [
-        0x00_0000_0001,
-        0x00_0000_0010,
-        0x00_0000_0100,
-        0x00_0000_1000,
-        0x00_0001_0000,
-        0x00_0010_0000,
-        0x00_0100_0000,
-        0x00_1000_0000,
-        0x01_0000_0000,
-        0x10_0000_0000,
-    ];
-
-    for y in 0..BOARD_SIZE {
-        for x in 0..BOARD_SIZE {
-            if b.get_cell(x, y) > 9 {
-                return false;
-            }
-        }
-    }
-
-    // Check rows.
-    for y in 0..BOARD_SIZE {
-        let mut acc = 0;
-
-        for x in 0..BOARD_SIZE {
-            acc += PRECALC_MASKS[b.get_cell(x, y) as usize];
-        }
+    valid_with_rules(b, &Rules::classic())
+}
 
-        if (acc & 0xee_eeee_eee0) != 0 {
-            return false;
-        }
-    }
+/// Test whether a sudoku board state obeys the given [`Rules`].
+///
+/// ## Example
+///
+/// A board with a duplicate on the leading diagonal, which is only invalid for X-sudoku:
+///
+/// ```rust
+/// # fn main() {
+/// # use sudoku_solver::*;
+/// let mut board = Board::from(&[[0u8; BOARD_SIZE]; BOARD_SIZE]);
+/// board.set_cell(0, 0, 9);
+/// board.set_cell(8, 8, 9);
+///
+/// let x_sudoku = Rules::classic().with(Diagonals);
+///
+/// assert!(valid(&board));
+/// assert!(!valid_with_rules(&board, &x_sudoku));
+/// # }
+/// ```
+pub fn valid_with_rules(b: &Board, rules: &Rules) -> bool {
+    rules.valid(b)
+}
 
-    // Check columns.
-    for x in 0..BOARD_SIZE {
-        let mut acc = 0;
+fn cell_with_fewest_propagated_candidates(b: &Board, rules: &Rules) -> Option<(usize, usize, u16)> {
+    let mut masks = [[0; BOARD_SIZE]; BOARD_SIZE];
 
-        for y in 0..BOARD_SIZE {
-            acc += PRECALC_MASKS[b.get_cell(x, y) as usize];
-        }
+    for (y, row) in masks.iter_mut().enumerate() {
+        for (x, mask) in row.iter_mut().enumerate() {
+            *mask = match b.get_cell_as_mask(x, y) {
+                1 => rules.candidates(b, x, y),
+                value => value,
+            };
 
-        if (acc & 0xee_eeee_eee0) != 0 {
-            return false;
+            if *mask == 0 {
+                // No valid choices for this empty cell, so we need to backtrack.
+                return None;
+            }
         }
     }
 
-    // Check squares.
-    for square in 0..BOARD_SIZE {
-        let mut acc = 0;
+    rules.propagate(b, &mut masks);
 
-        let x = SQUARE_SIZE * (square % SQUARE_SIZE);
-        let y = SQUARE_SIZE * (square / SQUARE_SIZE);
+    let mut min = (0, 0, 0);
+    let mut min_count = BOARD_SIZE + 1;
 
-        for i in 0..BOARD_SIZE {
-            acc += PRECALC_MASKS[b.get_cell(x + (i % 3), y + (i / 3)) as usize];
-        }
+    for (y, row) in masks.iter().enumerate() {
+        for (x, &mask) in row.iter().enumerate() {
+            if b.get_cell_as_mask(x, y) == 1 {
+                let count = mask.count_ones() as usize;
 
-        if (acc & 0xee_eeee_eee0) != 0 {
-            return false;
+                if count == 0 {
+                    return None;
+                } else if count < min_count {
+                    min = (x, y, mask);
+                    min_count = count;
+                }
+            }
         }
     }
 
-    true
+    Some(min)
 }
 
-fn valid_choices_for_cell(b: &Board, x: usize, y: usize) -> u16 {
-    let mut cs = 0b00_0000_0001;
-
-    let xs = SQUARE_SIZE * (x / SQUARE_SIZE);
-    let ys = SQUARE_SIZE * (y / SQUARE_SIZE);
-
-    // Generate a mask of already-used values.
-    for i in 0..BOARD_SIZE {
-        cs |= b.get_cell_as_mask(x, i);
-        cs |= b.get_cell_as_mask(i, y);
-        cs |= b.get_cell_as_mask(xs + (i % 3), ys + (i / 3));
+fn cell_with_fewest_candidates(b: &Board, rules: &Rules) -> Option<(usize, usize, u16)> {
+    if rules.propagates() {
+        return cell_with_fewest_propagated_candidates(b, rules);
     }
 
-    // Invert the mask to indicate which choices are still available.
-    cs ^ 0b11_1111_1111u16
-}
-
-fn cell_with_fewest_candidates(b: &Board) -> Option<(usize, usize, u16)> {
     let mut min_x = 0;
     let mut min_y = 0;
     let mut min_candidates = 0;
@@ -125,7 +114,7 @@ fn cell_with_fewest_candidates(b: &Board) -> Option<(usize, usize, u16)> {
     for y in 0..BOARD_SIZE {
         for x in 0..BOARD_SIZE {
             if b.get_cell_as_mask(x, y) == 1 {
-                let cs = valid_choices_for_cell(b, x, y);
+                let cs = rules.candidates(b, x, y);
 
                 if cs == 0 {
                     // No valid choices for this empty cell, so we need to backtrack.
@@ -233,6 +222,7 @@ pub fn solve(b: &Board) -> Option<Board> {
 pub struct SolutionIter {
     first: bool,
     board: Board,
+    rules: Rules,
     stack: Vec<(usize, usize, BitIter<u16>)>,
 }
 
@@ -262,12 +252,69 @@ impl SolutionIter {
     /// # }
     /// ```
     pub fn new(board: &Board) -> Self {
+        Self::with_rules(board, Rules::default())
+    }
+
+    /// Construct a `SolutionIter` value from a [`Board`], using the given [`Rules`] in place of the
+    /// classic sudoku constraints.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let board = Board::from(&[
+    ///     [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 1
+    ///     [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 2
+    ///     [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+    ///     [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 4
+    ///     [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 5
+    ///     [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+    ///     [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 7
+    ///     [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 8
+    ///     [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 9
+    /// ]);
+    ///
+    /// let x_sudoku = Rules::classic().with(Diagonals);
+    ///
+    /// let solution = SolutionIter::with_rules(&board, x_sudoku.clone()).next().unwrap();
+    ///
+    /// assert!(valid_with_rules(&solution, &x_sudoku));
+    /// # }
+    /// ```
+    pub fn with_rules(board: &Board, rules: Rules) -> Self {
         Self {
             first: true,
             board: *board,
+            rules,
             stack: Vec::with_capacity(BOARD_SIZE * BOARD_SIZE),
         }
     }
+
+    /// Construct a `SolutionIter` value for a Sukaku puzzle, in which each cell may only take one
+    /// of the candidates given by a [`CandidateGrid`].  The classic sudoku constraints apply.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// let mut grid = CandidateGrid::default();
+    ///
+    /// for x in 0..BOARD_SIZE {
+    ///     grid.set_candidates(x, 0, 1 << (x + 1));
+    /// }
+    ///
+    /// let solution = SolutionIter::from_candidates(&grid).next().unwrap();
+    ///
+    /// for x in 0..BOARD_SIZE {
+    ///     assert_eq!(solution.get_cell(x, 0), x as u8 + 1);
+    /// }
+    /// # }
+    /// ```
+    pub fn from_candidates(grid: &CandidateGrid) -> Self {
+        Self::with_rules(&Board::default(), Rules::classic().with(*grid))
+    }
 }
 
 /// `From` implementation for `SolutionIter`.
@@ -285,8 +332,9 @@ impl Iterator for SolutionIter {
         if self.first {
             self.first = false;
 
-            if valid(&self.board) {
-                if let Some((x, y, values)) = cell_with_fewest_candidates(&self.board) {
+            if self.rules.valid(&self.board) {
+                if let Some((x, y, values)) = cell_with_fewest_candidates(&self.board, &self.rules)
+                {
                     if values == 0 {
                         return Some(self.board);
                     }
@@ -301,16 +349,20 @@ impl Iterator for SolutionIter {
                 if let Some(value) = values.next() {
                     self.board.set_cell(x, y, value as u8);
 
-                    if let Some(cs) = cell_with_fewest_candidates(&self.board) {
-                        self.stack.push((x, y, values));
-
+                    if let Some(cs) = cell_with_fewest_candidates(&self.board, &self.rules) {
                         if cs.2 == 0 {
-                            return Some(self.board);
+                            // The board is full.  Constraints are only required to narrow down
+                            // candidates conservatively, so check the grid before reporting it.
+                            if self.rules.valid(&self.board) {
+                                self.stack.push((x, y, values));
+                                return Some(self.board);
+                            }
+                        } else {
+                            self.stack.push((x, y, values));
+                            x = cs.0;
+                            y = cs.1;
+                            values = cs.2.into();
                         }
-
-                        x = cs.0;
-                        y = cs.1;
-                        values = cs.2.into();
                     }
                 } else {
                     self.board.set_cell(x, y, 0);
@@ -1,4 +1,15 @@
 use super::*;
+use crate::arrow::*;
+use crate::candidates::*;
+use crate::chess::*;
+use crate::comparison::*;
+use crate::killer::*;
+use crate::lines::*;
+use crate::little_killer::*;
+use crate::markers::*;
+use crate::parity::*;
+use crate::sandwich::*;
+use crate::thermo::*;
 
 #[test]
 fn invalid_cell_values_are_detected() {
@@ -439,3 +450,869 @@ fn solution_iter_from_impl_works() {
     assert_eq!(new_solutions.next(), None);
     assert_eq!(from_solutions.next(), None);
 }
+
+#[test]
+fn duplicate_on_diagonal_is_detected() {
+    let x_sudoku = Rules::classic().with(Diagonals);
+
+    let board = Board::from(&[
+        [1, 0, 0, 0, 0, 0, 0, 0, 0], // row 1
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 2
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 4
+        [0, 0, 0, 0, 1, 0, 0, 0, 0], // row 5
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 7
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 9
+    ]);
+
+    assert!(valid(&board));
+    assert!(!valid_with_rules(&board, &x_sudoku));
+
+    let board = Board::from(&[
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 1
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 2
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 4
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 5
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 7
+        [0, 7, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 9
+    ]);
+
+    assert!(valid_with_rules(&board, &x_sudoku));
+
+    let mut board = board;
+    board.set_cell(4, 4, 7);
+
+    assert!(valid(&board));
+    assert!(!valid_with_rules(&board, &x_sudoku));
+}
+
+#[test]
+fn solves_x_sudoku_puzzles() {
+    let x_sudoku = Rules::classic().with(Diagonals);
+
+    let board = Board::from(&[
+        [0, 2, 0, 4, 0, 0, 0, 0, 0], // row 1
+        [0, 0, 6, 0, 8, 0, 0, 2, 0], // row 2
+        [7, 0, 0, 0, 0, 3, 0, 0, 6], // row 3
+        [9, 0, 5, 0, 0, 0, 8, 0, 0], // row 4
+        [0, 1, 0, 0, 3, 0, 0, 0, 0], // row 5
+        [0, 0, 2, 0, 0, 0, 0, 0, 0], // row 6
+        [0, 0, 0, 0, 0, 0, 0, 7, 0], // row 7
+        [0, 0, 0, 8, 6, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 3, 0, 8], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [1, 2, 3, 4, 5, 6, 7, 8, 9], // row 1
+        [4, 5, 6, 7, 8, 9, 1, 2, 3], // row 2
+        [7, 8, 9, 1, 2, 3, 4, 5, 6], // row 3
+        [9, 3, 5, 2, 4, 1, 8, 6, 7], // row 4
+        [6, 1, 7, 5, 3, 8, 2, 9, 4], // row 5
+        [8, 4, 2, 6, 9, 7, 5, 3, 1], // row 6
+        [2, 9, 8, 3, 1, 4, 6, 7, 5], // row 7
+        [3, 7, 1, 8, 6, 5, 9, 4, 2], // row 8
+        [5, 6, 4, 9, 7, 2, 3, 1, 8], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, x_sudoku.clone());
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+    assert!(valid_with_rules(&correct_solution, &x_sudoku));
+
+    // Without the diagonal constraint, the puzzle is ambiguous.
+    assert!(SolutionIter::new(&board).nth(1).is_some());
+}
+
+fn killer_cages() -> Cages {
+    Cages::new(vec![
+        Cage::new(&[(0, 0), (0, 1), (1, 1)], 18).unwrap(),
+        Cage::new(&[(1, 0), (2, 0)], 8).unwrap(),
+        Cage::new(&[(3, 0), (3, 1), (4, 0), (5, 0)], 22).unwrap(),
+        Cage::new(&[(6, 0), (6, 1), (7, 1)], 20).unwrap(),
+        Cage::new(&[(7, 0), (8, 0)], 9).unwrap(),
+        Cage::new(&[(2, 1), (2, 2), (2, 3), (2, 4)], 19).unwrap(),
+        Cage::new(&[(4, 1), (4, 2), (4, 3)], 19).unwrap(),
+        Cage::new(&[(5, 1), (5, 2)], 5).unwrap(),
+        Cage::new(&[(8, 1), (8, 2), (7, 2), (8, 3)], 18).unwrap(),
+        Cage::new(&[(0, 2), (0, 3)], 9).unwrap(),
+        Cage::new(&[(1, 2), (1, 3), (1, 4)], 18).unwrap(),
+        Cage::new(&[(3, 2), (3, 3), (3, 4)], 15).unwrap(),
+        Cage::new(&[(6, 2), (6, 3)], 8).unwrap(),
+        Cage::new(&[(5, 3), (5, 4), (5, 5)], 10).unwrap(),
+        Cage::new(&[(7, 3), (7, 4)], 5).unwrap(),
+        Cage::new(&[(0, 4), (0, 5)], 12).unwrap(),
+        Cage::new(&[(4, 4), (4, 5), (4, 6)], 14).unwrap(),
+        Cage::new(&[(6, 4), (6, 5), (6, 6), (7, 5)], 25).unwrap(),
+        Cage::new(&[(8, 4), (8, 5), (8, 6)], 17).unwrap(),
+        Cage::new(&[(1, 5), (2, 5), (3, 5), (3, 6)], 16).unwrap(),
+        Cage::new(&[(0, 6), (1, 6), (0, 7)], 8).unwrap(),
+        Cage::new(&[(2, 6), (2, 7), (2, 8), (1, 7)], 24).unwrap(),
+        Cage::new(&[(5, 6), (5, 7), (4, 7), (5, 8)], 26).unwrap(),
+        Cage::new(&[(7, 6), (7, 7), (7, 8)], 15).unwrap(),
+        Cage::new(&[(3, 7), (3, 8)], 13).unwrap(),
+        Cage::new(&[(6, 7), (6, 8)], 3).unwrap(),
+        Cage::new(&[(8, 7), (8, 8)], 15).unwrap(),
+        Cage::new(&[(0, 8), (1, 8)], 13).unwrap(),
+        Cage::new(&[(4, 8)], 1).unwrap(),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn cage_violations_are_detected() {
+    let killer = Rules::classic().with(killer_cages());
+
+    let mut board = Board::default();
+    assert!(valid_with_rules(&board, &killer));
+
+    // The cage at (1, 0) and (2, 0) adds up to 8, so cannot contain a 9.
+    board.set_cell(1, 0, 9);
+    assert!(valid(&board));
+    assert!(!valid_with_rules(&board, &killer));
+
+    // A complete cage with the wrong total.
+    board.set_cell(1, 0, 1);
+    board.set_cell(2, 0, 6);
+    assert!(!valid_with_rules(&board, &killer));
+
+    board.set_cell(2, 0, 7);
+    assert!(valid_with_rules(&board, &killer));
+
+    // A repeated digit within a cage, in cells which share no row, column or square.
+    let mut board = Board::default();
+    board.set_cell(7, 2, 5);
+    board.set_cell(8, 3, 5);
+    assert!(valid(&board));
+    assert!(!valid_with_rules(&board, &killer));
+}
+
+#[test]
+fn solves_killer_puzzles() {
+    let killer = Rules::classic().with(killer_cages());
+
+    let board = Board::from(&[
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 1
+        [0, 0, 2, 0, 0, 0, 0, 0, 0], // row 2
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+        [0, 0, 6, 0, 0, 0, 0, 0, 0], // row 4
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 5
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 7
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [4, 3, 5, 2, 6, 9, 7, 8, 1], // row 1
+        [6, 8, 2, 5, 7, 1, 4, 9, 3], // row 2
+        [1, 9, 7, 8, 3, 4, 5, 6, 2], // row 3
+        [8, 2, 6, 1, 9, 5, 3, 4, 7], // row 4
+        [3, 7, 4, 6, 8, 2, 9, 1, 5], // row 5
+        [9, 5, 1, 7, 4, 3, 6, 2, 8], // row 6
+        [5, 1, 9, 3, 2, 6, 8, 7, 4], // row 7
+        [2, 4, 8, 9, 5, 7, 1, 3, 6], // row 8
+        [7, 6, 3, 4, 1, 8, 2, 5, 9], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, killer);
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_thermometer_puzzles() {
+    // This puzzle was set for this test rather than taken from a published source, so the
+    // expected solution is checked against the rules directly below, without the solver or
+    // `Thermometer`, before the solver's answer is compared with it.
+    let thermometers = [
+        &[(1, 2), (0, 1), (1, 1)][..],
+        &[(5, 1), (4, 1), (5, 0), (6, 0)],
+        &[(6, 5), (6, 6), (5, 5)],
+        &[(7, 7), (8, 6), (7, 5)],
+        &[(2, 2), (2, 3), (3, 2)],
+        &[(4, 0), (3, 0), (3, 1), (4, 2), (4, 3)],
+        &[(7, 0), (7, 1), (8, 0)],
+        &[(3, 3), (4, 4), (3, 5), (4, 5)],
+        &[(1, 5), (1, 4), (0, 4)],
+        &[(6, 4), (7, 3), (6, 3)],
+        &[(5, 8), (4, 7), (3, 7), (3, 8)],
+        &[(6, 7), (5, 6), (5, 7)],
+    ];
+    let mut thermo = Rules::classic();
+
+    for cells in thermometers {
+        thermo.add(Thermometer::new(cells).unwrap());
+    }
+
+    let board = Board::from(&[
+        [0, 0, 6, 0, 0, 0, 0, 0, 0], // row 1
+        [0, 0, 0, 0, 0, 0, 4, 0, 0], // row 2
+        [0, 7, 0, 0, 0, 0, 0, 0, 0], // row 3
+        [4, 5, 0, 1, 0, 0, 0, 0, 0], // row 4
+        [0, 8, 0, 0, 0, 0, 0, 0, 0], // row 5
+        [0, 1, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [0, 0, 0, 3, 0, 0, 0, 0, 0], // row 7
+        [0, 4, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [1, 2, 6, 4, 3, 7, 9, 5, 8], // row 1
+        [8, 9, 5, 6, 2, 1, 4, 7, 3], // row 2
+        [3, 7, 4, 9, 8, 5, 1, 2, 6], // row 3
+        [4, 5, 7, 1, 9, 3, 8, 6, 2], // row 4
+        [9, 8, 3, 2, 4, 6, 5, 1, 7], // row 5
+        [6, 1, 2, 5, 7, 8, 3, 9, 4], // row 6
+        [2, 6, 9, 3, 1, 4, 7, 8, 5], // row 7
+        [5, 4, 8, 7, 6, 9, 2, 3, 1], // row 8
+        [7, 3, 1, 8, 5, 2, 6, 4, 9], // row 9
+    ]);
+
+    assert!(valid(&correct_solution));
+
+    for cells in thermometers {
+        let digits: Vec<u8> = cells
+            .iter()
+            .map(|&(x, y)| correct_solution.get_cell(x, y))
+            .collect();
+
+        assert!(digits.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    for y in 0..BOARD_SIZE {
+        for x in 0..BOARD_SIZE {
+            let given = board.get_cell(x, y);
+            assert!(given == 0 || given == correct_solution.get_cell(x, y));
+        }
+    }
+
+    let mut solutions = SolutionIter::with_rules(&board, thermo);
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_arrow_puzzles() {
+    let mut arrows = Rules::classic();
+
+    for (circle, shaft) in [
+        ((4, 0), &[(5, 1), (6, 2)][..]),
+        ((5, 3), &[(5, 4), (6, 3)]),
+        ((8, 3), &[(8, 4), (7, 5)]),
+        ((3, 2), &[(4, 2), (3, 1)]),
+        ((8, 7), &[(7, 7), (6, 8), (6, 7)]),
+        ((0, 8), &[(1, 7), (0, 7), (1, 6)]),
+        ((2, 2), &[(3, 3), (2, 4), (1, 3)]),
+        ((6, 4), &[(5, 5), (6, 5)]),
+        ((3, 5), &[(3, 4), (2, 5)]),
+        ((7, 0), &[(6, 1), (5, 2)]),
+    ] {
+        arrows.add(Arrow::new(&[circle], shaft).unwrap());
+    }
+
+    let board = Board::from(&[
+        [0, 3, 0, 2, 0, 0, 0, 8, 0], // row 1
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 2
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+        [0, 0, 0, 0, 9, 0, 0, 0, 0], // row 4
+        [0, 7, 0, 0, 0, 0, 0, 1, 0], // row 5
+        [0, 0, 0, 0, 4, 3, 0, 2, 0], // row 6
+        [5, 0, 0, 0, 0, 0, 0, 0, 4], // row 7
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 6, 0, 4, 0, 0, 0, 0, 0], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [4, 3, 5, 2, 6, 9, 7, 8, 1], // row 1
+        [6, 8, 2, 5, 7, 1, 4, 9, 3], // row 2
+        [1, 9, 7, 8, 3, 4, 5, 6, 2], // row 3
+        [8, 2, 6, 1, 9, 5, 3, 4, 7], // row 4
+        [3, 7, 4, 6, 8, 2, 9, 1, 5], // row 5
+        [9, 5, 1, 7, 4, 3, 6, 2, 8], // row 6
+        [5, 1, 9, 3, 2, 6, 8, 7, 4], // row 7
+        [2, 4, 8, 9, 5, 7, 1, 3, 6], // row 8
+        [7, 6, 3, 4, 1, 8, 2, 5, 9], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, arrows);
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_kropki_puzzles() {
+    use KropkiDot::*;
+
+    let kropki = Kropki::new(&[
+        ((0, 0), (1, 0), White),
+        ((4, 0), (4, 1), White),
+        ((6, 0), (7, 0), White),
+        ((7, 0), (7, 1), White),
+        ((1, 1), (1, 2), White),
+        ((6, 1), (6, 2), White),
+        ((8, 1), (8, 2), White),
+        ((2, 2), (3, 2), White),
+        ((2, 2), (2, 3), White),
+        ((4, 2), (5, 2), White),
+        ((5, 2), (6, 2), White),
+        ((5, 2), (5, 3), White),
+        ((6, 2), (7, 2), White),
+        ((4, 3), (4, 4), White),
+        ((6, 3), (7, 3), White),
+        ((3, 4), (3, 5), White),
+        ((4, 4), (4, 5), Black),
+        ((5, 4), (5, 5), White),
+        ((7, 4), (7, 5), White),
+        ((4, 5), (5, 5), White),
+        ((4, 5), (4, 6), Black),
+        ((5, 5), (6, 5), Black),
+        ((5, 5), (5, 6), Black),
+        ((8, 5), (8, 6), Black),
+        ((2, 6), (2, 7), White),
+        ((3, 6), (4, 6), White),
+        ((5, 6), (5, 7), White),
+        ((6, 6), (7, 6), White),
+        ((0, 7), (1, 7), Black),
+        ((1, 7), (2, 7), Black),
+        ((2, 7), (3, 7), White),
+        ((5, 7), (5, 8), White),
+        ((6, 7), (6, 8), White),
+        ((7, 7), (8, 7), Black),
+        ((0, 8), (1, 8), White),
+        ((1, 8), (2, 8), Black),
+        ((2, 8), (3, 8), White),
+    ])
+    .unwrap()
+    .with_negative_constraint();
+
+    let correct_solution = Board::from(&[
+        [4, 3, 5, 2, 6, 9, 7, 8, 1], // row 1
+        [6, 8, 2, 5, 7, 1, 4, 9, 3], // row 2
+        [1, 9, 7, 8, 3, 4, 5, 6, 2], // row 3
+        [8, 2, 6, 1, 9, 5, 3, 4, 7], // row 4
+        [3, 7, 4, 6, 8, 2, 9, 1, 5], // row 5
+        [9, 5, 1, 7, 4, 3, 6, 2, 8], // row 6
+        [5, 1, 9, 3, 2, 6, 8, 7, 4], // row 7
+        [2, 4, 8, 9, 5, 7, 1, 3, 6], // row 8
+        [7, 6, 3, 4, 1, 8, 2, 5, 9], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&Board::default(), Rules::classic().with(kropki));
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_xv_puzzles() {
+    use XVSign::*;
+
+    let xv = XV::new(&[
+        ((0, 0), (0, 1), X),
+        ((5, 0), (5, 1), X),
+        ((1, 1), (2, 1), X),
+        ((4, 1), (4, 2), X),
+        ((5, 1), (6, 1), V),
+        ((5, 1), (5, 2), V),
+        ((8, 1), (8, 2), V),
+        ((0, 2), (1, 2), X),
+        ((7, 2), (7, 3), X),
+        ((0, 3), (1, 3), X),
+        ((2, 3), (2, 4), X),
+        ((3, 3), (4, 3), X),
+        ((7, 3), (7, 4), V),
+        ((0, 4), (1, 4), X),
+        ((2, 4), (3, 4), X),
+        ((2, 4), (2, 5), V),
+        ((4, 4), (5, 4), X),
+        ((5, 4), (5, 5), V),
+        ((6, 4), (7, 4), X),
+        ((2, 5), (2, 6), X),
+        ((3, 5), (3, 6), X),
+        ((7, 5), (8, 5), X),
+        ((1, 6), (2, 6), X),
+        ((1, 6), (1, 7), V),
+        ((3, 6), (4, 6), V),
+        ((7, 6), (7, 7), X),
+        ((8, 6), (8, 7), X),
+        ((1, 7), (1, 8), X),
+        ((3, 8), (4, 8), V),
+        ((5, 8), (6, 8), X),
+    ])
+    .unwrap()
+    .with_negative_constraint();
+
+    let board = Board::from(&[
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 1
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 2
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 4
+        [3, 0, 0, 6, 0, 0, 0, 0, 0], // row 5
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 7
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [4, 3, 5, 2, 6, 9, 7, 8, 1], // row 1
+        [6, 8, 2, 5, 7, 1, 4, 9, 3], // row 2
+        [1, 9, 7, 8, 3, 4, 5, 6, 2], // row 3
+        [8, 2, 6, 1, 9, 5, 3, 4, 7], // row 4
+        [3, 7, 4, 6, 8, 2, 9, 1, 5], // row 5
+        [9, 5, 1, 7, 4, 3, 6, 2, 8], // row 6
+        [5, 1, 9, 3, 2, 6, 8, 7, 4], // row 7
+        [2, 4, 8, 9, 5, 7, 1, 3, 6], // row 8
+        [7, 6, 3, 4, 1, 8, 2, 5, 9], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, Rules::classic().with(xv));
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_anti_knight_puzzles() {
+    let board = Board::from(&[
+        [0, 0, 0, 4, 5, 0, 0, 0, 9], // row 1
+        [9, 0, 0, 0, 1, 0, 0, 0, 6], // row 2
+        [0, 0, 0, 0, 0, 0, 0, 1, 0], // row 3
+        [0, 0, 0, 0, 0, 0, 6, 0, 4], // row 4
+        [0, 0, 0, 0, 0, 1, 0, 0, 0], // row 5
+        [0, 9, 0, 0, 0, 0, 0, 3, 0], // row 6
+        [0, 1, 0, 0, 0, 9, 0, 0, 0], // row 7
+        [3, 7, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [6, 0, 0, 0, 0, 0, 8, 0, 0], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [1, 2, 3, 4, 5, 6, 7, 8, 9], // row 1
+        [9, 8, 7, 3, 1, 2, 4, 5, 6], // row 2
+        [4, 6, 5, 7, 9, 8, 2, 1, 3], // row 3
+        [2, 5, 1, 9, 8, 3, 6, 7, 4], // row 4
+        [7, 3, 4, 6, 2, 1, 5, 9, 8], // row 5
+        [8, 9, 6, 5, 7, 4, 1, 3, 2], // row 6
+        [5, 1, 2, 8, 4, 9, 3, 6, 7], // row 7
+        [3, 7, 8, 2, 6, 5, 9, 4, 1], // row 8
+        [6, 4, 9, 1, 3, 7, 8, 2, 5], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, Rules::classic().with(AntiKnight));
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_anti_king_puzzles() {
+    let board = Board::from(&[
+        [0, 2, 3, 0, 5, 0, 0, 0, 0], // row 1
+        [0, 8, 0, 0, 0, 0, 0, 0, 6], // row 2
+        [4, 0, 0, 0, 8, 0, 0, 0, 0], // row 3
+        [8, 0, 2, 5, 0, 0, 6, 9, 0], // row 4
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 5
+        [0, 9, 0, 0, 0, 8, 3, 0, 0], // row 6
+        [6, 0, 1, 0, 0, 0, 0, 0, 3], // row 7
+        [0, 3, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 5, 0, 3, 7, 9, 0, 6, 0], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [1, 2, 3, 4, 5, 6, 7, 8, 9], // row 1
+        [7, 8, 5, 2, 9, 3, 1, 4, 6], // row 2
+        [4, 6, 9, 1, 8, 7, 2, 3, 5], // row 3
+        [8, 1, 2, 5, 3, 4, 6, 9, 7], // row 4
+        [3, 7, 4, 9, 6, 2, 5, 1, 8], // row 5
+        [5, 9, 6, 7, 1, 8, 3, 2, 4], // row 6
+        [6, 4, 1, 8, 2, 5, 9, 7, 3], // row 7
+        [9, 3, 7, 6, 4, 1, 8, 5, 2], // row 8
+        [2, 5, 8, 3, 7, 9, 4, 6, 1], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, Rules::classic().with(AntiKing));
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_sandwich_puzzles() {
+    let puzzle: SandwichPuzzle = "
+           11 14 0  16 19 0  14 10 35
+        15 -  -  5  -  -  9  -  -  -
+        4  6  -  2  -  -  -  -  -  -
+        0  1  -  -  8  -  -  -  -  -
+        0  -  2  -  -  -  -  -  -  -
+        0  -  -  -  -  8  -  -  -  -
+        5  -  -  -  -  -  -  6  -  -
+        0  -  -  -  -  -  -  -  -  -
+        12 -  -  -  -  -  -  -  -  -
+        15 -  -  -  -  -  -  -  -  -
+    "
+    .parse()
+    .unwrap();
+
+    let correct_solution = Board::from(&[
+        [4, 3, 5, 2, 6, 9, 7, 8, 1], // row 1
+        [6, 8, 2, 5, 7, 1, 4, 9, 3], // row 2
+        [1, 9, 7, 8, 3, 4, 5, 6, 2], // row 3
+        [8, 2, 6, 1, 9, 5, 3, 4, 7], // row 4
+        [3, 7, 4, 6, 8, 2, 9, 1, 5], // row 5
+        [9, 5, 1, 7, 4, 3, 6, 2, 8], // row 6
+        [5, 1, 9, 3, 2, 6, 8, 7, 4], // row 7
+        [2, 4, 8, 9, 5, 7, 1, 3, 6], // row 8
+        [7, 6, 3, 4, 1, 8, 2, 5, 9], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&puzzle.board, puzzle.rules());
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_windoku_puzzles() {
+    let board = Board::from(&[
+        [0, 5, 0, 2, 0, 0, 0, 0, 0], // row 1
+        [0, 9, 0, 0, 0, 0, 7, 0, 0], // row 2
+        [0, 0, 2, 0, 0, 0, 0, 8, 0], // row 3
+        [0, 0, 0, 8, 0, 0, 1, 0, 3], // row 4
+        [0, 0, 0, 0, 3, 0, 0, 0, 0], // row 5
+        [9, 0, 0, 0, 0, 6, 0, 0, 0], // row 6
+        [0, 0, 0, 0, 0, 0, 9, 0, 0], // row 7
+        [0, 0, 1, 0, 0, 0, 0, 7, 0], // row 8
+        [0, 0, 0, 7, 0, 0, 0, 0, 1], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [7, 5, 3, 2, 4, 8, 6, 1, 9], // row 1
+        [8, 9, 4, 1, 6, 5, 7, 3, 2], // row 2
+        [1, 6, 2, 3, 7, 9, 4, 8, 5], // row 3
+        [4, 7, 5, 8, 9, 2, 1, 6, 3], // row 4
+        [2, 1, 6, 4, 3, 7, 5, 9, 8], // row 5
+        [9, 3, 8, 5, 1, 6, 2, 4, 7], // row 6
+        [3, 2, 7, 6, 8, 1, 9, 5, 4], // row 7
+        [5, 4, 1, 9, 2, 3, 8, 7, 6], // row 8
+        [6, 8, 9, 7, 5, 4, 3, 2, 1], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, Rules::classic().with(Windows));
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_disjoint_groups_puzzles() {
+    let board = Board::from(&[
+        [0, 0, 0, 0, 0, 9, 0, 8, 0], // row 1
+        [0, 9, 0, 5, 2, 0, 3, 0, 0], // row 2
+        [0, 0, 2, 0, 4, 0, 0, 0, 0], // row 3
+        [0, 0, 0, 6, 0, 0, 0, 0, 4], // row 4
+        [0, 0, 0, 0, 3, 0, 0, 0, 0], // row 5
+        [2, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [0, 3, 0, 0, 0, 0, 8, 0, 0], // row 7
+        [0, 0, 1, 0, 0, 0, 0, 6, 0], // row 8
+        [0, 0, 0, 8, 0, 0, 0, 0, 7], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [7, 5, 3, 1, 6, 9, 4, 8, 2], // row 1
+        [8, 9, 4, 5, 2, 7, 3, 1, 6], // row 2
+        [1, 6, 2, 3, 4, 8, 7, 9, 5], // row 3
+        [3, 1, 8, 6, 7, 5, 9, 2, 4], // row 4
+        [6, 4, 5, 9, 3, 2, 1, 7, 8], // row 5
+        [2, 7, 9, 4, 8, 1, 6, 5, 3], // row 6
+        [5, 3, 7, 2, 9, 6, 8, 4, 1], // row 7
+        [4, 8, 1, 7, 5, 3, 2, 6, 9], // row 8
+        [9, 2, 6, 8, 1, 4, 5, 3, 7], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, Rules::classic().with(DisjointGroups));
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_greater_than_puzzles() {
+    let board = Board::from(&[
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 1
+        [0, 0, 0, 0, 0, 0, 4, 0, 0], // row 2
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 4
+        [0, 0, 0, 0, 0, 0, 5, 0, 0], // row 5
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 7
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 9
+    ]);
+
+    let correct_solution = Board::from(&[
+        [1, 2, 6, 4, 3, 7, 9, 5, 8], // row 1
+        [8, 9, 5, 6, 2, 1, 4, 7, 3], // row 2
+        [3, 7, 4, 9, 8, 5, 1, 2, 6], // row 3
+        [4, 5, 7, 1, 9, 3, 8, 6, 2], // row 4
+        [9, 8, 3, 2, 4, 6, 5, 1, 7], // row 5
+        [6, 1, 2, 5, 7, 8, 3, 9, 4], // row 6
+        [2, 6, 9, 3, 1, 4, 7, 8, 5], // row 7
+        [5, 4, 8, 7, 6, 9, 2, 3, 1], // row 8
+        [7, 3, 1, 8, 5, 2, 6, 4, 9], // row 9
+    ]);
+
+    // A sign between every pair of adjacent cells within each square, as in the usual layout.
+    let mut signs = Vec::new();
+
+    for y in 0..BOARD_SIZE {
+        for x in 0..BOARD_SIZE {
+            let mut pairs = Vec::new();
+
+            if x % SQUARE_SIZE != SQUARE_SIZE - 1 {
+                pairs.push(((x, y), (x + 1, y)));
+            }
+
+            if y % SQUARE_SIZE != SQUARE_SIZE - 1 {
+                pairs.push(((x, y), (x, y + 1)));
+            }
+
+            for (a, b) in pairs {
+                if correct_solution.get_cell(a.0, a.1) > correct_solution.get_cell(b.0, b.1) {
+                    signs.push((a, b));
+                } else {
+                    signs.push((b, a));
+                }
+            }
+        }
+    }
+
+    let rules = Rules::classic().with(Inequalities::new(&signs).unwrap());
+    let mut solutions = SolutionIter::with_rules(&board, rules);
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_odd_even_puzzles() {
+    let board = Board::from(&[
+        [0, 0, 0, 0, 0, 0, 0, 5, 0], // row 1
+        [0, 0, 0, 0, 2, 0, 0, 0, 0], // row 2
+        [3, 0, 0, 9, 8, 0, 0, 0, 0], // row 3
+        [0, 0, 7, 1, 0, 0, 0, 6, 0], // row 4
+        [0, 8, 0, 0, 4, 0, 5, 1, 0], // row 5
+        [0, 0, 0, 0, 0, 0, 0, 9, 0], // row 6
+        [0, 6, 0, 0, 1, 0, 0, 0, 0], // row 7
+        [5, 4, 8, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 9
+    ]);
+
+    let odd_even: OddEven = "
+        o - - e - - o - -
+        e - - e - - e - -
+        o - - o - - o - -
+        e - - o - - e - -
+        o - - e - - o - -
+        e - - o - - o - -
+        e - - o - - o - -
+        o - - o - - e - -
+        o - - e - - e - -
+    "
+    .parse()
+    .unwrap();
+
+    let correct_solution = Board::from(&[
+        [1, 2, 6, 4, 3, 7, 9, 5, 8], // row 1
+        [8, 9, 5, 6, 2, 1, 4, 7, 3], // row 2
+        [3, 7, 4, 9, 8, 5, 1, 2, 6], // row 3
+        [4, 5, 7, 1, 9, 3, 8, 6, 2], // row 4
+        [9, 8, 3, 2, 4, 6, 5, 1, 7], // row 5
+        [6, 1, 2, 5, 7, 8, 3, 9, 4], // row 6
+        [2, 6, 9, 3, 1, 4, 7, 8, 5], // row 7
+        [5, 4, 8, 7, 6, 9, 2, 3, 1], // row 8
+        [7, 3, 1, 8, 5, 2, 6, 4, 9], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, Rules::classic().with(odd_even));
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_line_puzzles() {
+    let board = Board::from(&[
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 1
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 2
+        [0, 0, 0, 8, 3, 0, 0, 0, 0], // row 3
+        [0, 2, 6, 0, 0, 0, 0, 4, 0], // row 4
+        [0, 0, 0, 0, 0, 0, 9, 0, 0], // row 5
+        [9, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [5, 1, 9, 3, 2, 0, 0, 0, 0], // row 7
+        [2, 0, 0, 0, 0, 0, 0, 0, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 9
+    ]);
+
+    let rules = Rules::classic()
+        .with(Whisper::new(&[(3, 8), (3, 7), (4, 8), (5, 8), (6, 8), (5, 7)]).unwrap())
+        .with(Whisper::new(&[(2, 1), (1, 1), (0, 2), (0, 3), (0, 4)]).unwrap())
+        .with(Renban::new(&[(7, 0), (6, 0), (5, 0), (4, 0)]).unwrap())
+        .with(Renban::new(&[(4, 7), (4, 6), (4, 5), (5, 6), (5, 5)]).unwrap())
+        .with(Palindrome::new(&[(6, 7), (6, 6), (7, 5), (8, 5), (7, 4)]).unwrap())
+        .with(Palindrome::new(&[(6, 2), (5, 2), (5, 3)]).unwrap());
+
+    let correct_solution = Board::from(&[
+        [4, 3, 5, 2, 6, 9, 7, 8, 1], // row 1
+        [6, 8, 2, 5, 7, 1, 4, 9, 3], // row 2
+        [1, 9, 7, 8, 3, 4, 5, 6, 2], // row 3
+        [8, 2, 6, 1, 9, 5, 3, 4, 7], // row 4
+        [3, 7, 4, 6, 8, 2, 9, 1, 5], // row 5
+        [9, 5, 1, 7, 4, 3, 6, 2, 8], // row 6
+        [5, 1, 9, 3, 2, 6, 8, 7, 4], // row 7
+        [2, 4, 8, 9, 5, 7, 1, 3, 6], // row 8
+        [7, 6, 3, 4, 1, 8, 2, 5, 9], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, rules);
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_little_killer_puzzles() {
+    let board = Board::from(&[
+        [0, 0, 0, 0, 3, 7, 9, 5, 0], // row 1
+        [8, 9, 5, 6, 0, 0, 0, 0, 0], // row 2
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 3
+        [0, 0, 7, 0, 0, 0, 0, 0, 2], // row 4
+        [0, 0, 0, 0, 0, 6, 0, 0, 0], // row 5
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 6
+        [0, 0, 0, 0, 1, 0, 0, 0, 0], // row 7
+        [0, 4, 0, 0, 0, 0, 0, 3, 0], // row 8
+        [0, 0, 0, 0, 0, 0, 0, 0, 0], // row 9
+    ]);
+
+    let clues = LittleKillers::new(
+        [
+            ((2, 0), Direction::DownRight, 42),
+            ((5, 0), Direction::DownRight, 15),
+            ((0, 3), Direction::DownRight, 25),
+            ((0, 6), Direction::DownRight, 7),
+            ((6, 0), Direction::DownLeft, 25),
+            ((3, 0), Direction::DownLeft, 20),
+            ((8, 3), Direction::DownLeft, 24),
+            ((8, 6), Direction::DownLeft, 14),
+            ((0, 0), Direction::DownRight, 46),
+            ((1, 8), Direction::UpRight, 40),
+        ]
+        .iter()
+        .map(|&(start, direction, sum)| LittleKiller::new(start, direction, sum).unwrap())
+        .collect(),
+    );
+
+    let correct_solution = Board::from(&[
+        [1, 2, 6, 4, 3, 7, 9, 5, 8], // row 1
+        [8, 9, 5, 6, 2, 1, 4, 7, 3], // row 2
+        [3, 7, 4, 9, 8, 5, 1, 2, 6], // row 3
+        [4, 5, 7, 1, 9, 3, 8, 6, 2], // row 4
+        [9, 8, 3, 2, 4, 6, 5, 1, 7], // row 5
+        [6, 1, 2, 5, 7, 8, 3, 9, 4], // row 6
+        [2, 6, 9, 3, 1, 4, 7, 8, 5], // row 7
+        [5, 4, 8, 7, 6, 9, 2, 3, 1], // row 8
+        [7, 3, 1, 8, 5, 2, 6, 4, 9], // row 9
+    ]);
+
+    assert!(clues.violations(&correct_solution).is_empty());
+
+    let mut solutions = SolutionIter::with_rules(&board, Rules::classic().with(clues));
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_puzzles_with_extra_houses() {
+    // The Windoku puzzle again, with its windows given as houses.
+    let board = Board::from(&[
+        [0, 5, 0, 2, 0, 0, 0, 0, 0], // row 1
+        [0, 9, 0, 0, 0, 0, 7, 0, 0], // row 2
+        [0, 0, 2, 0, 0, 0, 0, 8, 0], // row 3
+        [0, 0, 0, 8, 0, 0, 1, 0, 3], // row 4
+        [0, 0, 0, 0, 3, 0, 0, 0, 0], // row 5
+        [9, 0, 0, 0, 0, 6, 0, 0, 0], // row 6
+        [0, 0, 0, 0, 0, 0, 9, 0, 0], // row 7
+        [0, 0, 1, 0, 0, 0, 0, 7, 0], // row 8
+        [0, 0, 0, 7, 0, 0, 0, 0, 1], // row 9
+    ]);
+
+    let mut rules = Rules::classic();
+
+    for (xs, ys) in [(1, 1), (5, 1), (1, 5), (5, 5)] {
+        let cells: Vec<(usize, usize)> = (0..BOARD_SIZE)
+            .map(|i| (xs + i % SQUARE_SIZE, ys + i / SQUARE_SIZE))
+            .collect();
+
+        rules.add(House::new(&cells).unwrap());
+    }
+
+    let correct_solution = Board::from(&[
+        [7, 5, 3, 2, 4, 8, 6, 1, 9], // row 1
+        [8, 9, 4, 1, 6, 5, 7, 3, 2], // row 2
+        [1, 6, 2, 3, 7, 9, 4, 8, 5], // row 3
+        [4, 7, 5, 8, 9, 2, 1, 6, 3], // row 4
+        [2, 1, 6, 4, 3, 7, 5, 9, 8], // row 5
+        [9, 3, 8, 5, 1, 6, 2, 4, 7], // row 6
+        [3, 2, 7, 6, 8, 1, 9, 5, 4], // row 7
+        [5, 4, 1, 9, 2, 3, 8, 7, 6], // row 8
+        [6, 8, 9, 7, 5, 4, 3, 2, 1], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::with_rules(&board, rules);
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
+
+#[test]
+fn solves_sukaku_puzzles() {
+    let grid: CandidateGrid = "
+        12.4.6789.23.56..9.2..5...912345.7.91.34.6.89..3.56.89.2.45678.1.3.5..8.1.3..6.89
+        .23.56.....3..6.8..2.45...91..456789...45678912.4.6..91234.67.9.234.6.89.23.5...9
+        12345...912.45...912.4.67..1.3..6.891.3...7.9.2.4.678.1..45.7...23.56789.234.67.9
+        .2...6.89.2...678..2.456..912.45678...3456789.23456..91.3456.891234.67..1..4567..
+        ..34..7.9.2....78....45....1..456.89.2.4.678..23.5.......45..89123.5.7..123456..9
+        .234.6.8912..567..12345.7.....4..7.9...456....234.67891.34567.9123.56789....5..89
+        .23.567891.34.678...34.6..9.23..678..23..67...234567....34.6.8..23..6789.234.6.8.
+        12.4...8.12.45.7.91234.6.8.1.3.5..891.3.5..89.23...7891234.....1.3.....9.234.6..9
+        ....567.91234.6.....345.789.2345..8.1.3.5.78.1.34...89.2.4.6..912.456.8..23456789
+    "
+    .parse()
+    .unwrap();
+
+    let correct_solution = Board::from(&[
+        [4, 3, 5, 2, 6, 9, 7, 8, 1], // row 1
+        [6, 8, 2, 5, 7, 1, 4, 9, 3], // row 2
+        [1, 9, 7, 8, 3, 4, 5, 6, 2], // row 3
+        [8, 2, 6, 1, 9, 5, 3, 4, 7], // row 4
+        [3, 7, 4, 6, 8, 2, 9, 1, 5], // row 5
+        [9, 5, 1, 7, 4, 3, 6, 2, 8], // row 6
+        [5, 1, 9, 3, 2, 6, 8, 7, 4], // row 7
+        [2, 4, 8, 9, 5, 7, 1, 3, 6], // row 8
+        [7, 6, 3, 4, 1, 8, 2, 5, 9], // row 9
+    ]);
+
+    let mut solutions = SolutionIter::from_candidates(&grid);
+
+    assert_eq!(solutions.next(), Some(correct_solution));
+    assert_eq!(solutions.next(), None);
+}
@@ -0,0 +1,492 @@
+//! # SVG rendering
+//!
+//! An [`Svg`] draws a board as a scalable vector graphic, suitable for printing or for showing
+//! on a web page.  It is built from the puzzle's givens, and can then be given a solution, whose
+//! digits are drawn in a different style, pencilmarks for the unfilled cells, highlighted cells,
+//! and the cages and thermometers of variant puzzles.  The image is produced by formatting the
+//! `Svg` with [`Display`](std::fmt::Display).
+//!
+//! Each part of the image has a CSS class, which the built-in style sheet uses to set its
+//! colours and line widths:
+//!
+//! | Class           | Drawn for                                       |
+//! | --------------- | ----------------------------------------------- |
+//! | `background`    | the area behind the grid                        |
+//! | `cell-line`     | the lines between cells in the same square      |
+//! | `box-line`      | the lines between squares, and the grid's edge  |
+//! | `given`         | the digits of the puzzle                        |
+//! | `solved`        | the digits of the solution which were not given |
+//! | `pencilmark`    | the candidates of unfilled cells                |
+//! | `highlight`     | highlighted cells                               |
+//! | `cage`          | the outlines of killer cages                    |
+//! | `cage-sum`      | the totals of killer cages                      |
+//! | `thermometer`   | thermometer bulbs and tubes                     |
+//!
+//! Extra rules may be added with [`Svg::with_style()`], for example to use a different font.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let cages = Cages::new(vec![Cage::new(&[(0, 0), (1, 0)], 3).unwrap()]).unwrap();
+//! let thermometer = Thermometer::new(&[(4, 4), (5, 4), (6, 5)]).unwrap();
+//!
+//! let mut board = Board::default();
+//! board.set_cell(0, 0, 1);
+//!
+//! let svg = Svg::new(&board)
+//!     .with_cages(&cages)
+//!     .with_thermometer(&thermometer)
+//!     .highlight(&[(8, 8)], "#ffe680")
+//!     .to_string();
+//!
+//! assert!(svg.starts_with("<svg "));
+//! assert!(svg.contains(r#"class="given""#));
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+use super::candidates::*;
+use super::killer::*;
+use super::thermo::*;
+
+/// The default width and height of a cell, in SVG user units.
+const DEFAULT_CELL_SIZE: f64 = 50.0;
+
+/// The distance of a cage outline from the edges of its cells, as a fraction of a cell.
+const CAGE_INSET: f64 = 0.08;
+
+/// A renderer which draws a board, and optionally the marks of a variant puzzle, as SVG.
+#[derive(Clone, Debug)]
+pub struct Svg {
+    givens: Board,
+    solution: Option<Board>,
+    pencilmarks: Option<CandidateGrid>,
+    highlights: Vec<((usize, usize), String)>,
+    cages: Vec<Cage>,
+    thermometers: Vec<Thermometer>,
+    cell_size: f64,
+    style: String,
+}
+
+impl Svg {
+    /// Create an `Svg` which draws the digits of `givens` as the clues of a puzzle.
+    pub fn new(givens: &Board) -> Self {
+        Self {
+            givens: *givens,
+            solution: None,
+            pencilmarks: None,
+            highlights: Vec::new(),
+            cages: Vec::new(),
+            thermometers: Vec::new(),
+            cell_size: DEFAULT_CELL_SIZE,
+            style: String::new(),
+        }
+    }
+
+    /// Also draw the digits of `solution` in the cells which are not given, in the `solved`
+    /// style.  The solution may be partial.
+    pub fn with_solution(mut self, solution: &Board) -> Self {
+        self.solution = Some(*solution);
+        self
+    }
+
+    /// Draw the candidates in `grid` as pencilmarks in each cell which has no digit.
+    pub fn with_pencilmarks(mut self, grid: &CandidateGrid) -> Self {
+        self.pencilmarks = Some(*grid);
+        self
+    }
+
+    /// Fill the given cells with `colour`, which may be any SVG colour, such as `"yellow"` or
+    /// `"#ffe680"`.  Later highlights are drawn over earlier ones.
+    pub fn highlight(mut self, cells: &[(usize, usize)], colour: &str) -> Self {
+        self.highlights
+            .extend(cells.iter().map(|&cell| (cell, colour.to_string())));
+        self
+    }
+
+    /// Draw the outlines and totals of the given killer cages.
+    pub fn with_cages(mut self, cages: &Cages) -> Self {
+        self.cages.extend_from_slice(cages.cages());
+        self
+    }
+
+    /// Draw a thermometer, with its bulb in its first cell.
+    pub fn with_thermometer(mut self, thermometer: &Thermometer) -> Self {
+        self.thermometers.push(thermometer.clone());
+        self
+    }
+
+    /// Set the width and height of each cell, in SVG user units.  The default is 50.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `size` is not positive.
+    pub fn cell_size(mut self, size: f64) -> Self {
+        assert!(size > 0.0, "cell size must be positive");
+        self.cell_size = size;
+        self
+    }
+
+    /// Add CSS rules to the image's style sheet, after the built-in rules, so that they take
+    /// precedence over them.
+    pub fn with_style(mut self, css: &str) -> Self {
+        self.style.push_str(css);
+        self.style.push('\n');
+        self
+    }
+
+    /// The distance between the edge of the image and the edge of the grid.
+    #[inline]
+    fn margin(&self) -> f64 {
+        self.cell_size / 5.0
+    }
+
+    /// The total width and height of the image.
+    #[inline]
+    fn image_size(&self) -> f64 {
+        self.cell_size * BOARD_SIZE as f64 + 2.0 * self.margin()
+    }
+
+    /// Returns the position of the point `(u, v)`, measured in cells from the top left of the
+    /// grid, in SVG user units.
+    #[inline]
+    fn point(&self, (u, v): (f64, f64)) -> (f64, f64) {
+        (
+            self.margin() + u * self.cell_size,
+            self.margin() + v * self.cell_size,
+        )
+    }
+
+    /// Returns the digit to draw in a cell, and the class to draw it with.
+    fn digit(&self, x: usize, y: usize) -> Option<(u8, &'static str)> {
+        match self.givens.get_cell(x, y) {
+            0 => match self.solution.map(|s| s.get_cell(x, y)) {
+                Some(0) | None => None,
+                Some(v) => Some((v, "solved")),
+            },
+            v => Some((v, "given")),
+        }
+    }
+
+    fn write_style(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = self.cell_size;
+
+        writeln!(f, "<style>")?;
+        writeln!(f, ".background {{ fill: #ffffff; }}")?;
+        writeln!(
+            f,
+            ".cell-line {{ stroke: #000000; stroke-width: {}; fill: none; }}",
+            num(s / 50.0)
+        )?;
+        writeln!(
+            f,
+            ".box-line {{ stroke: #000000; stroke-width: {}; fill: none; stroke-linecap: square; }}",
+            num(s / 16.0)
+        )?;
+        writeln!(
+            f,
+            ".given, .solved {{ font-family: sans-serif; font-size: {}px; }}",
+            num(s * 0.6)
+        )?;
+        writeln!(f, ".given {{ fill: #000000; }}")?;
+        writeln!(f, ".solved {{ fill: #1a5fb4; }}")?;
+        writeln!(
+            f,
+            ".pencilmark {{ font-family: sans-serif; font-size: {}px; fill: #555555; }}",
+            num(s * 0.24)
+        )?;
+        writeln!(
+            f,
+            ".cage {{ stroke: #000000; stroke-width: {}; stroke-dasharray: {} {}; fill: none; }}",
+            num(s / 50.0),
+            num(s / 16.0),
+            num(s / 25.0)
+        )?;
+        writeln!(
+            f,
+            ".cage-sum {{ font-family: sans-serif; font-size: {}px; fill: #000000; \
+             stroke: #ffffff; stroke-width: {}; paint-order: stroke; }}",
+            num(s * 0.22),
+            num(s / 16.0)
+        )?;
+        writeln!(
+            f,
+            ".thermometer {{ stroke: #c0c0c0; fill: none; stroke-width: {}; \
+             stroke-linecap: round; stroke-linejoin: round; }}",
+            num(s * 0.3)
+        )?;
+        writeln!(f, "circle.thermometer {{ fill: #c0c0c0; stroke: none; }}")?;
+        write!(f, "{}", escape(&self.style))?;
+        writeln!(f, "</style>")
+    }
+
+    fn write_highlights(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((x, y), colour) in &self.highlights {
+            let (left, top) = self.point((*x as f64, *y as f64));
+
+            writeln!(
+                f,
+                r#"<rect class="highlight" x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                num(left),
+                num(top),
+                num(self.cell_size),
+                num(self.cell_size),
+                escape(colour)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn write_thermometers(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for thermometer in &self.thermometers {
+            let centres: Vec<(f64, f64)> = thermometer
+                .cells()
+                .iter()
+                .map(|&(x, y)| self.point((x as f64 + 0.5, y as f64 + 0.5)))
+                .collect();
+            let (cx, cy) = centres[0];
+
+            writeln!(
+                f,
+                r#"<circle class="thermometer" cx="{}" cy="{}" r="{}"/>"#,
+                num(cx),
+                num(cy),
+                num(self.cell_size * 0.38)
+            )?;
+
+            let points: Vec<String> = centres
+                .iter()
+                .map(|&(x, y)| format!("{},{}", num(x), num(y)))
+                .collect();
+
+            writeln!(
+                f,
+                r#"<polyline class="thermometer" points="{}"/>"#,
+                points.join(" ")
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn write_cages(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for cage in &self.cages {
+            let path: Vec<String> = cage_outline(cage.cells())
+                .into_iter()
+                .map(|(a, b)| {
+                    let (ax, ay) = self.point(a);
+                    let (bx, by) = self.point(b);
+                    format!("M{} {}L{} {}", num(ax), num(ay), num(bx), num(by))
+                })
+                .collect();
+
+            writeln!(f, r#"<path class="cage" d="{}"/>"#, path.join(""))?;
+
+            // The total is written in the top left corner of the cage's first cell in reading
+            // order.
+            let &(x, y) = cage.cells().iter().min_by_key(|&&(x, y)| (y, x)).unwrap();
+            let (left, top) =
+                self.point((x as f64 + CAGE_INSET / 2.0, y as f64 + CAGE_INSET / 2.0));
+
+            writeln!(
+                f,
+                r#"<text class="cage-sum" x="{}" y="{}" dominant-baseline="hanging">{}</text>"#,
+                num(left),
+                num(top),
+                cage.sum()
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn write_grid(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (start, end) = (
+            self.margin(),
+            self.margin() + self.cell_size * BOARD_SIZE as f64,
+        );
+        let mut thin = String::new();
+        let mut thick = String::new();
+
+        for i in 1..BOARD_SIZE {
+            let at = self.margin() + self.cell_size * i as f64;
+            let path = if i % SQUARE_SIZE == 0 {
+                &mut thick
+            } else {
+                &mut thin
+            };
+
+            path.push_str(&format!(
+                "M{} {}V{}M{} {}H{}",
+                num(at),
+                num(start),
+                num(end),
+                num(start),
+                num(at),
+                num(end)
+            ));
+        }
+
+        writeln!(f, r#"<path class="cell-line" d="{}"/>"#, thin)?;
+        writeln!(f, r#"<path class="box-line" d="{}"/>"#, thick)?;
+        writeln!(
+            f,
+            r#"<rect class="box-line" x="{}" y="{}" width="{}" height="{}"/>"#,
+            num(start),
+            num(start),
+            num(end - start),
+            num(end - start)
+        )
+    }
+
+    fn write_digits(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                match self.digit(x, y) {
+                    Some((v, class)) => {
+                        let (cx, cy) = self.point((x as f64 + 0.5, y as f64 + 0.5));
+                        write_text(f, class, (cx, cy), v)?;
+                    }
+                    None => {
+                        let grid = match &self.pencilmarks {
+                            Some(grid) => grid,
+                            None => continue,
+                        };
+
+                        for d in grid.digits(x, y) {
+                            let (col, row) = ((d - 1) % 3, (d - 1) / 3);
+                            let centre = self.point((
+                                x as f64 + (col as f64 + 0.5) / 3.0,
+                                y as f64 + (row as f64 + 0.5) / 3.0,
+                            ));
+                            write_text(f, "pencilmark", centre, d)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Svg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let size = num(self.image_size());
+
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+            size
+        )?;
+        self.write_style(f)?;
+        writeln!(
+            f,
+            r#"<rect class="background" x="0" y="0" width="{0}" height="{0}"/>"#,
+            size
+        )?;
+        self.write_highlights(f)?;
+        self.write_thermometers(f)?;
+        self.write_cages(f)?;
+        self.write_grid(f)?;
+        self.write_digits(f)?;
+        writeln!(f, "</svg>")
+    }
+}
+
+/// Write a digit centred on the given point.
+fn write_text(
+    f: &mut std::fmt::Formatter<'_>,
+    class: &str,
+    (x, y): (f64, f64),
+    digit: u8,
+) -> std::fmt::Result {
+    writeln!(
+        f,
+        r#"<text class="{}" x="{}" y="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+        class,
+        num(x),
+        num(y),
+        digit
+    )
+}
+
+/// Returns the segments of the outline of a cage, inset from the edges of its cells, with points
+/// measured in cells from the top left of the grid.
+///
+/// Each cell contributes a segment for each of its sides which is not shared with another cell
+/// of the cage.  The ends of a segment are moved along the side to meet the segment of the next
+/// cell, or of the same cell, around the outline.
+fn cage_outline(cells: &[(usize, usize)]) -> Vec<((f64, f64), (f64, f64))> {
+    let contains =
+        |x: isize, y: isize| x >= 0 && y >= 0 && cells.contains(&(x as usize, y as usize));
+    let mut segments = Vec::new();
+
+    for &(x, y) in cells {
+        let (x, y) = (x as isize, y as isize);
+
+        // The outward normal of each side, and the direction along it, clockwise.
+        for (nx, ny) in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+            if contains(x + nx, y + ny) {
+                continue;
+            }
+
+            let (tx, ty) = (-ny, nx);
+
+            // How far each end of the segment lies from the middle of the side.
+            let reach = |sign: isize| {
+                let (ax, ay) = (x + sign * tx, y + sign * ty);
+
+                match (contains(ax, ay), contains(ax + nx, ay + ny)) {
+                    (false, _) => 0.5 - CAGE_INSET,
+                    (true, false) => 0.5,
+                    (true, true) => 0.5 + CAGE_INSET,
+                }
+            };
+
+            let end = |sign: isize| {
+                let along = sign as f64 * reach(sign);
+
+                (
+                    x as f64 + 0.5 + nx as f64 * (0.5 - CAGE_INSET) + tx as f64 * along,
+                    y as f64 + 0.5 + ny as f64 * (0.5 - CAGE_INSET) + ty as f64 * along,
+                )
+            };
+
+            segments.push((end(-1), end(1)));
+        }
+    }
+
+    segments
+}
+
+/// Format a coordinate, rounded to two decimal places, without trailing zeros.
+pub(crate) fn num(v: f64) -> String {
+    let rounded = (v * 100.0).round() / 100.0;
+
+    // Avoid writing "-0".
+    format!("{}", rounded + 0.0)
+}
+
+/// Escape the characters which may not appear in XML text or attribute values.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,199 @@
+use super::*;
+
+/// Returns the number of elements in `svg` with the given class.
+fn count_class(svg: &str, class: &str) -> usize {
+    svg.matches(&format!(r#"class="{}""#, class)).count()
+}
+
+#[test]
+fn draws_empty_grid() {
+    let svg = Svg::new(&Board::default()).to_string();
+
+    assert!(svg.starts_with(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="470" height="470" viewBox="0 0 470 470">"#
+    ));
+    assert!(svg.ends_with("</svg>\n"));
+    assert!(svg
+        .contains(r#"<path class="box-line" d="M160 10V460M10 160H460M310 10V460M10 310H460"/>"#));
+    assert!(svg.contains(r#"<rect class="box-line" x="10" y="10" width="450" height="450"/>"#));
+    assert_eq!(svg.matches("<text").count(), 0);
+}
+
+#[test]
+fn draws_givens_and_solution() {
+    let mut givens = Board::default();
+    givens.set_cell(0, 0, 4);
+    givens.set_cell(8, 8, 9);
+
+    let mut solution = givens;
+    solution.set_cell(1, 0, 3);
+    solution.set_cell(8, 8, 9);
+
+    let svg = Svg::new(&givens).with_solution(&solution).to_string();
+
+    assert_eq!(count_class(&svg, "given"), 2);
+    assert_eq!(count_class(&svg, "solved"), 1);
+    assert!(svg.contains(
+        r#"<text class="given" x="35" y="35" text-anchor="middle" dominant-baseline="central">4</text>"#
+    ));
+    assert!(svg.contains(
+        r#"<text class="solved" x="85" y="35" text-anchor="middle" dominant-baseline="central">3</text>"#
+    ));
+}
+
+#[test]
+fn draws_pencilmarks_in_unfilled_cells() {
+    let mut board = Board::default();
+    board.set_cell(0, 0, 5);
+
+    let mut grid = CandidateGrid::from(&board);
+    grid.set_candidates(1, 0, 0b10_0000_0010);
+
+    let svg = Svg::new(&board).with_pencilmarks(&grid).to_string();
+
+    // The given cell has no pencilmarks, and the rest of the first row has eight candidates
+    // each, except the cell whose candidates were set.
+    let marks: usize = (1..BOARD_SIZE * BOARD_SIZE)
+        .map(|i| grid.digits(i % BOARD_SIZE, i / BOARD_SIZE).count())
+        .sum();
+    assert_eq!(count_class(&svg, "pencilmark"), marks);
+    assert_eq!(count_class(&svg, "given"), 1);
+
+    // Digit 1 is in the top left of its cell, and 9 in the bottom right.
+    let third = 50.0 / 3.0;
+    assert!(svg.contains(&format!(
+        r#"<text class="pencilmark" x="{}" y="{}" text-anchor="middle" dominant-baseline="central">1</text>"#,
+        num(60.0 + third / 2.0),
+        num(10.0 + third / 2.0)
+    )));
+    assert!(svg.contains(&format!(
+        r#"<text class="pencilmark" x="{}" y="{}" text-anchor="middle" dominant-baseline="central">9</text>"#,
+        num(110.0 - third / 2.0),
+        num(60.0 - third / 2.0)
+    )));
+}
+
+#[test]
+fn draws_highlights_and_thermometers() {
+    let thermometer = Thermometer::new(&[(0, 8), (1, 7), (2, 7)]).unwrap();
+    let svg = Svg::new(&Board::default())
+        .highlight(&[(0, 0), (1, 0)], "yellow")
+        .highlight(&[(4, 4)], "\"><script>")
+        .with_thermometer(&thermometer)
+        .cell_size(10.0)
+        .to_string();
+
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="94" height="94""#));
+    assert_eq!(count_class(&svg, "highlight"), 3);
+    assert!(svg.contains(
+        r#"<rect class="highlight" x="12" y="2" width="10" height="10" fill="yellow"/>"#
+    ));
+    assert!(svg.contains(r#"fill="&quot;&gt;&lt;script&gt;"/>"#));
+    assert!(!svg.contains("<script>"));
+
+    assert!(svg.contains(r#"<circle class="thermometer" cx="7" cy="87" r="3.8"/>"#));
+    assert!(svg.contains(r#"<polyline class="thermometer" points="7,87 17,77 27,77"/>"#));
+}
+
+#[test]
+fn draws_cages() {
+    let cages = Cages::new(vec![
+        Cage::new(&[(4, 4)], 5).unwrap(),
+        Cage::new(&[(1, 0), (0, 1), (1, 1), (1, 2), (2, 2)], 20).unwrap(),
+    ])
+    .unwrap();
+    let svg = Svg::new(&Board::default()).with_cages(&cages).to_string();
+
+    assert_eq!(count_class(&svg, "cage"), 2);
+    assert!(svg.contains(
+        r#"<text class="cage-sum" x="212" y="212" dominant-baseline="hanging">5</text>"#
+    ));
+    assert!(svg
+        .contains(r#"<text class="cage-sum" x="62" y="12" dominant-baseline="hanging">20</text>"#));
+}
+
+#[test]
+fn cage_outlines_are_closed() {
+    for cells in [
+        vec![(4, 4)],
+        vec![(0, 0), (1, 0)],
+        vec![(1, 0), (0, 1), (1, 1), (1, 2), (2, 2)],
+        vec![
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (0, 1),
+            (2, 1),
+            (0, 2),
+            (1, 2),
+            (2, 2),
+        ],
+    ] {
+        let segments = cage_outline(&cells);
+
+        // Every cell side on the edge of the cage has a segment.
+        let sides: usize = cells
+            .iter()
+            .map(|&(x, y)| {
+                [(0, -1), (1, 0), (0, 1), (-1, 0)]
+                    .iter()
+                    .filter(|&&(dx, dy)| {
+                        !cells.contains(&((x as isize + dx) as usize, (y as isize + dy) as usize))
+                    })
+                    .count()
+            })
+            .sum();
+        assert_eq!(segments.len(), sides);
+
+        // Each segment ends where another begins.
+        let key = |(x, y): (f64, f64)| (num(x), num(y));
+
+        for &(_, end) in &segments {
+            assert_eq!(
+                segments
+                    .iter()
+                    .filter(|&&(start, _)| key(start) == key(end))
+                    .count(),
+                1,
+                "no segment follows {:?} in the outline of {:?}",
+                end,
+                cells
+            );
+        }
+    }
+
+    let square: Vec<String> = cage_outline(&[(0, 0)])
+        .into_iter()
+        .map(|((ax, ay), (bx, by))| format!("{},{} {},{}", num(ax), num(ay), num(bx), num(by)))
+        .collect();
+
+    assert_eq!(
+        square,
+        vec![
+            "0.08,0.08 0.92,0.08",
+            "0.92,0.08 0.92,0.92",
+            "0.92,0.92 0.08,0.92",
+            "0.08,0.92 0.08,0.08",
+        ]
+    );
+}
+
+#[test]
+fn adds_styles() {
+    let svg = Svg::new(&Board::default())
+        .with_style(".given { font-family: serif; }")
+        .to_string();
+
+    let style = &svg[svg.find("<style>").unwrap()..svg.find("</style>").unwrap()];
+
+    assert!(style.contains(".given { fill: #000000; }"));
+    assert!(style.ends_with(".given { font-family: serif; }\n"));
+}
+
+#[test]
+fn formats_numbers() {
+    assert_eq!(num(10.0), "10");
+    assert_eq!(num(2.5), "2.5");
+    assert_eq!(num(1.0 / 3.0), "0.33");
+    assert_eq!(num(-0.001), "0");
+}
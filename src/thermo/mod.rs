@@ -0,0 +1,204 @@
+//! # Thermometer sudoku
+//!
+//! A thermometer is a path of cells, starting at the bulb.  Digits must strictly increase along
+//! the path from the bulb to the tip.
+//!
+//! Each [`Thermometer`] implements [`Constraint`], so thermometers are added to [`Rules`]
+//! individually.  Thermometers may share cells, as in puzzles with branching thermometers.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() {
+//! # use sudoku_solver::*;
+//! let thermo = Thermometer::new(&[(0, 0), (1, 0), (2, 0)]).unwrap();
+//! let rules = Rules::classic().with(thermo);
+//!
+//! let solution = SolutionIter::with_rules(&Board::default(), rules).next().unwrap();
+//!
+//! assert!(solution.get_cell(0, 0) < solution.get_cell(1, 0));
+//! assert!(solution.get_cell(1, 0) < solution.get_cell(2, 0));
+//! # }
+//! ```
+//!
+//! [`Rules`]: crate::Rules
+
+#[cfg(test)]
+mod tests;
+
+use super::board::*;
+use super::constraint::*;
+
+/// An error arising from an invalid thermometer definition.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ThermometerError {
+    /// A thermometer contains no cells.
+    Empty,
+
+    /// A thermometer is longer than the number of distinct digits.
+    TooManyCells(usize),
+
+    /// A cell lies outside the board.
+    CellOutOfRange(usize, usize),
+
+    /// A cell appears more than once on the same thermometer.
+    DuplicateCell(usize, usize),
+
+    /// A cell is not adjacent, orthogonally or diagonally, to the previous cell on the path.
+    NotAdjacent(usize, usize),
+}
+
+impl std::fmt::Display for ThermometerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThermometerError::Empty => write!(f, "thermometer contains no cells"),
+            ThermometerError::TooManyCells(n) => {
+                write!(f, "thermometer contains too many cells ({})", n)
+            }
+            ThermometerError::CellOutOfRange(x, y) => {
+                write!(f, "cell ({}, {}) is off the board", x, y)
+            }
+            ThermometerError::DuplicateCell(x, y) => {
+                write!(
+                    f,
+                    "cell ({}, {}) appears more than once on a thermometer",
+                    x, y
+                )
+            }
+            ThermometerError::NotAdjacent(x, y) => {
+                write!(
+                    f,
+                    "cell ({}, {}) is not adjacent to the previous cell",
+                    x, y
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThermometerError {}
+
+/// A path of cells along which digits must strictly increase.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Thermometer {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Thermometer {
+    /// Create a `Thermometer` from a path of cells, starting with the bulb.
+    ///
+    /// Cells are given as `(x, y)` pairs, using the same coordinates as [`Board::get_cell()`].
+    /// Each cell must be adjacent, orthogonally or diagonally, to the one before it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() {
+    /// # use sudoku_solver::*;
+    /// assert!(Thermometer::new(&[(4, 4), (5, 5), (5, 6)]).is_ok());
+    /// assert_eq!(
+    ///     Thermometer::new(&[(4, 4), (6, 4)]),
+    ///     Err(ThermometerError::NotAdjacent(6, 4))
+    /// );
+    /// # }
+    /// ```
+    pub fn new(cells: &[(usize, usize)]) -> Result<Self, ThermometerError> {
+        if cells.is_empty() {
+            return Err(ThermometerError::Empty);
+        }
+
+        if cells.len() > BOARD_SIZE {
+            return Err(ThermometerError::TooManyCells(cells.len()));
+        }
+
+        for (i, &(x, y)) in cells.iter().enumerate() {
+            if x >= BOARD_SIZE || y >= BOARD_SIZE {
+                return Err(ThermometerError::CellOutOfRange(x, y));
+            }
+
+            if cells[..i].contains(&(x, y)) {
+                return Err(ThermometerError::DuplicateCell(x, y));
+            }
+
+            if i > 0 {
+                let (px, py) = cells[i - 1];
+
+                if px.abs_diff(x) > 1 || py.abs_diff(y) > 1 {
+                    return Err(ThermometerError::NotAdjacent(x, y));
+                }
+            }
+        }
+
+        Ok(Self {
+            cells: cells.to_vec(),
+        })
+    }
+
+    /// The cells on the thermometer, from bulb to tip, as `(x, y)` pairs.
+    #[inline]
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+}
+
+impl Constraint for Thermometer {
+    fn candidates(&self, board: &Board, x: usize, y: usize) -> u16 {
+        let k = match self.cells.iter().position(|&c| c == (x, y)) {
+            Some(k) => k,
+            None => return ALL_CANDIDATES,
+        };
+
+        // A cell k steps from the bulb must leave room for k smaller digits before it, and for
+        // every cell after it to hold a larger digit.
+        let mut lo = k + 1;
+        let mut hi = BOARD_SIZE - (self.cells.len() - 1 - k);
+
+        for (j, &(cx, cy)) in self.cells.iter().enumerate() {
+            let v = board.get_cell(cx, cy) as usize;
+
+            if v == 0 {
+                continue;
+            }
+
+            if j < k {
+                lo = lo.max(v + (k - j));
+            } else if j > k {
+                hi = hi.min(v.saturating_sub(j - k));
+            }
+        }
+
+        if lo > hi {
+            0
+        } else {
+            // Bits lo..=hi.
+            ((1u16 << (hi + 1)) - 1) & !((1u16 << lo) - 1)
+        }
+    }
+
+    fn valid(&self, board: &Board) -> bool {
+        let n = self.cells.len();
+        let mut last: Option<(usize, usize)> = None;
+
+        for (k, &(x, y)) in self.cells.iter().enumerate() {
+            let v = board.get_cell(x, y) as usize;
+
+            if v == 0 {
+                continue;
+            }
+
+            if v < k + 1 || v > BOARD_SIZE - (n - 1 - k) {
+                return false;
+            }
+
+            if let Some((j, u)) = last {
+                if v < u + (k - j) {
+                    return false;
+                }
+            }
+
+            last = Some((k, v));
+        }
+
+        true
+    }
+}
@@ -0,0 +1,71 @@
+use super::*;
+
+#[test]
+fn invalid_thermometers_are_rejected() {
+    assert_eq!(Thermometer::new(&[]), Err(ThermometerError::Empty));
+    assert_eq!(
+        Thermometer::new(&[(8, 0), (9, 0)]),
+        Err(ThermometerError::CellOutOfRange(9, 0))
+    );
+    assert_eq!(
+        Thermometer::new(&[(0, 0), (1, 0), (0, 0)]),
+        Err(ThermometerError::DuplicateCell(0, 0))
+    );
+    assert_eq!(
+        Thermometer::new(&[(0, 0), (1, 1), (1, 3)]),
+        Err(ThermometerError::NotAdjacent(1, 3))
+    );
+
+    let cells: Vec<(usize, usize)> = (0..BOARD_SIZE + 1).map(|i| (i % 9, i / 9)).collect();
+    assert_eq!(
+        Thermometer::new(&cells),
+        Err(ThermometerError::TooManyCells(10))
+    );
+}
+
+#[test]
+fn candidates_are_pruned_by_position() {
+    let thermo = Thermometer::new(&[(0, 0), (1, 0), (2, 0), (3, 0)]).unwrap();
+    let mut board = Board::default();
+
+    assert_eq!(thermo.candidates(&board, 0, 0), 0b00_0111_1110); // {1, ..., 6}
+    assert_eq!(thermo.candidates(&board, 1, 0), 0b00_1111_1100); // {2, ..., 7}
+    assert_eq!(thermo.candidates(&board, 2, 0), 0b01_1111_1000); // {3, ..., 8}
+    assert_eq!(thermo.candidates(&board, 3, 0), 0b11_1111_0000); // {4, ..., 9}
+    assert_eq!(thermo.candidates(&board, 4, 0), ALL_CANDIDATES);
+
+    board.set_cell(0, 0, 3);
+    board.set_cell(3, 0, 7);
+
+    assert_eq!(thermo.candidates(&board, 1, 0), 0b00_0011_0000); // {4, 5}
+    assert_eq!(thermo.candidates(&board, 2, 0), 0b00_0110_0000); // {5, 6}
+
+    board.set_cell(3, 0, 5);
+    assert_eq!(thermo.candidates(&board, 1, 0), 0);
+}
+
+#[test]
+fn violations_are_detected() {
+    let thermo = Thermometer::new(&[(0, 0), (1, 0), (2, 0), (3, 0)]).unwrap();
+    let mut board = Board::default();
+    assert!(thermo.valid(&board));
+
+    board.set_cell(1, 0, 2);
+    board.set_cell(3, 0, 4);
+    assert!(thermo.valid(&board));
+
+    // The cell between would have to hold a digit strictly between 2 and 3, which is impossible.
+    board.set_cell(3, 0, 3);
+    assert!(!thermo.valid(&board));
+
+    // The tip of a four-cell thermometer cannot hold a 3.
+    let mut board = Board::default();
+    board.set_cell(3, 0, 3);
+    assert!(!thermo.valid(&board));
+
+    // Digits must strictly increase.
+    let mut board = Board::default();
+    board.set_cell(0, 0, 5);
+    board.set_cell(1, 0, 5);
+    assert!(!thermo.valid(&board));
+}